@@ -18,27 +18,96 @@
 //! This module provides state for the media browser widget, managing selected
 //! artist, album and tracks, and navigating between the various panes in the
 //! UI.
+//!
+//! The catalog can be browsed through several hierarchies, each of which shows
+//! its own sequence of panes.
 
 use ratatui::widgets::ListState;
 
-use crate::model::{Album, Artist, Track};
+use crate::model::{Album, Artist, Genre, Track};
 
-#[derive(Default, Eq, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
 pub(crate) enum MediaBrowserPane {
     #[default]
     Artist,
     Album,
-    Track
+    Track,
+    Genre,
+    Decade,
+    Year,
+}
+
+/// The ways of browsing the catalog.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum BrowseHierarchy {
+    /// Artist, then album, then track.
+    #[default]
+    Artist,
+    /// Genre, then artist, then album.
+    Genre,
+    /// Decade, then year, then album.
+    Decade,
+    /// Recently added album, then track.
+    RecentlyAdded,
+}
+
+impl BrowseHierarchy {
+    /// Returns the panes shown for this hierarchy, from left to right.
+    pub(crate) fn panes(self) -> &'static [MediaBrowserPane] {
+        match self {
+            BrowseHierarchy::Artist => &[
+                MediaBrowserPane::Artist,
+                MediaBrowserPane::Album,
+                MediaBrowserPane::Track,
+            ],
+            BrowseHierarchy::Genre => &[
+                MediaBrowserPane::Genre,
+                MediaBrowserPane::Artist,
+                MediaBrowserPane::Album,
+            ],
+            BrowseHierarchy::Decade => &[
+                MediaBrowserPane::Decade,
+                MediaBrowserPane::Year,
+                MediaBrowserPane::Album,
+            ],
+            BrowseHierarchy::RecentlyAdded => &[MediaBrowserPane::Album, MediaBrowserPane::Track],
+        }
+    }
+
+    pub(crate) fn title(self) -> &'static str {
+        match self {
+            BrowseHierarchy::Artist => "Artists",
+            BrowseHierarchy::Genre => "Genres",
+            BrowseHierarchy::Decade => "Decades",
+            BrowseHierarchy::RecentlyAdded => "Recently Added",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            BrowseHierarchy::Artist => BrowseHierarchy::Genre,
+            BrowseHierarchy::Genre => BrowseHierarchy::Decade,
+            BrowseHierarchy::Decade => BrowseHierarchy::RecentlyAdded,
+            BrowseHierarchy::RecentlyAdded => BrowseHierarchy::Artist,
+        }
+    }
 }
 
 #[derive(Default)]
 pub(crate) struct MediaBrowser {
+    pub(crate) hierarchy: BrowseHierarchy,
     pub(crate) active_pane: MediaBrowserPane,
 
+    pub(crate) genres: Vec<Genre>,
+    pub(crate) decades: Vec<i64>,
+    pub(crate) years: Vec<i64>,
     pub(crate) artists: Vec<Artist>,
     pub(crate) albums: Vec<Album>,
     pub(crate) tracks: Vec<Track>,
 
+    pub(crate) genres_state: ListState,
+    pub(crate) decades_state: ListState,
+    pub(crate) years_state: ListState,
     pub(crate) artists_state: ListState,
     pub(crate) albums_state: ListState,
     pub(crate) tracks_state: ListState,
@@ -53,20 +122,50 @@ impl MediaBrowser {
         self.active_pane = pane;
     }
 
+    /// Switches to the next way of browsing, emptying all of the panes ready
+    /// for the new hierarchy to be fetched.
+    pub(crate) fn next_hierarchy(&mut self) {
+        self.hierarchy = self.hierarchy.next();
+        self.active_pane = self.hierarchy.panes()[0];
+
+        self.set_genres(vec![]);
+        self.set_decades(vec![]);
+        self.set_years(vec![]);
+        self.set_artists(vec![]);
+        self.set_albums(vec![]);
+        self.set_tracks(vec![]);
+    }
+
+    /// Returns `true` if the current hierarchy shows a pane.
+    pub(crate) fn shows_pane(&self, pane: MediaBrowserPane) -> bool {
+        self.hierarchy.panes().contains(&pane)
+    }
+
     pub(crate) fn next_pane(&mut self) {
-        self.active_pane = match self.active_pane {
-            MediaBrowserPane::Artist => MediaBrowserPane::Album,
-            MediaBrowserPane::Album => MediaBrowserPane::Track,
-            MediaBrowserPane::Track => MediaBrowserPane::Artist,
-        };
+        let panes = self.hierarchy.panes();
+        let index = panes.iter().position(|&p| p == self.active_pane).unwrap_or(0);
+        self.active_pane = panes[(index + 1) % panes.len()];
     }
 
     pub(crate) fn previous_pane(&mut self) {
-        self.active_pane = match self.active_pane {
-            MediaBrowserPane::Artist => MediaBrowserPane::Track,
-            MediaBrowserPane::Album => MediaBrowserPane::Artist,
-            MediaBrowserPane::Track => MediaBrowserPane::Album,
-        };
+        let panes = self.hierarchy.panes();
+        let index = panes.iter().position(|&p| p == self.active_pane).unwrap_or(0);
+        self.active_pane = panes[(index + panes.len() - 1) % panes.len()];
+    }
+
+    pub(crate) fn selected_genre_id(&self) -> Option<i32> {
+        let index = self.genres_state.selected()?;
+        self.genres.get(index).map(|genre| genre.id)
+    }
+
+    pub(crate) fn selected_decade(&self) -> Option<i64> {
+        let index = self.decades_state.selected()?;
+        self.decades.get(index).copied()
+    }
+
+    pub(crate) fn selected_year(&self) -> Option<i64> {
+        let index = self.years_state.selected()?;
+        self.years.get(index).copied()
     }
 
     pub(crate) fn selected_artist_id(&self) -> Option<i32> {
@@ -84,6 +183,15 @@ impl MediaBrowser {
         self.tracks.get(index).map(|track| track.id)
     }
 
+    pub(crate) fn next_genre(&mut self) { Self::next(&mut self.genres_state, self.genres.len()); }
+    pub(crate) fn previous_genre(&mut self) { Self::previous(&mut self.genres_state, self.genres.len()); }
+
+    pub(crate) fn next_decade(&mut self) { Self::next(&mut self.decades_state, self.decades.len()); }
+    pub(crate) fn previous_decade(&mut self) { Self::previous(&mut self.decades_state, self.decades.len()); }
+
+    pub(crate) fn next_year(&mut self) { Self::next(&mut self.years_state, self.years.len()); }
+    pub(crate) fn previous_year(&mut self) { Self::previous(&mut self.years_state, self.years.len()); }
+
     pub(crate) fn next_artist(&mut self) { Self::next(&mut self.artists_state, self.artists.len()); }
     pub(crate) fn previous_artist(&mut self) { Self::previous(&mut self.artists_state, self.artists.len()); }
 
@@ -93,6 +201,21 @@ impl MediaBrowser {
     pub(crate) fn next_track(&mut self) { Self::next(&mut self.tracks_state, self.tracks.len()); }
     pub(crate) fn previous_track(&mut self) { Self::previous(&mut self.tracks_state, self.tracks.len()); }
 
+    pub(crate) fn set_genres(&mut self, genres: Vec<Genre>) {
+        self.genres = genres;
+        self.genres_state.select((!self.genres.is_empty()).then_some(0));
+    }
+
+    pub(crate) fn set_decades(&mut self, decades: Vec<i64>) {
+        self.decades = decades;
+        self.decades_state.select((!self.decades.is_empty()).then_some(0));
+    }
+
+    pub(crate) fn set_years(&mut self, years: Vec<i64>) {
+        self.years = years;
+        self.years_state.select((!self.years.is_empty()).then_some(0));
+    }
+
     pub(crate) fn set_artists(&mut self, artists: Vec<Artist>) {
         self.artists = artists;
         self.artists_state.select((!self.artists.is_empty()).then_some(0));
@@ -26,7 +26,17 @@ use anyhow::Result;
 use crossterm::event::{Event, KeyCode};
 use tui_input::{Input, backend::crossterm::EventHandler};
 
-use crate::{MainView, RepeatMode, events::AppEvent, model::{Recency, SearchQuery}, tasks::AppTask};
+use crate::{
+    MainView, RepeatMode,
+    events::AppEvent,
+    model::{
+        PlayFilter, Recency, SearchOrder, SearchQuery, StarRating,
+        queue::ShuffleMode,
+        sleep::{SleepAction, SleepTarget},
+    },
+    tasks::AppTask,
+    util::format::parse_duration,
+};
 
 pub(crate) struct Commander {
     active: bool,
@@ -133,6 +143,12 @@ impl Commander {
                 let query = SearchQuery::for_recency(Recency::LastMonth);
                 task_tx.send(AppTask::Search(query))?
             }
+            ["fp", terms @ ..] => match parse_play_query(terms) {
+                Ok(query) => task_tx.send(AppTask::Search(query))?,
+                Err(term) => {
+                    event_tx.send(AppEvent::Error(format!("Invalid search term: {term}")))?
+                }
+            },
 
             ["far", artist_parts @ ..] => {
                 if !artist_parts.is_empty() {
@@ -195,12 +211,36 @@ impl Commander {
 
             ["re"] => event_tx.send(AppEvent::ResetEqualizer)?,
 
-            ["md"] => {} // mode default
-            ["ms"] => {} // mode shuffle
+            ["md"] => event_tx.send(AppEvent::SetShuffleMode(ShuffleMode::Off))?,
+            ["ms"] => event_tx.send(AppEvent::SetShuffleMode(ShuffleMode::Track))?,
+            ["ms", mode] => {
+                let mode = match *mode {
+                    "track" => Some(ShuffleMode::Track),
+                    "album" => Some(ShuffleMode::Album),
+                    "weighted" => Some(ShuffleMode::Weighted),
+                    _ => None,
+                };
+
+                if let Some(mode) = mode {
+                    event_tx.send(AppEvent::SetShuffleMode(mode))?
+                }
+            }
 
             ["sq"] => event_tx.send(AppEvent::ShuffleQueue)?,
             ["rq"] => event_tx.send(AppEvent::ResetQueue)?,
 
+            ["stars", "off"] => event_tx.send(AppEvent::RateSelectedStars(None))?,
+            ["stars", value] => match parse_stars(value) {
+                Some(stars) => event_tx.send(AppEvent::RateSelectedStars(Some(stars)))?,
+                None => event_tx.send(AppEvent::Error(format!("Invalid star rating: {value}")))?,
+            },
+
+            ["dj", "on"] => event_tx.send(AppEvent::SetAutoDj(true))?,
+            ["dj", "off"] => event_tx.send(AppEvent::SetAutoDj(false))?,
+
+            ["undo"] => event_tx.send(AppEvent::UndoQueue)?,
+            ["redo"] => event_tx.send(AppEvent::RedoQueue)?,
+
             ["r0"] => event_tx.send(AppEvent::SetRepeatMode(RepeatMode::NoRepeat))?,
             ["r1"] => event_tx.send(AppEvent::SetRepeatMode(RepeatMode::RepeatOne))?,
             ["ra"] => event_tx.send(AppEvent::SetRepeatMode(RepeatMode::RepeatAll))?,
@@ -223,6 +263,68 @@ impl Commander {
 
             ["pq"] => event_tx.send(AppEvent::PlayPlaylist)?,
 
+            ["sleep", "off"] => event_tx.send(AppEvent::CancelSleepTimer)?,
+            ["sleep", target, options @ ..] => {
+                let target = match *target {
+                    "end-of-track" => Some(SleepTarget::EndOfTrack),
+                    "end-of-queue" => Some(SleepTarget::EndOfQueue),
+                    duration => parse_duration(duration).map(SleepTarget::After),
+                };
+
+                let action = if options.contains(&"quit") {
+                    SleepAction::Quit
+                } else {
+                    SleepAction::Stop
+                };
+
+                if let Some(target) = target {
+                    event_tx.send(AppEvent::SetSleepTimer(target, action))?
+                } else {
+                    // error
+                }
+            }
+
+            ["bm"] => event_tx.send(AppEvent::AddBookmark(None))?,
+            ["bm", name_parts @ ..] => {
+                let name = name_parts.join(" ");
+                event_tx.send(AppEvent::AddBookmark(Some(name)))?
+            }
+            ["bn"] => event_tx.send(AppEvent::NextBookmark)?,
+            ["bp"] => event_tx.send(AppEvent::PreviousBookmark)?,
+            ["bml"] => event_tx.send(AppEvent::SetMainView(MainView::Bookmarks))?,
+
+            ["hl"] => event_tx.send(AppEvent::SetMainView(MainView::History))?,
+
+            ["st"] => {
+                task_tx.send(AppTask::GetStats)?;
+                event_tx.send(AppEvent::SetMainView(MainView::Stats))?
+            }
+
+            ["organise"] => {
+                task_tx.send(AppTask::PreviewOrganise)?;
+                event_tx.send(AppEvent::SetMainView(MainView::Organise))?
+            }
+            ["organise", "apply"] => {
+                event_tx.send(AppEvent::ApplyOrganise)?;
+                event_tx.send(AppEvent::SetMainView(MainView::Organise))?
+            }
+
+            ["sa", url, name_parts @ ..] => {
+                let name = if name_parts.is_empty() {
+                    url.to_string()
+                } else {
+                    name_parts.join(" ")
+                };
+                task_tx.send(AppTask::AddStation(name, url.to_string()))?
+            }
+            ["sl"] => event_tx.send(AppEvent::SetMainView(MainView::Stations))?,
+
+            ["ps", url] => task_tx.send(AppTask::SubscribePodcast(url.to_string()))?,
+            ["pr"] => task_tx.send(AppTask::RefreshPodcasts)?,
+            ["pl"] => event_tx.send(AppEvent::SetMainView(MainView::Podcasts))?,
+
+            ["lyrics"] => event_tx.send(AppEvent::SetMainView(MainView::Lyrics))?,
+
             // maybe vq vb vs vf vc etc?
             ["0"] => event_tx.send(AppEvent::SetMainView(MainView::Help))?,
             ["1"] => event_tx.send(AppEvent::SetMainView(MainView::Playlist))?,
@@ -231,6 +333,9 @@ impl Commander {
             ["4"] => event_tx.send(AppEvent::SetMainView(MainView::Favourites))?,
             ["5"] => event_tx.send(AppEvent::SetMainView(MainView::Equalizer))?,
             ["6"] => event_tx.send(AppEvent::SetMainView(MainView::Catalog))?,
+            ["7"] => event_tx.send(AppEvent::SetMainView(MainView::Bookmarks))?,
+            ["8"] => event_tx.send(AppEvent::SetMainView(MainView::Stations))?,
+            ["9"] => event_tx.send(AppEvent::SetMainView(MainView::Podcasts))?,
 
             ["repeat", mode_str] => {
                 let mode = match mode_str.to_lowercase().as_str() {
@@ -256,3 +361,69 @@ impl Commander {
         Ok(())
     }
 }
+
+/// Builds a search query from filters on play statistics, words in the
+/// lyrics and a sort order, for example `plays>10 played>30d lyrics:rain
+/// sort:plays`.
+///
+/// Underscores in `lyrics:` terms stand for spaces, to search for phrases. Any
+/// other terms are searched for as text.
+///
+/// # Errors
+///
+/// Returns the first term that is not understood.
+fn parse_play_query(terms: &[&str]) -> Result<SearchQuery, String> {
+    let mut query = SearchQuery::default();
+    let mut text = Vec::new();
+
+    for term in terms {
+        if let Some(words) = term.strip_prefix("lyrics:") {
+            if words.is_empty() {
+                return Err(term.to_string());
+            }
+            query.lyrics.push(words.replace('_', " "));
+        } else if let Some(order) = term.strip_prefix("sort:") {
+            query.order = match order {
+                "plays" => SearchOrder::MostPlayed,
+                "skips" => SearchOrder::MostSkipped,
+                "recent" => SearchOrder::RecentlyPlayed,
+                "oldest" => SearchOrder::LeastRecentlyPlayed,
+                _ => return Err(term.to_string()),
+            };
+        } else if let Some(index) = term.find(['<', '>']) {
+            let (field, rest) = term.split_at(index);
+            let (op, value) = rest.split_at(1);
+            let filter = parse_play_filter(field, op, value).ok_or_else(|| term.to_string())?;
+            query.play_filters.push(filter);
+        } else {
+            text.push(*term);
+        }
+    }
+
+    query.search = text.join(" ");
+    Ok(query)
+}
+
+fn parse_play_filter(field: &str, op: &str, value: &str) -> Option<PlayFilter> {
+    let filter = match (field, op) {
+        ("plays", ">") => PlayFilter::MorePlaysThan(value.parse().ok()?),
+        ("plays", "<") => PlayFilter::FewerPlaysThan(value.parse().ok()?),
+        ("skips", ">") => PlayFilter::MoreSkipsThan(value.parse().ok()?),
+        ("skips", "<") => PlayFilter::FewerSkipsThan(value.parse().ok()?),
+        ("played", "<") => PlayFilter::PlayedWithin(parse_duration(value)?.as_secs()),
+        ("played", ">") => PlayFilter::NotPlayedWithin(parse_duration(value)?.as_secs()),
+        _ => return None,
+    };
+
+    Some(filter)
+}
+
+/// Parses a number of stars from half a star to five, such as `3.5`.
+fn parse_stars(value: &str) -> Option<StarRating> {
+    let stars: f64 = value.parse().ok()?;
+    let half_stars = stars * 2.0;
+    if half_stars.fract() != 0.0 {
+        return None;
+    }
+    StarRating::from_half_stars(u8::try_from(half_stars as i64).ok()?)
+}
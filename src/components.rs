@@ -18,18 +18,34 @@
 //! This module acts as a central export point for reusable interface elements,
 //! organizing complex widgets into separately maintainable sub-modules.
 
+pub(crate) mod bookmarks;
 pub(crate) mod catalog;
 pub(crate) mod equalizer;
 pub(crate) mod favourites;
 pub(crate) mod help;
+pub(crate) mod history;
+pub(crate) mod lyrics;
+pub(crate) mod organise;
 pub(crate) mod playlist;
+pub(crate) mod podcasts;
 pub(crate) mod search;
+pub(crate) mod stations;
+pub(crate) mod stats;
+pub(crate) mod tag_editor;
 pub(crate) mod track_table;
 
+pub(crate) use bookmarks::*;
 pub(crate) use catalog::*;
 pub(crate) use equalizer::*;
 pub(crate) use favourites::*;
 pub(crate) use help::*;
+pub(crate) use history::*;
+pub(crate) use lyrics::*;
+pub(crate) use organise::*;
 pub(crate) use playlist::*;
+pub(crate) use podcasts::*;
 pub(crate) use search::*;
+pub(crate) use stations::*;
+pub(crate) use stats::*;
+pub(crate) use tag_editor::*;
 pub(crate) use track_table::*;
@@ -0,0 +1,119 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! UI rendering logic for the bookmarks view.
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout},
+    prelude::Rect,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Padding, Paragraph, Row, Table},
+};
+
+use crate::{
+    components::BookmarksView,
+    model::TrackInfo,
+    theme::Theme,
+    util::format::{TimeFormat, format_time},
+};
+
+impl BookmarksView {
+    pub(crate) fn draw(
+        &mut self,
+        f: &mut Frame,
+        area: Rect,
+        now_playing: Option<&TrackInfo>,
+        theme: &Theme,
+    ) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(0)])
+            .split(area);
+
+        let header_block = Block::default()
+            .borders(Borders::BOTTOM)
+            .padding(Padding::horizontal(1));
+
+        let bookmarks = self.bookmarks.lock().unwrap();
+
+        let header_text = format!("Bookmarks | {} bookmarks", bookmarks.len());
+        let header = Paragraph::new(header_text).block(header_block);
+        f.render_widget(header, chunks[0]);
+
+        let playing_id = now_playing.map(|track| track.durable_id);
+
+        let rows = bookmarks.iter().map(|bookmark| {
+            let track = &bookmark.track;
+
+            // Bookmarks in the track that is currently playing are highlighted
+            let name_style = if Some(track.durable_id) == playing_id {
+                Style::default()
+                    .fg(theme.accent_colour)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.table_track_fg)
+            };
+
+            Row::new(vec![
+                Cell::from(
+                    Line::from(format_time(bookmark.position, TimeFormat::Minutes))
+                        .style(Style::default().fg(theme.table_time_fg))
+                        .alignment(Alignment::Right),
+                ),
+                Cell::from(Line::from(bookmark.name.as_str()).style(name_style)),
+                Cell::from(
+                    Line::from(track.track_title.as_str())
+                        .style(Style::default().fg(theme.table_track_fg)),
+                ),
+                Cell::from(
+                    Line::from(track.artist_name.as_str())
+                        .style(Style::default().fg(theme.table_artist_fg)),
+                ),
+                Cell::from(
+                    Line::from(track.album_title.as_str())
+                        .style(Style::default().fg(theme.table_album_fg)),
+                ),
+            ])
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Fill(25),
+                Constraint::Fill(35),
+                Constraint::Fill(20),
+                Constraint::Fill(20),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                Cell::from(Line::from("Position").alignment(Alignment::Right)),
+                Cell::from("Bookmark"),
+                Cell::from("Title"),
+                Cell::from("Artist"),
+                Cell::from("Album"),
+            ])
+            .style(Style::default().bold().fg(theme.accent_colour))
+            .bottom_margin(1),
+        )
+        .row_highlight_style(Style::default().bg(Color::Blue).fg(Color::White))
+        .block(Block::default().padding(Padding::horizontal(1)));
+
+        f.render_stateful_widget(table, chunks[1], &mut self.table_state);
+    }
+}
@@ -23,9 +23,13 @@ use std::sync::mpsc::Sender;
 
 use crossterm::event::{Event, KeyCode};
 
-use crate::{events::AppEvent, model::equalizer::Equalizer, tasks::AppTask};
+use crate::{
+    events::AppEvent,
+    model::equalizer::{Equalizer, MAX_BALANCE, MIN_BALANCE},
+    tasks::AppTask,
+};
 
-use super::{AMP_STEP, EqualizerSelection, EqualizerView, MAX_AMP, MIN_AMP};
+use super::{AMP_STEP, BALANCE_STEP, EqualizerSelection, EqualizerView, MAX_AMP, MIN_AMP};
 
 impl EqualizerView {
     pub(crate) fn process_event(
@@ -76,6 +80,29 @@ impl EqualizerView {
                     self.set_selected_amp(0.0, event_tx, equalizer);
                 }
 
+                // Channel mixing
+                (KeyCode::Char('('), _) => {
+                    self.adjust_balance(-BALANCE_STEP, event_tx, equalizer)?;
+                }
+                (KeyCode::Char(')'), _) => {
+                    self.adjust_balance(BALANCE_STEP, event_tx, equalizer)?;
+                }
+                (KeyCode::Char('|'), _) => {
+                    let mut channel_mix = equalizer.channel_mix;
+                    channel_mix.balance = 0;
+                    event_tx.send(AppEvent::SetChannelMix(channel_mix))?;
+                }
+                (KeyCode::Char('M'), _) => {
+                    let mut channel_mix = equalizer.channel_mix;
+                    channel_mix.mono = !channel_mix.mono;
+                    event_tx.send(AppEvent::SetChannelMix(channel_mix))?;
+                }
+                (KeyCode::Char('w'), _) => {
+                    let mut channel_mix = equalizer.channel_mix;
+                    channel_mix.swap = !channel_mix.swap;
+                    event_tx.send(AppEvent::SetChannelMix(channel_mix))?;
+                }
+
                 _ => {}
             },
 
@@ -109,6 +136,19 @@ impl EqualizerView {
         }
     }
 
+    /// Shift the stereo balance by the given delta, negative towards the left.
+    fn adjust_balance(
+        &mut self,
+        delta: i32,
+        event_tx: &Sender<AppEvent>,
+        equalizer: &Equalizer,
+    ) -> Result<()> {
+        let mut channel_mix = equalizer.channel_mix;
+        channel_mix.balance = (channel_mix.balance + delta).clamp(MIN_BALANCE, MAX_BALANCE);
+        event_tx.send(AppEvent::SetChannelMix(channel_mix))?;
+        Ok(())
+    }
+
     /// Get the index and current value of the selected amp.
     /// Returns (0, preamp_value) for preamp, or (band_index+1, gain_value) for bands.
     fn get_current_amp_values(&self, equalizer: &Equalizer) -> (usize, f64) {
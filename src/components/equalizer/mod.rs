@@ -5,6 +5,7 @@ const BANDS: usize = 18;
 pub(crate) const MIN_AMP: f64 = -20.0;
 pub(crate) const MAX_AMP: f64 = 20.0;
 const AMP_STEP: f64 = 1.0;
+const BALANCE_STEP: i32 = 10;
 
 /// Represents which equalizer control is currently selected.
 #[derive(Debug, Clone, Copy, PartialEq)]
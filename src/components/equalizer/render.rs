@@ -44,8 +44,22 @@ impl EqualizerView {
             .constraints([Constraint::Length(2), Constraint::Min(0)])
             .split(area);
 
+        let channel_mix = &equalizer.channel_mix;
+        let balance = match channel_mix.balance {
+            0 => "Centre".to_string(),
+            b if b < 0 => format!("L {}", -b),
+            b => format!("R {}", b),
+        };
+        let on_off = |enabled: bool| if enabled { "On" } else { "Off" };
+        let header_text = format!(
+            "Equalizer Settings | Balance: {} | Mono: {} | Swap L/R: {}",
+            balance,
+            on_off(channel_mix.mono),
+            on_off(channel_mix.swap)
+        );
+
         f.render_widget(
-            Paragraph::new("Equalizer Settings").block(
+            Paragraph::new(header_text).block(
                 Block::default()
                     .borders(Borders::BOTTOM)
                     .padding(Padding::horizontal(1)),
@@ -0,0 +1,99 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Event routing for the favourites view.
+
+use std::sync::mpsc::Sender;
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyModifiers};
+
+use crate::{
+    components::{FavouritesView, TrackTableAction},
+    events::AppEvent,
+    model::Rating,
+    tasks::AppTask,
+};
+
+impl FavouritesView {
+    pub(crate) fn process_event(
+        &mut self,
+        event: &Event,
+        task_tx: &Sender<AppTask>,
+        event_tx: &Sender<AppEvent>,
+    ) -> Result<()> {
+        if !self.is_active {
+            return Ok(());
+        }
+
+        if let Some(action) = self.track_table.process_event(event) {
+            if let TrackTableAction::CommitSelection(track_ids) = action {
+                let tracks = self.track_table.clone_tracks(track_ids);
+                event_tx.send(AppEvent::AddTracksToPlaylist(tracks))?;
+            }
+            return Ok(());
+        }
+
+        if let Event::Key(key_event) = event {
+            match (key_event.code, key_event.modifiers) {
+                (KeyCode::Char(']'), _) => {
+                    if let Some(track) = self.track_table.clone_current() {
+                        task_tx.send(AppTask::RateTrack(track, Rating::Like))?;
+                    }
+                }
+
+                (KeyCode::Char('['), _) => {
+                    if let Some(track) = self.track_table.clone_current() {
+                        task_tx.send(AppTask::RateTrack(track, Rating::Dislike))?;
+                    }
+                }
+
+                (KeyCode::Char('x'), _) => {
+                    if let Some(track) = self.track_table.clone_current() {
+                        task_tx.send(AppTask::RateTrack(track, Rating::Neutral))?;
+                    }
+                }
+
+                (KeyCode::Char('p'), _) => {
+                    if let Some(track) = self.track_table.clone_current() {
+                        task_tx.send(AppTask::PlayTrack(track))?;
+                    }
+                }
+
+                (KeyCode::Char('e'), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                    let tracks = self.track_table.clone_selected_or_current();
+                    if !tracks.is_empty() {
+                        task_tx.send(AppTask::ReadTags(tracks))?;
+                    }
+                }
+
+                (KeyCode::Char('o'), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                    self.order = self.order.next();
+                    self.refresh(task_tx)?;
+                }
+
+                (KeyCode::Char('d'), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                    self.show_disliked = !self.show_disliked;
+                    self.track_table.reset_table_selection();
+                    self.refresh(task_tx)?;
+                }
+
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,11 +1,60 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Favourite tracks view, listing liked tracks, or disliked tracks for
+//! cleaning up.
+
+mod event;
 mod render;
 
+use std::sync::{Arc, Mutex, mpsc::Sender};
+
+use anyhow::Result;
+
+use crate::{
+    components::TrackTable,
+    model::{FavouritesOrder, Rating, TrackInfo},
+    tasks::AppTask,
+};
+
 pub(crate) struct FavouritesView {
+    pub(crate) track_table: TrackTable,
+    order: FavouritesOrder,
+    show_disliked: bool,
     pub(crate) is_active: bool,
 }
 
 impl FavouritesView {
-    pub(crate) fn new() -> Self {
-        Self { is_active: false }
+    pub(crate) fn new(tracks: Arc<Mutex<Vec<TrackInfo>>>) -> Self {
+        Self {
+            track_table: TrackTable::new(tracks),
+            order: FavouritesOrder::default(),
+            show_disliked: false,
+            is_active: false,
+        }
+    }
+
+    /// Fetches the tracks to list again, with the current rating and order.
+    pub(crate) fn refresh(&self, task_tx: &Sender<AppTask>) -> Result<()> {
+        let rating = if self.show_disliked {
+            Rating::Dislike
+        } else {
+            Rating::Like
+        };
+        task_tx.send(AppTask::GetFavourites(rating, self.order))?;
+
+        Ok(())
     }
 }
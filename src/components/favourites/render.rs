@@ -15,6 +15,8 @@
 
 //! UI rendering logic for the favourites view.
 
+use std::fmt::Write;
+
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout},
@@ -22,10 +24,21 @@ use ratatui::{
     widgets::{Block, Borders, Padding, Paragraph},
 };
 
-use crate::{components::FavouritesView, theme::Theme};
+use crate::{
+    components::FavouritesView,
+    model::{FavouritesOrder, Rating, favourites::Favourites},
+    render::Render,
+    theme::Theme,
+};
 
 impl FavouritesView {
-    pub(crate) fn draw(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
+    pub(crate) fn draw(
+        &mut self,
+        f: &mut Frame,
+        area: Rect,
+        favourites: &Favourites,
+        theme: &Theme,
+    ) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Length(2), Constraint::Min(0)])
@@ -35,10 +48,30 @@ impl FavouritesView {
             .borders(Borders::BOTTOM)
             .padding(Padding::horizontal(1));
 
-        let mut header_text = "Favourites";
+        let track_count = favourites.tracks().lock().unwrap().len();
+        let selected_count = self.track_table.selected_count();
+
+        let rated = match favourites.rating() {
+            Rating::Dislike => "disliked",
+            _ => "liked",
+        };
+        let order = match self.order {
+            FavouritesOrder::RatedAt => format!("date {}", rated),
+            FavouritesOrder::Artist => "artist".to_string(),
+            FavouritesOrder::Album => "album".to_string(),
+        };
+
+        let mut header_text = format!(
+            "Favourites | {} {} tracks | by {}",
+            track_count, rated, order
+        );
+        if selected_count > 0 {
+            let _ = write!(header_text, " | {} selected", selected_count);
+        }
 
         let header = Paragraph::new(header_text).block(header_block);
 
         f.render_widget(header, chunks[0]);
+        self.track_table.draw(f, chunks[1], theme);
     }
 }
@@ -94,6 +94,9 @@ fn build_left_hotkeys() -> Vec<Line<'static>> {
     kv(&mut lines, "4", "Favourites");
     kv(&mut lines, "5", "Equalizer");
     kv(&mut lines, "6", "Catalog");
+    kv(&mut lines, "7", "Bookmarks");
+    kv(&mut lines, "8", "Stations");
+    kv(&mut lines, "9", "Podcasts");
     blank(&mut lines);
 
     section_title(&mut lines, "Navigation");
@@ -108,6 +111,7 @@ fn build_left_hotkeys() -> Vec<Line<'static>> {
     kv(&mut lines, "Esc", "Exit command mode");
     kv(&mut lines, "]", "Like track");
     kv(&mut lines, "[", "Dislike track");
+    kv(&mut lines, "e", "Edit tags of selection");
 
     lines
 }
@@ -130,6 +134,19 @@ fn build_right_hotkeys() -> Vec<Line<'static>> {
     kv(&mut lines, "m", "Mute");
     kv(&mut lines, "a", "Add selection to queue");
     kv(&mut lines, "c", "Clear queue");
+    kv(&mut lines, "u", "Undo queue edit");
+    kv(&mut lines, "Ctrl-r", "Redo queue edit");
+    blank(&mut lines);
+
+    section_title(&mut lines, "Playlist (View Specific)");
+    kv(&mut lines, "Enter", "Play from here");
+    kv(&mut lines, "K / J", "Move selection up / down");
+    kv(&mut lines, "N", "Play selection next");
+    kv(&mut lines, "d / Del", "Remove selection");
+    blank(&mut lines);
+
+    section_title(&mut lines, "Browse (View Specific)");
+    kv(&mut lines, "b", "Browse by artist, genre, decade or recent");
     blank(&mut lines);
 
     section_title(&mut lines, "Equalizer (View Specific)");
@@ -139,6 +156,55 @@ fn build_right_hotkeys() -> Vec<Line<'static>> {
     kv(&mut lines, "Down / L", "Decrease gain");
     kv(&mut lines, "g / G", "First / Last band");
     kv(&mut lines, "0", "Reset band");
+    kv(&mut lines, "( / )", "Balance left / right");
+    kv(&mut lines, "|", "Centre balance");
+    kv(&mut lines, "M", "Toggle mono");
+    kv(&mut lines, "w", "Swap left / right");
+    blank(&mut lines);
+
+    section_title(&mut lines, "Favourites (View Specific)");
+    kv(&mut lines, "p", "Play track");
+    kv(&mut lines, "x", "Clear rating");
+    kv(&mut lines, "o", "Sort by date rated, artist or album");
+    kv(&mut lines, "d", "Toggle liked / disliked tracks");
+    blank(&mut lines);
+
+    section_title(&mut lines, "Bookmarks (View Specific)");
+    kv(&mut lines, "Enter", "Jump to bookmark");
+    kv(&mut lines, "d", "Delete bookmark");
+    blank(&mut lines);
+
+    section_title(&mut lines, "History (View Specific)");
+    kv(&mut lines, "Enter", "Add track to queue");
+    blank(&mut lines);
+
+    section_title(&mut lines, "Stats (View Specific)");
+    kv(&mut lines, "r", "Refresh statistics");
+    blank(&mut lines);
+
+    section_title(&mut lines, "Stations (View Specific)");
+    kv(&mut lines, "Enter", "Play station");
+    kv(&mut lines, "d", "Delete station");
+    blank(&mut lines);
+
+    section_title(&mut lines, "Podcasts (View Specific)");
+    kv(&mut lines, "Enter", "Play episode (downloading if needed)");
+    kv(&mut lines, "D", "Download episode");
+    kv(&mut lines, "x", "Toggle played");
+    kv(&mut lines, "r", "Refresh feeds");
+    kv(&mut lines, "U", "Unsubscribe from podcast");
+    blank(&mut lines);
+
+    section_title(&mut lines, "Lyrics (View Specific)");
+    kv(&mut lines, "Enter", "Seek to selected line");
+    kv(&mut lines, "f", "Follow playback");
+    blank(&mut lines);
+
+    section_title(&mut lines, "Tag Editor");
+    kv(&mut lines, "Tab / Down", "Next field");
+    kv(&mut lines, "Shift-Tab / Up", "Previous field");
+    kv(&mut lines, "Enter", "Save tags to files");
+    kv(&mut lines, "Esc", "Cancel");
 
     lines
 }
@@ -179,7 +245,9 @@ fn build_left_commands() -> Vec<Line<'static>> {
     kv(&mut lines, "cq", "Clear queue");
     kv(&mut lines, "pq", "Play queue");
     kv(&mut lines, "sq", "Shuffle queue");
-    kv(&mut lines, "rq", "Reset queue");
+    kv(&mut lines, "rq", "Reset queue order");
+    kv(&mut lines, "undo", "Undo queue edit");
+    kv(&mut lines, "redo", "Redo queue edit");
     kv(&mut lines, "qar", "Add selected artist to queue");
     kv(&mut lines, "qal", "Add selected album to queue");
     kv(&mut lines, "qtr", "Add selected track to queue");
@@ -201,6 +269,23 @@ fn build_right_commands() -> Vec<Line<'static>> {
     kv(&mut lines, "fld", "Find added today");
     kv(&mut lines, "flw", "Find added this week");
     kv(&mut lines, "flm", "Find added this month");
+    kv(&mut lines, "fp <terms>", "Find by plays (plays>N, skips<N, played<7d)");
+    kv(&mut lines, "fp .. sort:<by>", "Sort by plays, skips, recent or oldest");
+    kv(&mut lines, "fp .. lyrics:<words>", "Find by lyrics (_ for spaces)");
+    blank(&mut lines);
+
+    section_title(&mut lines, "Ratings");
+    kv(&mut lines, "stars <0.5-5>", "Rate selected or playing track");
+    kv(&mut lines, "stars off", "Clear star rating");
+    blank(&mut lines);
+
+    section_title(&mut lines, "Lyrics");
+    kv(&mut lines, "lyrics", "Show lyrics of playing track");
+    blank(&mut lines);
+
+    section_title(&mut lines, "Organise");
+    kv(&mut lines, "organise", "Preview renaming files from tags");
+    kv(&mut lines, "organise apply", "Make the previewed moves");
     blank(&mut lines);
 
     section_title(&mut lines, "Repeat");
@@ -209,6 +294,46 @@ fn build_right_commands() -> Vec<Line<'static>> {
     kv(&mut lines, "ra", "Repeat: all");
     blank(&mut lines);
 
+    section_title(&mut lines, "Shuffle");
+    kv(&mut lines, "ms", "Shuffle: tracks");
+    kv(&mut lines, "ms album", "Shuffle: albums");
+    kv(&mut lines, "ms weighted", "Shuffle: favour liked, unplayed");
+    kv(&mut lines, "md", "Shuffle: off");
+    blank(&mut lines);
+
+    section_title(&mut lines, "Auto-DJ");
+    kv(&mut lines, "dj on", "Top up queue when it runs low");
+    kv(&mut lines, "dj off", "Stop topping up queue");
+    blank(&mut lines);
+
+    section_title(&mut lines, "Sleep Timer");
+    kv(&mut lines, "sleep <when>", "Fade out and stop (30m, end-of-track, end-of-queue)");
+    kv(&mut lines, "sleep .. quit", "Quit instead of stopping");
+    kv(&mut lines, "sleep off", "Cancel sleep timer");
+    blank(&mut lines);
+
+    section_title(&mut lines, "Bookmarks");
+    kv(&mut lines, "bm [name]", "Bookmark current position");
+    kv(&mut lines, "bn / bp", "Next / previous bookmark in track");
+    kv(&mut lines, "bml", "List bookmarks");
+    blank(&mut lines);
+
+    section_title(&mut lines, "History");
+    kv(&mut lines, "hl", "List recently played tracks");
+    kv(&mut lines, "st", "Show listening statistics");
+    blank(&mut lines);
+
+    section_title(&mut lines, "Internet Radio");
+    kv(&mut lines, "sa <url>", "Add station, optionally followed by a name");
+    kv(&mut lines, "sl", "List stations");
+    blank(&mut lines);
+
+    section_title(&mut lines, "Podcasts");
+    kv(&mut lines, "ps <url>", "Subscribe to podcast feed");
+    kv(&mut lines, "pr", "Refresh podcast feeds");
+    kv(&mut lines, "pl", "List podcast episodes");
+    blank(&mut lines);
+
     section_title(&mut lines, "Other");
     kv(&mut lines, "re", "Reset equalizer");
     kv(&mut lines, "scan", "Scan catalog");
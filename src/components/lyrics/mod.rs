@@ -0,0 +1,112 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//! Lyrics view.
+//!
+//! Synchronised lyrics follow playback, with the line being sung highlighted
+//! and kept in the middle of the view. Moving the selection stops following
+//! until a line is played from, or following is resumed.
+
+mod event;
+mod render;
+
+use std::time::Duration;
+
+use ratatui::widgets::ListState;
+
+use crate::model::lyrics::Lyrics;
+
+pub(crate) struct LyricsView {
+    list_state: ListState,
+
+    /// The line being sung.
+    current: Option<usize>,
+
+    /// Whether the selection follows the line being sung.
+    following: bool,
+
+    pub(crate) is_active: bool,
+}
+
+impl LyricsView {
+    pub(crate) fn new() -> Self {
+        Self {
+            list_state: ListState::default(),
+            current: None,
+            following: true,
+            is_active: false,
+        }
+    }
+
+    /// Starts again from the top, for new lyrics.
+    pub(crate) fn reset(&mut self) {
+        self.list_state = ListState::default();
+        self.current = None;
+        self.following = true;
+    }
+
+    /// Moves the highlight to the line being sung at the playback time.
+    pub(crate) fn time_changed(&mut self, lyrics: Option<&Lyrics>, time: Duration) {
+        self.current = lyrics.and_then(|lyrics| lyrics.line_at(time));
+        if self.following && self.current.is_some() {
+            self.list_state.select(self.current);
+        }
+    }
+
+    fn follow(&mut self) {
+        self.following = true;
+        self.list_state.select(self.current);
+    }
+
+    fn goto_next(&mut self, len: usize) {
+        if len == 0 {
+            return;
+        }
+        self.following = false;
+        let i = match self.list_state.selected() {
+            Some(i) if i < len - 1 => i + 1,
+            Some(i) => i,
+            None => 0,
+        };
+        self.list_state.select(Some(i));
+    }
+
+    fn goto_previous(&mut self, len: usize) {
+        if len == 0 {
+            return;
+        }
+        self.following = false;
+        let i = match self.list_state.selected() {
+            Some(i) => i.saturating_sub(1),
+            None => 0,
+        };
+        self.list_state.select(Some(i));
+    }
+
+    fn goto_first(&mut self) {
+        self.following = false;
+        self.list_state.select_first();
+    }
+
+    fn goto_last(&mut self, len: usize) {
+        self.following = false;
+        self.list_state.select(len.checked_sub(1));
+    }
+
+    /// The time the selected line is sung at, for synchronised lyrics.
+    fn selected_time(&self, lyrics: &Lyrics) -> Option<Duration> {
+        let index = self.list_state.selected()?;
+        lyrics.lines().get(index)?.time
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//! UI rendering logic for the lyrics view.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    prelude::Rect,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, List, ListItem, Padding, Paragraph},
+};
+
+use crate::{
+    components::LyricsView,
+    model::{TrackInfo, lyrics::Lyrics},
+    theme::Theme,
+};
+
+impl LyricsView {
+    pub(crate) fn draw(
+        &mut self,
+        f: &mut Frame,
+        area: Rect,
+        lyrics: Option<&Lyrics>,
+        now_playing: Option<&TrackInfo>,
+        theme: &Theme,
+    ) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(0)])
+            .split(area);
+
+        let header_block = Block::default()
+            .borders(Borders::BOTTOM)
+            .padding(Padding::horizontal(1));
+
+        let header_text = match (now_playing, lyrics) {
+            (Some(track), Some(lyrics)) if !lyrics.is_synced() => format!(
+                "Lyrics | {} by {} | Not synchronised",
+                track.track_title, track.artist_name
+            ),
+            (Some(track), _) => format!("Lyrics | {} by {}", track.track_title, track.artist_name),
+            (None, _) => "Lyrics".to_string(),
+        };
+        let header = Paragraph::new(header_text).block(header_block);
+        f.render_widget(header, chunks[0]);
+
+        let body_block = Block::default().padding(Padding::horizontal(1));
+
+        let Some(lyrics) = lyrics.filter(|_| now_playing.is_some()) else {
+            let message = if now_playing.is_some() {
+                "No lyrics found"
+            } else {
+                "Nothing playing"
+            };
+            let empty = Paragraph::new(message)
+                .style(Style::default().fg(theme.table_time_fg))
+                .block(body_block);
+            f.render_widget(empty, chunks[1]);
+            return;
+        };
+
+        let items: Vec<ListItem> = lyrics
+            .lines()
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                let style = if Some(index) == self.current {
+                    Style::default()
+                        .fg(theme.accent_colour)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.table_track_fg)
+                };
+                ListItem::new(Line::from(line.text.as_str()).style(style))
+            })
+            .collect();
+
+        // While following, the line being sung is kept in the middle of the
+        // view rather than scrolled to the bottom edge
+        if self.following
+            && let Some(current) = self.current
+        {
+            let half_height = usize::from(chunks[1].height / 2);
+            *self.list_state.offset_mut() = current.saturating_sub(half_height);
+        }
+
+        let highlight_style = if self.following {
+            Style::default()
+        } else {
+            Style::default().bg(Color::Blue).fg(Color::White)
+        };
+
+        let list = List::new(items)
+            .highlight_style(highlight_style)
+            .block(body_block);
+
+        f.render_stateful_widget(list, chunks[1], &mut self.list_state);
+    }
+}
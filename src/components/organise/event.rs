@@ -0,0 +1,39 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Event routing for the organise view.
+
+use crossterm::event::{Event, KeyCode};
+
+use crate::components::OrganiseView;
+
+impl OrganiseView {
+    pub(crate) fn process_event(&mut self, event: &Event) {
+        if !self.is_active {
+            return;
+        }
+
+        if let Event::Key(key_event) = event {
+            match key_event.code {
+                KeyCode::Char('j') | KeyCode::Down => self.goto_next(),
+                KeyCode::Char('k') | KeyCode::Up => self.goto_previous(),
+                KeyCode::Char('g') => self.goto_first(),
+                KeyCode::Char('G') => self.goto_last(),
+
+                _ => {}
+            }
+        }
+    }
+}
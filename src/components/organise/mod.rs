@@ -0,0 +1,87 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Organise files view.
+//!
+//! Lists the moves planned by `:organise`, and their progress once they are
+//! being made.
+
+mod event;
+mod render;
+
+use std::sync::{Arc, Mutex};
+
+use ratatui::widgets::TableState;
+
+use crate::model::organise::FileMove;
+
+pub(crate) struct OrganiseView {
+    moves: Arc<Mutex<Vec<FileMove>>>,
+    table_state: TableState,
+    pub(crate) is_active: bool,
+}
+
+impl OrganiseView {
+    pub(crate) fn new(moves: Arc<Mutex<Vec<FileMove>>>) -> Self {
+        Self {
+            moves,
+            table_state: TableState::new(),
+            is_active: false,
+        }
+    }
+
+    fn goto_next(&mut self) {
+        let len = self.moves.lock().unwrap().len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.table_state.selected() {
+            Some(i) if i < len - 1 => i + 1,
+            _ => 0,
+        };
+        self.table_state.select(Some(i));
+    }
+
+    fn goto_previous(&mut self) {
+        let len = self.moves.lock().unwrap().len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.table_state.selected() {
+            Some(i) if i > 0 => i - 1,
+            _ => len - 1,
+        };
+        self.table_state.select(Some(i));
+    }
+
+    fn goto_first(&mut self) {
+        self.table_state.select_first();
+    }
+
+    fn goto_last(&mut self) {
+        self.table_state.select_last();
+    }
+
+    /// Keeps the highlighted row valid after a new plan has been made.
+    pub(crate) fn ensure_table_selection(&mut self) {
+        let len = self.moves.lock().unwrap().len();
+        match self.table_state.selected() {
+            _ if len == 0 => self.table_state.select(None),
+            Some(i) if i >= len => self.table_state.select(Some(len - 1)),
+            None => self.table_state.select(Some(0)),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! UI rendering logic for the organise view.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    prelude::Rect,
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Padding, Paragraph, Row, Table},
+};
+
+use crate::{
+    components::OrganiseView,
+    model::organise::{MoveStatus, Organise, OrganiseStatus},
+    theme::Theme,
+};
+
+impl OrganiseView {
+    pub(crate) fn draw(&mut self, f: &mut Frame, area: Rect, organise: &Organise, theme: &Theme) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(0)])
+            .split(area);
+
+        let header_block = Block::default()
+            .borders(Borders::BOTTOM)
+            .padding(Padding::horizontal(1));
+
+        let moves = self.moves.lock().unwrap();
+
+        let count =
+            |status: fn(&MoveStatus) -> bool| moves.iter().filter(|m| status(&m.status)).count();
+        let pending = count(|s| matches!(s, MoveStatus::Pending));
+        let skipped = count(|s| matches!(s, MoveStatus::Skipped(_)));
+        let failed = count(|s| matches!(s, MoveStatus::Failed(_)));
+
+        let header_text = match organise.status {
+            OrganiseStatus::Idle => "Organise Files".to_string(),
+            OrganiseStatus::Planned => format!(
+                "Organise Files (Preview) | {} to move, {} skipped | :organise apply to move them",
+                pending, skipped
+            ),
+            OrganiseStatus::Moving => format!(
+                "Organise Files (Moving...) | {} of {} moved",
+                moves.len() - pending - skipped - failed,
+                moves.len() - skipped
+            ),
+            OrganiseStatus::Finished => format!(
+                "Organise Files (Done) | {} moved, {} failed, {} skipped",
+                organise.moved(),
+                failed,
+                skipped
+            ),
+        };
+        let header = Paragraph::new(header_text).block(header_block);
+        f.render_widget(header, chunks[0]);
+
+        let rows = moves.iter().map(|file_move| {
+            let (status, status_fg) = match &file_move.status {
+                MoveStatus::Pending => ("Pending", theme.table_time_fg),
+                MoveStatus::Moved => ("Moved", theme.rating_like_fg),
+                MoveStatus::Skipped(_) => ("Skipped", theme.accent_colour),
+                MoveStatus::Failed(_) => ("Failed", theme.rating_dislike_fg),
+            };
+
+            let destination = match &file_move.status {
+                MoveStatus::Skipped(reason) | MoveStatus::Failed(reason) => reason.clone(),
+                _ => file_move.to.display().to_string(),
+            };
+
+            Row::new(vec![
+                Cell::from(Line::from(status).style(Style::default().fg(status_fg))),
+                Cell::from(
+                    Line::from(file_move.from.display().to_string())
+                        .style(Style::default().fg(theme.table_album_fg)),
+                ),
+                Cell::from(
+                    Line::from(destination).style(Style::default().fg(theme.table_track_fg)),
+                ),
+            ])
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Fill(50),
+                Constraint::Fill(50),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                Cell::from("Status"),
+                Cell::from("File"),
+                Cell::from("Destination"),
+            ])
+            .style(Style::default().bold().fg(theme.accent_colour))
+            .bottom_margin(1),
+        )
+        .row_highlight_style(Style::default().bg(Color::Blue).fg(Color::White))
+        .block(Block::default().padding(Padding::horizontal(1)));
+
+        f.render_stateful_widget(table, chunks[1], &mut self.table_state);
+    }
+}
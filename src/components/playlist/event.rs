@@ -22,7 +22,7 @@
 use std::sync::mpsc::Sender;
 
 use anyhow::Result;
-use crossterm::event::{Event, KeyCode, KeyEvent};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 
 use crate::{
     components::{PlaylistView, TrackTableAction},
@@ -47,7 +47,7 @@ impl PlaylistView {
         }
 
         if let Event::Key(key) = event {
-            return self.handle_key_event(key, task_tx);
+            return self.handle_key_event(key, task_tx, event_tx);
         }
 
         Ok(())
@@ -59,27 +59,63 @@ impl PlaylistView {
         event_tx: &Sender<AppEvent>,
     ) -> Result<()> {
         match action {
-            _ => {}
+            TrackTableAction::ActivateCurrent(row) => {
+                event_tx.send(AppEvent::PlayQueueEntry(row))?;
+            }
+
+            // Enter plays from the highlighted row even with a selection
+            TrackTableAction::CommitSelection(_) => {
+                if let Some(row) = self.track_table.current_row() {
+                    event_tx.send(AppEvent::PlayQueueEntry(row))?;
+                }
+            }
         }
 
         Ok(())
     }
 
-    fn handle_key_event(&self, key: KeyEvent, command_tx: &Sender<AppTask>) -> Result<()> {
-        match key.code {
-            KeyCode::Char(']') => {
+    fn handle_key_event(
+        &self,
+        key: KeyEvent,
+        command_tx: &Sender<AppTask>,
+        event_tx: &Sender<AppEvent>,
+    ) -> Result<()> {
+        // Control combinations not handled by the table must not fall through
+        // to the plain key bindings, e.g. Ctrl-d would otherwise remove entries
+        match (key.code, key.modifiers) {
+            (KeyCode::Char('K'), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                let rows = self.track_table.selected_rows();
+                event_tx.send(AppEvent::MoveQueueEntries(rows, -1))?;
+            }
+
+            (KeyCode::Char('J'), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                let rows = self.track_table.selected_rows();
+                event_tx.send(AppEvent::MoveQueueEntries(rows, 1))?;
+            }
+
+            (KeyCode::Char('N'), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                let rows = self.track_table.selected_rows();
+                event_tx.send(AppEvent::PlayQueueEntriesNext(rows))?;
+            }
+
+            (KeyCode::Char('d'), KeyModifiers::NONE | KeyModifiers::SHIFT) | (KeyCode::Delete, _) => {
+                let rows = self.track_table.selected_rows();
+                event_tx.send(AppEvent::RemoveQueueEntries(rows))?;
+            }
+
+            (KeyCode::Char(']'), _) => {
                 if let Some(track) = self.track_table.clone_current() {
                     command_tx.send(AppTask::RateTrack(track, Rating::Like))?;
                 }
             }
 
-            KeyCode::Char('[') => {
+            (KeyCode::Char('['), _) => {
                 if let Some(track) = self.track_table.clone_current() {
                     command_tx.send(AppTask::RateTrack(track, Rating::Dislike))?;
                 }
             }
 
-            KeyCode::Char('p') => {
+            (KeyCode::Char('p'), _) => {
                 if let Some(track) = self.track_table.clone_current() {
                     command_tx.send(AppTask::PlayTrack(track))?;
                 }
@@ -29,12 +29,22 @@ use ratatui::{
 };
 
 use crate::{
-    components::PlaylistView, model::queue::Queue, render::Render, theme::Theme,
+    components::PlaylistView,
+    model::{auto_dj::AutoDj, queue::Queue},
+    render::Render,
+    theme::Theme,
     util::format::TimeFormat,
 };
 
 impl PlaylistView {
-    pub(crate) fn draw(&mut self, f: &mut Frame, area: Rect, queue: &Queue, theme: &Theme) {
+    pub(crate) fn draw(
+        &mut self,
+        f: &mut Frame,
+        area: Rect,
+        queue: &Queue,
+        auto_dj: &AutoDj,
+        theme: &Theme,
+    ) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Length(2), Constraint::Min(0)])
@@ -62,6 +72,14 @@ impl PlaylistView {
             let _ = write!(header_text, " | {} selected", selected_count);
         }
 
+        if let Some(reason) = self
+            .track_table
+            .clone_current()
+            .and_then(|track| auto_dj.reason(track.durable_id))
+        {
+            let _ = write!(header_text, " | Auto-DJ: {}", reason);
+        }
+
         let header = Paragraph::new(header_text).block(header_block);
 
         f.render_widget(header, chunks[0]);
@@ -0,0 +1,81 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Event routing for the podcasts view.
+
+use std::sync::mpsc::Sender;
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode};
+
+use crate::{components::PodcastsView, events::AppEvent, tasks::AppTask};
+
+impl PodcastsView {
+    pub(crate) fn process_event(
+        &mut self,
+        event: &Event,
+        task_tx: &Sender<AppTask>,
+        _event_tx: &Sender<AppEvent>,
+    ) -> Result<()> {
+        if !self.is_active {
+            return Ok(());
+        }
+
+        if let Event::Key(key_event) = event {
+            match key_event.code {
+                KeyCode::Char('j') | KeyCode::Down => self.goto_next(),
+                KeyCode::Char('k') | KeyCode::Up => self.goto_previous(),
+                KeyCode::Char('g') => self.goto_first(),
+                KeyCode::Char('G') => self.goto_last(),
+
+                // Play the episode, downloading it first if necessary
+                KeyCode::Enter => {
+                    if let Some(episode) = self.clone_current() {
+                        match episode.durable_id {
+                            Some(durable_id) => task_tx.send(AppTask::PlayEpisode(durable_id))?,
+                            None => task_tx.send(AppTask::DownloadEpisode(episode.id))?,
+                        }
+                    }
+                }
+
+                KeyCode::Char('D') => {
+                    if let Some(episode) = self.clone_current() {
+                        task_tx.send(AppTask::DownloadEpisode(episode.id))?;
+                    }
+                }
+
+                KeyCode::Char('x') => {
+                    if let Some(episode) = self.clone_current() {
+                        task_tx.send(AppTask::SetEpisodePlayed(episode.id, !episode.played))?;
+                    }
+                }
+
+                KeyCode::Char('r') if key_event.modifiers.is_empty() => {
+                    task_tx.send(AppTask::RefreshPodcasts)?
+                }
+
+                KeyCode::Char('U') => {
+                    if let Some(episode) = self.clone_current() {
+                        task_tx.send(AppTask::UnsubscribePodcast(episode.podcast_id))?;
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! UI rendering logic for the podcasts view.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    prelude::Rect,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Padding, Paragraph, Row, Table},
+};
+
+use crate::{components::PodcastsView, model::TrackInfo, theme::Theme, util::format::format_date};
+
+impl PodcastsView {
+    pub(crate) fn draw(
+        &mut self,
+        f: &mut Frame,
+        area: Rect,
+        now_playing: Option<&TrackInfo>,
+        theme: &Theme,
+    ) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(0)])
+            .split(area);
+
+        let header_block = Block::default()
+            .borders(Borders::BOTTOM)
+            .padding(Padding::horizontal(1));
+
+        let episodes = self.episodes.lock().unwrap();
+
+        let unplayed = episodes.iter().filter(|episode| !episode.played).count();
+        let header_text = format!(
+            "Podcasts | {} episodes | {} unplayed",
+            episodes.len(),
+            unplayed
+        );
+        let header = Paragraph::new(header_text).block(header_block);
+        f.render_widget(header, chunks[0]);
+
+        let playing_id = now_playing.map(|track| track.durable_id);
+
+        let rows = episodes.iter().map(|episode| {
+            let title_style = if episode.durable_id.is_some() && episode.durable_id == playing_id {
+                Style::default()
+                    .fg(theme.accent_colour)
+                    .add_modifier(Modifier::BOLD)
+            } else if episode.played {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default().fg(theme.table_track_fg)
+            };
+
+            let published = episode
+                .published_at
+                .map(format_date)
+                .unwrap_or_default();
+
+            let status = match (episode.played, episode.filename.is_some()) {
+                (true, _) => "Played",
+                (false, true) => "Downloaded",
+                (false, false) => "",
+            };
+
+            Row::new(vec![
+                Cell::from(
+                    Line::from(episode.podcast_title.as_str())
+                        .style(Style::default().fg(theme.table_artist_fg)),
+                ),
+                Cell::from(Line::from(episode.title.as_str()).style(title_style)),
+                Cell::from(
+                    Line::from(published).style(Style::default().fg(theme.table_year_fg)),
+                ),
+                Cell::from(Line::from(status).style(Style::default().fg(theme.table_time_fg))),
+            ])
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(25),
+                Constraint::Fill(55),
+                Constraint::Length(10),
+                Constraint::Length(10),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                Cell::from("Podcast"),
+                Cell::from("Episode"),
+                Cell::from("Published"),
+                Cell::from("Status"),
+            ])
+            .style(Style::default().bold().fg(theme.accent_colour))
+            .bottom_margin(1),
+        )
+        .row_highlight_style(Style::default().bg(Color::Blue).fg(Color::White))
+        .block(Block::default().padding(Padding::horizontal(1)));
+
+        f.render_stateful_widget(table, chunks[1], &mut self.table_state);
+    }
+}
@@ -92,6 +92,13 @@ impl SearchView {
                 }
             }
 
+            KeyCode::Char('e') => {
+                let tracks = self.track_table.clone_selected_or_current();
+                if !tracks.is_empty() {
+                    command_tx.send(AppTask::ReadTags(tracks))?;
+                }
+            }
+
             _ => {}
         }
 
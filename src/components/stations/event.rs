@@ -0,0 +1,61 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Event routing for the stations view.
+
+use std::sync::mpsc::Sender;
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode};
+
+use crate::{components::StationsView, events::AppEvent, tasks::AppTask};
+
+impl StationsView {
+    pub(crate) fn process_event(
+        &mut self,
+        event: &Event,
+        task_tx: &Sender<AppTask>,
+        event_tx: &Sender<AppEvent>,
+    ) -> Result<()> {
+        if !self.is_active {
+            return Ok(());
+        }
+
+        if let Event::Key(key_event) = event {
+            match key_event.code {
+                KeyCode::Char('j') | KeyCode::Down => self.goto_next(),
+                KeyCode::Char('k') | KeyCode::Up => self.goto_previous(),
+                KeyCode::Char('g') => self.goto_first(),
+                KeyCode::Char('G') => self.goto_last(),
+
+                KeyCode::Enter => {
+                    if let Some(station) = self.clone_current() {
+                        event_tx.send(AppEvent::PlayStation(station))?;
+                    }
+                }
+
+                KeyCode::Char('d') | KeyCode::Delete => {
+                    if let Some(station) = self.clone_current() {
+                        task_tx.send(AppTask::DeleteStation(station.id))?;
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
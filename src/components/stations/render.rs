@@ -0,0 +1,83 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! UI rendering logic for the stations view.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    prelude::Rect,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Padding, Paragraph, Row, Table},
+};
+
+use crate::{components::StationsView, model::Station, theme::Theme};
+
+impl StationsView {
+    pub(crate) fn draw(
+        &mut self,
+        f: &mut Frame,
+        area: Rect,
+        now_streaming: Option<&Station>,
+        theme: &Theme,
+    ) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(0)])
+            .split(area);
+
+        let header_block = Block::default()
+            .borders(Borders::BOTTOM)
+            .padding(Padding::horizontal(1));
+
+        let stations = self.stations.lock().unwrap();
+
+        let header_text = format!("Stations | {} stations", stations.len());
+        let header = Paragraph::new(header_text).block(header_block);
+        f.render_widget(header, chunks[0]);
+
+        let streaming_id = now_streaming.map(|station| station.id);
+
+        let rows = stations.iter().map(|station| {
+            let name_style = if Some(station.id) == streaming_id {
+                Style::default()
+                    .fg(theme.accent_colour)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.table_track_fg)
+            };
+
+            Row::new(vec![
+                Cell::from(Line::from(station.name.as_str()).style(name_style)),
+                Cell::from(
+                    Line::from(station.url.as_str())
+                        .style(Style::default().fg(theme.table_album_fg)),
+                ),
+            ])
+        });
+
+        let table = Table::new(rows, [Constraint::Fill(40), Constraint::Fill(60)])
+            .header(
+                Row::new(vec![Cell::from("Station"), Cell::from("URL")])
+                    .style(Style::default().bold().fg(theme.accent_colour))
+                    .bottom_margin(1),
+            )
+            .row_highlight_style(Style::default().bg(Color::Blue).fg(Color::White))
+            .block(Block::default().padding(Padding::horizontal(1)));
+
+        f.render_stateful_widget(table, chunks[1], &mut self.table_state);
+    }
+}
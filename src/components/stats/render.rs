@@ -0,0 +1,251 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! UI rendering logic for the stats view.
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout},
+    prelude::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{
+        Bar, BarChart, BarGroup, Block, Borders, Cell, Padding, Paragraph, Row, Table, Wrap,
+    },
+};
+
+use crate::{
+    components::StatsView,
+    model::stats::{LibraryStats, Tally},
+    theme::Theme,
+    util::format::format_long_duration,
+};
+
+/// Width of each bar in the tracks added chart, enough for a `YYYY-MM` label.
+const MONTH_BAR_WIDTH: u16 = 7;
+
+impl StatsView {
+    pub(crate) fn draw(
+        &mut self,
+        f: &mut Frame,
+        area: Rect,
+        stats: Option<&LibraryStats>,
+        theme: &Theme,
+    ) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(0)])
+            .split(area);
+
+        let header_block = Block::default()
+            .borders(Borders::BOTTOM)
+            .padding(Padding::horizontal(1));
+
+        let Some(stats) = stats else {
+            let header = Paragraph::new("Stats | Loading...").block(header_block);
+            f.render_widget(header, chunks[0]);
+            return;
+        };
+
+        let summary = &stats.summary;
+        let header_text = format!(
+            "Stats | {} tracks | {} albums | {} artists | {}",
+            summary.tracks,
+            summary.albums,
+            summary.artists,
+            format_long_duration(summary.duration)
+        );
+        let header = Paragraph::new(header_text).block(header_block);
+        f.render_widget(header, chunks[0]);
+
+        let body = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(6),
+                Constraint::Min(0),
+                Constraint::Length(14),
+            ])
+            .split(chunks[1]);
+
+        draw_summary(f, body[0], stats, theme);
+
+        let top_lists = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ])
+            .split(body[1]);
+
+        draw_tally_table(f, top_lists[0], "Top Artists", &stats.top_artists, theme);
+        draw_tally_table(f, top_lists[1], "Top Albums", &stats.top_albums, theme);
+        draw_tally_table(f, top_lists[2], "Top Tracks", &stats.top_tracks, theme);
+
+        let charts = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Fill(1), Constraint::Fill(1)])
+            .split(body[2]);
+
+        // Show the most recent months that fit in the chart
+        let visible_months = usize::from(charts[0].width.saturating_sub(2) / (MONTH_BAR_WIDTH + 1));
+        let skipped_months = stats.added_per_month.len().saturating_sub(visible_months);
+
+        let added_bars: Vec<Bar> = stats
+            .added_per_month
+            .iter()
+            .skip(skipped_months)
+            .map(|(month, count)| {
+                Bar::default()
+                    .value(*count)
+                    .label(Line::from(month.as_str()))
+                    .style(Style::default().fg(theme.accent_colour))
+                    .value_style(
+                        Style::default()
+                            .fg(theme.background_colour)
+                            .bg(theme.accent_colour),
+                    )
+            })
+            .collect();
+
+        let added_chart = BarChart::default()
+            .block(chart_block("Tracks Added per Month", theme))
+            .data(BarGroup::default().bars(&added_bars))
+            .bar_width(MONTH_BAR_WIDTH)
+            .bar_gap(1);
+        f.render_widget(added_chart, charts[0]);
+
+        let genre_bars: Vec<Bar> = stats
+            .genre_plays
+            .iter()
+            .map(|(genre, plays)| {
+                Bar::default()
+                    .value(*plays)
+                    .label(Line::from(genre.as_str()))
+                    .style(Style::default().fg(theme.table_album_fg))
+                    .value_style(
+                        Style::default()
+                            .fg(theme.background_colour)
+                            .bg(theme.table_album_fg),
+                    )
+            })
+            .collect();
+
+        let genre_chart = BarChart::default()
+            .block(chart_block("Plays per Genre", theme))
+            .direction(Direction::Horizontal)
+            .data(BarGroup::default().bars(&genre_bars))
+            .bar_width(1)
+            .bar_gap(0);
+        f.render_widget(genre_chart, charts[1]);
+    }
+}
+
+fn draw_summary(f: &mut Frame, area: Rect, stats: &LibraryStats, theme: &Theme) {
+    let summary = &stats.summary;
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Fill(1), Constraint::Fill(1)])
+        .split(area);
+
+    let lines = vec![
+        stat_line("Plays", summary.plays.to_string(), theme),
+        stat_line(
+            "Listening time (est.)",
+            format_long_duration(summary.listening_time),
+            theme,
+        ),
+        stat_line(
+            "Never played",
+            format!("{} of {} tracks", summary.never_played, summary.tracks),
+            theme,
+        ),
+        stat_line(
+            "Liked / Disliked",
+            format!("{} / {}", summary.liked, summary.disliked),
+            theme,
+        ),
+    ];
+
+    let usage = Paragraph::new(lines).block(Block::default().padding(Padding::new(1, 1, 1, 0)));
+    f.render_widget(usage, columns[0]);
+
+    let formats = stats
+        .formats
+        .iter()
+        .map(|(format, count)| {
+            let percent = if summary.tracks > 0 {
+                *count as f64 * 100.0 / summary.tracks as f64
+            } else {
+                0.0
+            };
+            format!("{format} {count} ({percent:.0}%)")
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let formats = Paragraph::new(vec![stat_line("Formats", formats, theme)])
+        .wrap(Wrap { trim: true })
+        .block(Block::default().padding(Padding::new(1, 1, 1, 0)));
+    f.render_widget(formats, columns[1]);
+}
+
+fn stat_line(label: &'static str, value: String, theme: &Theme) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(
+            format!("{label}: "),
+            Style::default().fg(theme.accent_colour),
+        ),
+        Span::styled(value, Style::default().fg(theme.table_track_fg)),
+    ])
+}
+
+fn chart_block(title: &str, theme: &Theme) -> Block<'static> {
+    Block::default()
+        .title(format!(" {title}"))
+        .title_style(
+            Style::default()
+                .fg(theme.accent_colour)
+                .add_modifier(Modifier::BOLD),
+        )
+        .padding(Padding::horizontal(1))
+}
+
+fn draw_tally_table(f: &mut Frame, area: Rect, title: &str, tallies: &[Tally], theme: &Theme) {
+    let rows = tallies.iter().map(|(name, count)| {
+        Row::new(vec![
+            Cell::from(Line::from(name.as_str()).style(Style::default().fg(theme.table_track_fg))),
+            Cell::from(
+                Line::from(count.to_string())
+                    .style(Style::default().fg(theme.table_time_fg))
+                    .alignment(Alignment::Right),
+            ),
+        ])
+    });
+
+    let table = Table::new(rows, [Constraint::Fill(1), Constraint::Length(6)])
+        .header(
+            Row::new(vec![
+                Cell::from(title.to_string()),
+                Cell::from(Line::from("Plays").alignment(Alignment::Right)),
+            ])
+            .style(Style::default().bold().fg(theme.accent_colour))
+            .bottom_margin(1),
+        )
+        .block(Block::default().padding(Padding::horizontal(1)));
+
+    f.render_widget(table, area);
+}
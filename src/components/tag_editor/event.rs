@@ -0,0 +1,65 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Event routing for the tag editor.
+
+use std::sync::mpsc::Sender;
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode};
+use tui_input::backend::crossterm::EventHandler;
+
+use crate::{components::TagEditor, tasks::AppTask};
+
+impl TagEditor {
+    pub(crate) fn process_event(&mut self, event: &Event, task_tx: &Sender<AppTask>) -> Result<()> {
+        if !self.is_active {
+            return Ok(());
+        }
+
+        if let Event::Key(key_event) = event {
+            self.error = None;
+
+            match key_event.code {
+                KeyCode::Esc => self.close(),
+
+                KeyCode::Enter => {
+                    if let Some(error) = self.validate() {
+                        self.error = Some(error);
+                        return Ok(());
+                    }
+
+                    let changes = self.changes();
+                    if !changes.is_empty() {
+                        let tracks = std::mem::take(&mut self.tracks);
+                        task_tx.send(AppTask::EditTags(tracks, changes))?;
+                    }
+                    self.close();
+                }
+
+                KeyCode::Tab | KeyCode::Down => self.focus_next(),
+                KeyCode::BackTab | KeyCode::Up => self.focus_previous(),
+
+                _ => {
+                    if let Some(tag) = self.inputs.get_mut(self.focus) {
+                        tag.input.handle_event(event);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Tag editor popup.
+//!
+//! The editor changes the tags of one or more tracks at once. Each tag starts
+//! with the value the tracks share, and is left blank if they differ. Only the
+//! tags that are changed are written, so a blank tag for tracks with different
+//! values leaves those values alone.
+
+mod event;
+mod render;
+
+use tui_input::Input;
+
+use crate::model::{
+    TrackInfo,
+    tags::{TagChange, TagField, TrackTags},
+};
+
+pub(crate) struct TagEditor {
+    tracks: Vec<TrackInfo>,
+    inputs: Vec<TagInput>,
+    focus: usize,
+    error: Option<String>,
+    pub(crate) is_active: bool,
+}
+
+/// An editable tag, with the value it started with.
+struct TagInput {
+    field: TagField,
+    input: Input,
+    initial: String,
+    /// The tracks being edited have different values for this tag.
+    mixed: bool,
+}
+
+impl TagEditor {
+    pub(crate) fn new() -> Self {
+        Self {
+            tracks: vec![],
+            inputs: vec![],
+            focus: 0,
+            error: None,
+            is_active: false,
+        }
+    }
+
+    /// Opens the editor for some tracks, with the tags read from their files.
+    pub(crate) fn open(&mut self, tracks: Vec<TrackInfo>, tags: &[TrackTags]) {
+        self.inputs = TagField::ALL
+            .iter()
+            .map(|&field| {
+                let first = tags.first().map_or("", |t| t.get(field));
+                let mixed = tags.iter().any(|t| t.get(field) != first);
+                let initial = if mixed {
+                    String::new()
+                } else {
+                    first.to_string()
+                };
+
+                TagInput {
+                    field,
+                    input: Input::new(initial.clone()),
+                    initial,
+                    mixed,
+                }
+            })
+            .collect();
+
+        self.tracks = tracks;
+        self.focus = 0;
+        self.error = None;
+        self.is_active = true;
+    }
+
+    fn close(&mut self) {
+        self.tracks.clear();
+        self.inputs.clear();
+        self.is_active = false;
+    }
+
+    fn focus_next(&mut self) {
+        self.focus = (self.focus + 1) % self.inputs.len();
+    }
+
+    fn focus_previous(&mut self) {
+        self.focus = (self.focus + self.inputs.len() - 1) % self.inputs.len();
+    }
+
+    /// The tags that have been changed.
+    fn changes(&self) -> Vec<TagChange> {
+        self.inputs
+            .iter()
+            .filter(|tag| tag.input.value().trim() != tag.initial)
+            .map(|tag| (tag.field, tag.input.value().trim().to_string()))
+            .collect()
+    }
+
+    /// Checks that the numeric tags are numbers, returning an error message
+    /// for the first one that is not.
+    fn validate(&self) -> Option<String> {
+        self.inputs
+            .iter()
+            .filter(|tag| tag.field.is_numeric())
+            .find(|tag| {
+                let value = tag.input.value().trim();
+                !value.is_empty() && value.parse::<u16>().is_err()
+            })
+            .map(|tag| format!("{} must be a number", tag.field.label()))
+    }
+}
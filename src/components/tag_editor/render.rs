@@ -0,0 +1,132 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! UI rendering logic for the tag editor.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Flex, Layout, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
+};
+
+use crate::{components::TagEditor, theme::Theme};
+
+const POPUP_WIDTH: u16 = 64;
+
+const LABEL_WIDTH: u16 = 14;
+
+impl TagEditor {
+    pub(crate) fn draw(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.is_active {
+            return;
+        }
+
+        // One row per tag, plus the borders, padding and the footer
+        let height = self.inputs.len() as u16 + 6;
+        let popup = centered(area, POPUP_WIDTH, height);
+
+        let title = match self.tracks.len() {
+            1 => " Edit Tags ".to_string(),
+            count => format!(" Edit Tags | {} tracks ", count),
+        };
+
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent_colour))
+            .style(Style::default().bg(theme.background_colour))
+            .padding(Padding::uniform(1));
+
+        let inner = block.inner(popup);
+
+        f.render_widget(Clear, popup);
+        f.render_widget(block, popup);
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                self.inputs
+                    .iter()
+                    .map(|_| Constraint::Length(1))
+                    .chain([Constraint::Length(1), Constraint::Length(1)]),
+            )
+            .split(inner);
+
+        for (index, tag) in self.inputs.iter().enumerate() {
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(LABEL_WIDTH), Constraint::Min(1)])
+                .split(rows[index]);
+
+            let has_focus = index == self.focus;
+
+            let label_style = if has_focus {
+                Style::default()
+                    .fg(theme.accent_colour)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.table_time_fg)
+            };
+            f.render_widget(
+                Paragraph::new(tag.field.label()).style(label_style),
+                columns[0],
+            );
+
+            let value_area = columns[1];
+            let value_style = Style::default().bg(theme.gauge_track_colour);
+
+            let value = tag.input.value();
+            let scroll = tag.input.visual_scroll(value_area.width as usize);
+
+            let paragraph = if tag.mixed && value.is_empty() {
+                Paragraph::new(Line::from("(multiple values)"))
+                    .style(value_style.fg(theme.table_time_fg))
+            } else {
+                Paragraph::new(value)
+                    .style(value_style.fg(theme.commander_colour))
+                    .scroll((0, scroll as u16))
+            };
+            f.render_widget(paragraph, value_area);
+
+            if has_focus {
+                let cursor_x = value_area.x + (tag.input.visual_cursor() - scroll) as u16;
+                f.set_cursor_position((cursor_x, value_area.y));
+            }
+        }
+
+        let footer = match &self.error {
+            Some(error) => {
+                Paragraph::new(error.as_str()).style(Style::default().fg(theme.rating_dislike_fg))
+            }
+            None => Paragraph::new("Enter save | Esc cancel | Tab next field")
+                .style(Style::default().fg(theme.table_time_fg)),
+        };
+        f.render_widget(footer, rows[self.inputs.len() + 1]);
+    }
+}
+
+/// A rectangle of the given size in the centre of an area.
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+
+    area
+}
@@ -64,9 +64,13 @@ impl TrackTable {
         // External events that result in a table action
         let action = match event {
             Event::Key(key_event) => match (key_event.code, key_event.modifiers) {
-                (KeyCode::Enter, _) => Some(&self.selection)
-                    .filter(|s| !s.is_empty())
-                    .map(|s| TrackTableAction::CommitSelection(s.clone())),
+                (KeyCode::Enter, _) if !self.selection.is_empty() => {
+                    Some(TrackTableAction::CommitSelection(self.selection.clone()))
+                }
+                (KeyCode::Enter, _) => self
+                    .table_state
+                    .selected()
+                    .map(TrackTableAction::ActivateCurrent),
 
                 _ => None,
             },
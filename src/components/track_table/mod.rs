@@ -28,7 +28,7 @@ use ratatui::widgets::TableState;
 use crate::model::TrackInfo;
 
 pub(crate) enum TrackTableAction {
-    ActivateCurrent(i32),
+    ActivateCurrent(usize),
     CommitSelection(HashSet<i32>),
 }
 
@@ -219,6 +219,30 @@ impl TrackTable {
         self.selection.len()
     }
 
+    /// Row positions of the selected tracks, or of the highlighted track if
+    /// nothing is selected.
+    pub(crate) fn selected_rows(&self) -> Vec<usize> {
+        if self.selection.is_empty() {
+            return self.table_state.selected().into_iter().collect();
+        }
+
+        let tracks = self.tracks.lock().unwrap();
+        tracks
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| self.selection.contains(&t.track_id))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    pub(crate) fn current_row(&self) -> Option<usize> {
+        self.table_state.selected()
+    }
+
+    pub(crate) fn set_current_row(&mut self, row: Option<usize>) {
+        self.table_state.select(row);
+    }
+
     // FIXME there is some confusion between the track selection (the "true" selection) and the currently selected item in the
     //       table state, which is also called collection...
 
@@ -273,6 +297,15 @@ impl TrackTable {
             .collect()
     }
 
+    /// The selected tracks, or the highlighted track if nothing is selected.
+    pub(crate) fn clone_selected_or_current(&self) -> Vec<TrackInfo> {
+        if self.selection.is_empty() {
+            return self.clone_current().into_iter().collect();
+        }
+
+        self.clone_selected_tracks()
+    }
+
     pub(crate) fn clone_current(&self) -> Option<TrackInfo> {
         if let Some(index) = self.table_state.selected() {
             let locked = self.tracks.lock().unwrap();
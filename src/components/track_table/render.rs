@@ -29,10 +29,10 @@ use ratatui::{
 
 use crate::{
     components::TrackTable,
-    model::Rating,
+    model::{Rating, StarRating},
     render::{
         Render,
-        icons::{ICON_DISLIKE, ICON_FAVOURITE, ICON_LIKE},
+        icons::{ICON_DISLIKE, ICON_FAVOURITE, ICON_HALF_STAR, ICON_LIKE, ICON_STAR},
     },
     theme::Theme,
     util::format::TimeFormat,
@@ -49,6 +49,17 @@ impl Render for TrackTable {
 impl TrackTable {
     fn draw_table(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
         let tracks = self.tracks.lock().unwrap();
+
+        // Optional columns are only shown when some track has something to
+        // show in them
+        let show_album_artist = tracks.iter().any(|item| {
+            item.album_artist
+                .as_ref()
+                .is_some_and(|album_artist| !album_artist.eq_ignore_ascii_case(&item.artist_name))
+        });
+        let show_disc = tracks.iter().any(|item| item.disc_number.unwrap_or(1) > 1);
+        let show_composer = tracks.iter().any(|item| item.composer.is_some());
+
         let rows = tracks.iter().map(|item| {
             let selected = self.selection.contains(&item.track_id);
             let selection_indicator = if selected {
@@ -60,6 +71,7 @@ impl TrackTable {
             let duration: u64 = item.duration.try_into().unwrap_or(0);
             let time = crate::util::format::format_time(duration, TimeFormat::Minutes);
             let year = item.year.map(|y| y.to_string()).unwrap_or_default();
+            let disc_number = item.disc_number.map(|d| d.to_string()).unwrap_or_default();
             let track_number = format!("{:02}", item.track_number);
             let play_count = match item.play_count {
                 0 => "".to_string(),
@@ -75,8 +87,9 @@ impl TrackTable {
                 Rating::Neutral => theme.rating_neutral_fg,
                 Rating::Dislike => theme.rating_dislike_fg,
             };
+            let stars = item.stars.map(format_stars).unwrap_or_default();
 
-            Row::new(vec![
+            let mut cells = vec![
                 Cell::from(selection_indicator),
                 Cell::from(
                     Line::from(time)
@@ -88,11 +101,28 @@ impl TrackTable {
                     Line::from(item.artist_name.as_str())
                         .style(Style::default().fg(theme.table_artist_fg)),
                 ),
+            ];
+            if show_album_artist {
+                cells.push(Cell::from(
+                    Line::from(item.album_artist.as_deref().unwrap_or_default())
+                        .style(Style::default().fg(theme.table_artist_fg)),
+                ));
+            }
+            cells.extend([
                 Cell::from(Line::from(year).style(Style::default().fg(theme.table_year_fg))),
                 Cell::from(
                     Line::from(item.album_title.as_str())
                         .style(Style::default().fg(theme.table_album_fg)),
                 ),
+            ]);
+            if show_disc {
+                cells.push(Cell::from(
+                    Line::from(disc_number)
+                        .style(Style::default().fg(theme.table_track_number_fg))
+                        .alignment(Alignment::Right),
+                ));
+            }
+            cells.extend([
                 Cell::from(
                     Line::from(track_number)
                         .style(Style::default().fg(theme.table_track_number_fg))
@@ -102,52 +132,80 @@ impl TrackTable {
                     Line::from(item.track_title.as_str())
                         .style(Style::default().fg(theme.table_track_fg)),
                 ),
+            ]);
+            if show_composer {
+                cells.push(Cell::from(
+                    Line::from(item.composer.as_deref().unwrap_or_default())
+                        .style(Style::default().fg(theme.table_artist_fg)),
+                ));
+            }
+            cells.extend([
                 Cell::from(
                     Line::from(play_count)
                         .style(Style::default().fg(theme.table_track_fg))
                         .alignment(Alignment::Right),
                 ),
+                Cell::from(Line::from(stars).style(Style::default().fg(rating_colour))),
                 Cell::from(Line::from(rating).style(Style::default().fg(rating_colour))),
-            ])
+            ]);
+
+            Row::new(cells)
         });
 
-        let table = Table::new(
-            rows,
-            [
-                Constraint::Length(1),
-                Constraint::Length(6),
-                Constraint::Length(1),
-                Constraint::Fill(20),
-                Constraint::Length(4),
-                Constraint::Fill(25),
-                Constraint::Length(5),
-                Constraint::Fill(55),
-                Constraint::Length(3),
-                Constraint::Length(1),
-            ],
-        )
-        .header(
-            Row::new(vec![
-                Cell::from(""),
-                Cell::from(Line::from("Time").alignment(Alignment::Right)),
-                Cell::from(""),
-                Cell::from("Artist"),
-                Cell::from("Year"),
-                Cell::from("Album"),
-                Cell::from(Line::from("Track").alignment(Alignment::Right)),
-                Cell::from("Title"),
-                Cell::from(Line::from("#").alignment(Alignment::Right)),
-                Cell::from(ICON_FAVOURITE),
-            ])
-            .style(
-                ratatui::style::Style::default()
-                    .bold()
-                    .fg(theme.accent_colour),
+        let mut widths = vec![
+            Constraint::Length(1),
+            Constraint::Length(6),
+            Constraint::Length(1),
+            Constraint::Fill(20),
+        ];
+        let mut header = vec![
+            Cell::from(""),
+            Cell::from(Line::from("Time").alignment(Alignment::Right)),
+            Cell::from(""),
+            Cell::from("Artist"),
+        ];
+        if show_album_artist {
+            widths.push(Constraint::Fill(20));
+            header.push(Cell::from("Album Artist"));
+        }
+        widths.extend([Constraint::Length(4), Constraint::Fill(25)]);
+        header.extend([Cell::from("Year"), Cell::from("Album")]);
+        if show_disc {
+            widths.push(Constraint::Length(4));
+            header.push(Cell::from(Line::from("Disc").alignment(Alignment::Right)));
+        }
+        widths.extend([Constraint::Length(5), Constraint::Fill(55)]);
+        header.extend([
+            Cell::from(Line::from("Track").alignment(Alignment::Right)),
+            Cell::from("Title"),
+        ]);
+        if show_composer {
+            widths.push(Constraint::Fill(20));
+            header.push(Cell::from("Composer"));
+        }
+        widths.extend([
+            Constraint::Length(3),
+            Constraint::Length(5),
+            Constraint::Length(1),
+        ]);
+        header.extend([
+            Cell::from(Line::from("#").alignment(Alignment::Right)),
+            Cell::from("Stars"),
+            Cell::from(ICON_FAVOURITE),
+        ]);
+
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new(header)
+                    .style(
+                        ratatui::style::Style::default()
+                            .bold()
+                            .fg(theme.accent_colour),
+                    )
+                    .bottom_margin(1),
             )
-            .bottom_margin(1),
-        )
-        .row_highlight_style(Style::default().bg(Color::Blue).fg(Color::White))
-        .block(Block::default());
+            .row_highlight_style(Style::default().bg(Color::Blue).fg(Color::White))
+            .block(Block::default());
 
         let state = &mut self.table_state;
         f.render_stateful_widget(table, area, state);
@@ -155,3 +213,13 @@ impl TrackTable {
         self.table_rows = (area.height as usize).saturating_sub(RESERVED_ROWS);
     }
 }
+
+/// Formats a star rating as a row of stars, ending with a half if needed.
+fn format_stars(stars: StarRating) -> String {
+    let half_stars = stars.half_stars();
+    let mut text = ICON_STAR.repeat(usize::from(half_stars / 2));
+    if half_stars % 2 == 1 {
+        text.push_str(ICON_HALF_STAR);
+    }
+    text
+}
@@ -19,12 +19,71 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::model::equalizer::ChannelMix;
+
 const CONFIG_NAME: &str = "chooui";
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
 pub struct AppConfig {
     pub version: u32,
     pub media_dirs: Vec<String>,
+
+    /// How long, in seconds, the volume fades out before the sleep timer
+    /// stops playback.
+    pub sleep_fade_secs: u64,
+
+    /// Tracks at least this long, in seconds, resume from their last position.
+    pub resume_threshold_secs: u64,
+
+    /// Tracks in these genres always resume from their last position.
+    pub resume_genres: Vec<String>,
+
+    /// Stereo balance, mono downmix and channel swap settings.
+    pub channel_mix: ChannelMix,
+
+    /// The directory podcast episodes are downloaded to.
+    pub podcast_dir: String,
+
+    /// Whether the Auto-DJ tops up the queue when it is about to run out.
+    pub auto_dj: bool,
+
+    /// Number of tracks the Auto-DJ lets pass before repeating an artist.
+    pub auto_dj_artist_gap: usize,
+
+    /// The Auto-DJ does not choose tracks played within this many seconds.
+    /// Zero to allow any track.
+    pub auto_dj_replay_gap_secs: u64,
+
+    /// A play is counted once this percentage of a track has been listened
+    /// to...
+    pub play_count_percent: u64,
+
+    /// ...or this many seconds, whichever comes first.
+    pub play_count_secs: u64,
+
+    /// Plays are appended to this `.scrobbler.log` file, for uploading with
+    /// other tools. Empty to disable the log.
+    pub scrobbler_log: String,
+
+    /// Base URL of the ListenBrainz API, or of a compatible server.
+    pub listenbrainz_url: String,
+
+    /// ListenBrainz user token. Empty to disable submitting listens.
+    pub listenbrainz_token: String,
+
+    /// Star ratings are written back to the tags of rated files.
+    pub write_rating_tags: bool,
+
+    /// Where `:organise` moves files to, relative to their media directory.
+    /// Fields are `{albumartist}`, `{artist}`, `{album}`, `{title}`,
+    /// `{track}`, `{disc}`, `{year}`, `{genre}` and `{ext}`, and numbers can
+    /// be zero padded with a width, as in `{track:02}`.
+    pub organise_template: String,
+
+    /// How album art is drawn: `"kitty"`, `"sixel"`, `"iterm2"`,
+    /// `"halfblocks"`, `"none"`, or `"auto"` to detect it from the terminal.
+    pub cover_protocol: String,
 }
 
 impl Default for AppConfig {
@@ -32,10 +91,41 @@ impl Default for AppConfig {
         Self {
             version: 1,
             media_dirs: vec![],
+            sleep_fade_secs: 30,
+            resume_threshold_secs: 20 * 60,
+            resume_genres: vec!["Audiobook".into(), "Podcast".into()],
+            channel_mix: ChannelMix::default(),
+            podcast_dir: default_podcast_dir(),
+            auto_dj: false,
+            auto_dj_artist_gap: 5,
+            auto_dj_replay_gap_secs: 3 * 24 * 60 * 60,
+            play_count_percent: 50,
+            play_count_secs: 4 * 60,
+            scrobbler_log: default_scrobbler_log(),
+            listenbrainz_url: "https://api.listenbrainz.org".into(),
+            listenbrainz_token: String::new(),
+            write_rating_tags: false,
+            organise_template: "{albumartist}/{year} - {album}/{disc}{track:02} {title}.{ext}"
+                .into(),
+            cover_protocol: "auto".into(),
         }
     }
 }
 
+fn default_podcast_dir() -> String {
+    match std::env::var("HOME") {
+        Ok(home) => format!("{}/Podcasts", home),
+        Err(_) => "Podcasts".into(),
+    }
+}
+
+fn default_scrobbler_log() -> String {
+    match std::env::var("HOME") {
+        Ok(home) => format!("{}/.scrobbler.log", home),
+        Err(_) => ".scrobbler.log".into(),
+    }
+}
+
 pub fn load_config() -> AppConfig {
     confy::load(CONFIG_NAME, None).unwrap_or_default()
 }
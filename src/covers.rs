@@ -0,0 +1,137 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Album cover art.
+//!
+//! Covers are taken from the pictures embedded in tags, or from an image file
+//! in the album directory. Each cover is downscaled once to a thumbnail that
+//! is cached on disk, keyed by a hash of the original image, so that it can be
+//! shown without reading or decoding the audio files again.
+
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use image::codecs::jpeg::JpegEncoder;
+use lofty::{picture::PictureType, tag::Tag};
+
+/// Cover image file names looked for in the album directory, in order of
+/// preference, ignoring case.
+const FOLDER_COVERS: &[&str] = &[
+    "cover.jpg",
+    "cover.jpeg",
+    "cover.png",
+    "folder.jpg",
+    "folder.jpeg",
+    "folder.png",
+    "front.jpg",
+    "front.jpeg",
+    "front.png",
+];
+
+/// Larger covers are scaled down to fit within a square this many pixels
+/// across.
+const THUMBNAIL_SIZE: u32 = 512;
+
+const THUMBNAIL_QUALITY: u8 = 90;
+
+/// Returns the front cover embedded in a tag, or the first picture if none of
+/// them are marked as the front cover.
+pub(crate) fn embedded_cover(tag: &Tag) -> Option<Vec<u8>> {
+    tag.get_picture_type(PictureType::CoverFront)
+        .or_else(|| tag.pictures().first())
+        .map(|picture| picture.data().to_vec())
+}
+
+/// Reads the cover image in the directory of a track, if it has one.
+pub(crate) fn read_folder_cover(track_path: &Path) -> Result<Option<Vec<u8>>> {
+    let Some(dir) = track_path.parent() else {
+        return Ok(None);
+    };
+
+    let files: Vec<(String, PathBuf)> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            (name, entry.path())
+        })
+        .collect();
+
+    let cover = FOLDER_COVERS
+        .iter()
+        .find_map(|cover| files.iter().find(|(name, _)| name == cover));
+
+    match cover {
+        Some((_, path)) => Ok(Some(fs::read(path)?)),
+        None => Ok(None),
+    }
+}
+
+/// Adds a cover to the thumbnail cache, unless it is already there.
+///
+/// # Returns
+///
+/// Returns the hash identifying the cover.
+///
+/// # Errors
+///
+/// Returns an error if the image can not be decoded, or the thumbnail can not
+/// be written.
+pub(crate) fn cache_cover(data: &[u8]) -> Result<String> {
+    let hash = format!("{:016x}", xxhash_rust::xxh3::xxh3_64(data));
+
+    let path = thumbnail_path(&hash);
+    if path.exists() {
+        return Ok(hash);
+    }
+
+    let mut image = image::load_from_memory(data).context("Failed to decode cover art")?;
+    if image.width() > THUMBNAIL_SIZE || image.height() > THUMBNAIL_SIZE {
+        image = image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+    }
+    let thumbnail = image.to_rgb8();
+
+    let dir = cache_dir();
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+
+    // Written to a temporary file first so a partly written thumbnail is never
+    // mistaken for a cached one
+    let temp_path = path.with_extension("tmp");
+    let writer = BufWriter::new(File::create(&temp_path)?);
+    JpegEncoder::new_with_quality(writer, THUMBNAIL_QUALITY)
+        .encode_image(&thumbnail)
+        .with_context(|| format!("Failed to write thumbnail: {}", temp_path.display()))?;
+    fs::rename(&temp_path, &path)?;
+
+    Ok(hash)
+}
+
+/// The path of the cached thumbnail for a cover.
+pub(crate) fn thumbnail_path(hash: &str) -> PathBuf {
+    cache_dir().join(format!("{}.jpg", hash))
+}
+
+fn cache_dir() -> PathBuf {
+    let cache_home = std::env::var("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| std::env::var("HOME").map(|home| Path::new(&home).join(".cache")))
+        .unwrap_or_else(|_| PathBuf::from(".cache"));
+
+    cache_home.join("chooui").join("covers")
+}
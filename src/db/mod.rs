@@ -24,6 +24,19 @@
 //! * `artists` - Stores unique artist names.
 //! * `albums` - Groups tracks under titles, linked to artists.
 //! * `tracks` - Individual audio files with metadata and file paths.
+//! * `track_artists` - Every artist credited on a track, as split from its
+//!   artist tag.
+//! * `genres` - Unique genre names.
+//! * `track_genres` - Every genre of a track, as split from its genre tag.
+//! * `track_stats` - Play and skip counts, when last played, and ratings, star
+//!   ratings and when rated, keyed by durable track id.
+//! * `resume_positions` - Last playback position for resumable tracks.
+//! * `bookmarks` - Named positions within tracks.
+//! * `play_history` - Individual plays of tracks, and how long they lasted.
+//! * `pending_listens` - Listens not yet submitted to ListenBrainz.
+//! * `stations` - Internet radio stream URLs.
+//! * `podcasts` - Podcast feed subscriptions.
+//! * `episodes` - Episodes listed in podcast feeds, and their download state.
 //!
 //! # Performance
 //!
@@ -31,15 +44,33 @@
 //! to reduce SQL parsing overhead.
 
 mod model;
+pub(crate) mod organise;
 pub(crate) mod scan;
 
+use std::{collections::HashMap, path::Path};
+
 use anyhow::{Context, Result};
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params};
 
-use crate::model::{Album, Artist, Rating, Recency, SearchQuery, Track, TrackInfo};
+use crate::{
+    model::{
+        Album, Artist, Bookmark, Episode, FavouritesOrder, Genre, PendingListen, PlayFilter,
+        PlayRecord, Podcast, Rating, Recency, SearchOrder, SearchQuery, StarRating, Station, Track,
+        TrackInfo,
+        stats::{LibrarySummary, Tally},
+    },
+    podcast::Feed,
+    util::date::{date_from_timestamp, days_from_civil, now_timestamp},
+};
 
 const MIN_SEARCH_LEN: usize = 3;
 
+/// Matches tracks with any credited artist like the bound parameter.
+const TRACK_ARTIST_MATCH: &str = "EXISTS (
+    SELECT 1 FROM track_artists ta JOIN artists tar ON ta.artist_id = tar.id
+    WHERE ta.track_id = tr.id AND tar.name LIKE ?
+)";
+
 /// Opens a connection to the SQLite database and configures performance settings.
 ///
 /// This function performs the following setup:
@@ -108,13 +139,15 @@ fn create_schema(conn: &Connection) -> Result<()> {
 
         CREATE TABLE IF NOT EXISTS artists (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
-            name TEXT NOT NULL COLLATE NOCASE UNIQUE
+            name TEXT NOT NULL COLLATE NOCASE UNIQUE,
+            sort_name TEXT COLLATE NOCASE
         );
 
         CREATE TABLE IF NOT EXISTS albums (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             artist_id INTEGER NOT NULL,
             title TEXT NOT NULL COLLATE NOCASE,
+            cover_hash TEXT,
             UNIQUE (artist_id, title),
             FOREIGN KEY (artist_id) REFERENCES artists (id) ON DELETE CASCADE
         );
@@ -125,6 +158,7 @@ fn create_schema(conn: &Connection) -> Result<()> {
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             durable_id INTEGER NOT NULL UNIQUE,
             album_id INTEGER NOT NULL,
+            disc_number INTEGER,
             track_number INTEGER,
             title TEXT NOT NULL COLLATE NOCASE,
             duration INTEGER NOT NULL,
@@ -132,6 +166,11 @@ fn create_schema(conn: &Connection) -> Result<()> {
             year INTEGER,
             filename TEXT NOT NULL UNIQUE,
             created_at INTEGER NOT NULL,
+            is_podcast INTEGER NOT NULL DEFAULT 0,
+            lyrics TEXT,
+            artist TEXT,
+            album_artist TEXT,
+            composer TEXT,
             UNIQUE (album_id, filename),
             FOREIGN KEY (album_id) REFERENCES albums (id) ON DELETE CASCADE
         );
@@ -139,21 +178,179 @@ fn create_schema(conn: &Connection) -> Result<()> {
         CREATE INDEX IF NOT EXISTS idx_tracks_album_id ON tracks (album_id);
         CREATE INDEX IF NOT EXISTS idx_tracks_created_at ON tracks (created_at);
 
+        CREATE TABLE IF NOT EXISTS track_artists (
+            track_id INTEGER NOT NULL,
+            artist_id INTEGER NOT NULL,
+            PRIMARY KEY (track_id, artist_id),
+            FOREIGN KEY (track_id) REFERENCES tracks (id) ON DELETE CASCADE,
+            FOREIGN KEY (artist_id) REFERENCES artists (id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_track_artists_artist_id ON track_artists (artist_id);
+
+        CREATE TABLE IF NOT EXISTS genres (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL COLLATE NOCASE UNIQUE
+        );
+
+        CREATE TABLE IF NOT EXISTS track_genres (
+            track_id INTEGER NOT NULL,
+            genre_id INTEGER NOT NULL,
+            PRIMARY KEY (track_id, genre_id),
+            FOREIGN KEY (track_id) REFERENCES tracks (id) ON DELETE CASCADE,
+            FOREIGN KEY (genre_id) REFERENCES genres (id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_track_genres_genre_id ON track_genres (genre_id);
+
         CREATE TABLE IF NOT EXISTS track_stats (
             durable_id INTEGER PRIMARY KEY,
             play_count INTEGER NOT NULL DEFAULT 0,
-            rating INTEGER NOT NULL DEFAULT 0
+            rating INTEGER NOT NULL DEFAULT 0,
+            skip_count INTEGER NOT NULL DEFAULT 0,
+            last_played INTEGER,
+            rated_at INTEGER,
+            stars INTEGER
+        );
+
+        CREATE TABLE IF NOT EXISTS resume_positions (
+            durable_id INTEGER PRIMARY KEY,
+            position INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS bookmarks (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            durable_id INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            position INTEGER NOT NULL,
+            created_at INTEGER NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_bookmarks_durable_id ON bookmarks (durable_id);
+
+        CREATE TABLE IF NOT EXISTS play_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            durable_id INTEGER NOT NULL,
+            started_at INTEGER NOT NULL,
+            played_secs INTEGER NOT NULL,
+            skipped INTEGER NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_play_history_durable_id ON play_history (durable_id);
+        CREATE INDEX IF NOT EXISTS idx_play_history_started_at ON play_history (started_at);
+
+        CREATE TABLE IF NOT EXISTS pending_listens (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            listened_at INTEGER NOT NULL,
+            artist_name TEXT NOT NULL,
+            track_title TEXT NOT NULL,
+            album_title TEXT NOT NULL,
+            track_number INTEGER NOT NULL,
+            duration INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS stations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            url TEXT NOT NULL UNIQUE,
+            created_at INTEGER NOT NULL
         );
 
+        CREATE TABLE IF NOT EXISTS podcasts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            title TEXT NOT NULL,
+            feed_url TEXT NOT NULL UNIQUE,
+            created_at INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS episodes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            podcast_id INTEGER NOT NULL,
+            guid TEXT NOT NULL,
+            title TEXT NOT NULL,
+            url TEXT NOT NULL,
+            published_at INTEGER,
+            filename TEXT,
+            durable_id INTEGER,
+            played INTEGER NOT NULL DEFAULT 0,
+            UNIQUE (podcast_id, guid),
+            FOREIGN KEY (podcast_id) REFERENCES podcasts (id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_episodes_durable_id ON episodes (durable_id);
+
         COMMIT;",
     )
-    .context("Failed to create schema")
+    .context("Failed to create schema")?;
+
+    migrate_schema(conn)
+}
+
+/// Brings a database created by an earlier version up to date with the
+/// current schema.
+///
+/// New tables are handled by [`create_schema`], this only needs to deal with
+/// changes to existing tables.
+fn migrate_schema(conn: &Connection) -> Result<()> {
+    let columns = [
+        ("tracks", "is_podcast", "INTEGER NOT NULL DEFAULT 0"),
+        ("track_stats", "skip_count", "INTEGER NOT NULL DEFAULT 0"),
+        ("track_stats", "last_played", "INTEGER"),
+        ("track_stats", "rated_at", "INTEGER"),
+        ("track_stats", "stars", "INTEGER"),
+        ("albums", "cover_hash", "TEXT"),
+        ("tracks", "lyrics", "TEXT"),
+        ("tracks", "disc_number", "INTEGER"),
+        ("tracks", "album_artist", "TEXT"),
+        ("tracks", "composer", "TEXT"),
+        ("artists", "sort_name", "TEXT COLLATE NOCASE"),
+        ("tracks", "artist", "TEXT"),
+    ];
+
+    for (table, column, definition) in columns {
+        add_column_if_missing(conn, table, column, definition)
+            .context("Failed to migrate schema")?;
+    }
+
+    Ok(())
+}
+
+/// Adds a column to a table, unless the table already has it.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+/// * `table` - The name of the table.
+/// * `column` - The name of the column.
+/// * `definition` - The column type and constraints.
+fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<()> {
+    let exists: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info(?1) WHERE name = ?2",
+        params![table, column],
+        |row| row.get(0),
+    )?;
+
+    if !exists {
+        conn.execute_batch(&format!(
+            "ALTER TABLE {} ADD COLUMN {} {}",
+            table, column, definition
+        ))?;
+    }
+
+    Ok(())
 }
 
 /// Fetches all artist names from the database, sorted alphabetically.
 ///
 /// This function retrieves the complete list of artists available in the
-/// library.
+/// library. Artists with a sort name, such as "Beatles, The", are sorted by
+/// that instead of their name.
 ///
 /// # Arguments
 ///
@@ -171,7 +368,7 @@ fn create_schema(conn: &Connection) -> Result<()> {
 /// assert!(!artists.is_empty());
 /// ```
 pub(crate) fn fetch_artist_names(conn: &Connection) -> Result<Vec<Artist>> {
-    let mut stmt = conn.prepare_cached("SELECT id, name FROM artists ORDER BY name")?;
+    let mut stmt = conn.prepare_cached("SELECT id, name FROM artists ORDER BY COALESCE(sort_name, name)")?;
     let rows = stmt.query_map([], |row| {
         Ok(Artist {
             id: row.get(0)?,
@@ -191,7 +388,8 @@ pub(crate) fn fetch_artist_names(conn: &Connection) -> Result<Vec<Artist>> {
 /// alphabetically.
 ///
 /// This function retrieves the complete list of albums available in the
-/// library.
+/// library. Albums by other artists that the artist is credited on, such as
+/// compilations, are included.
 ///
 /// # Arguments
 ///
@@ -209,15 +407,17 @@ pub(crate) fn fetch_artist_names(conn: &Connection) -> Result<Vec<Artist>> {
 /// assert!(!albums.is_empty());
 /// ```
 pub(crate) fn fetch_artist_album_titles(conn: &Connection, artist_id: i32) -> Result<Vec<Album>> {
-    let mut stmt =
-        conn.prepare_cached("SELECT id, title, artist_id FROM albums WHERE artist_id = ?")?;
-    let rows = stmt.query_map([artist_id], |row| {
-        Ok(Album {
-            id: row.get(0)?,
-            title: row.get(1)?,
-            artist_id: row.get(2)?,
-        })
-    })?;
+    let mut stmt = conn.prepare_cached(
+        "SELECT al.id, al.title, al.artist_id, al.cover_hash, ar.name
+         FROM albums al
+         JOIN artists ar ON al.artist_id = ar.id
+         WHERE al.artist_id = ?1 OR al.id IN (
+            SELECT tr.album_id FROM tracks tr
+            JOIN track_artists ta ON tr.id = ta.track_id
+            WHERE ta.artist_id = ?1
+         )",
+    )?;
+    let rows = stmt.query_map([artist_id], album_from_row)?;
 
     let mut results = Vec::new();
     for row in rows {
@@ -227,8 +427,8 @@ pub(crate) fn fetch_artist_album_titles(conn: &Connection, artist_id: i32) -> Re
     Ok(results)
 }
 
-/// Fetches all tracks associated with a specific album, ordered by track
-/// number and title.
+/// Fetches all tracks associated with a specific album, ordered by disc
+/// number, track number and title.
 ///
 /// This function retrieves the complete list of tracks for the given album
 /// to facilitate sequential playback or detailed album views.
@@ -251,20 +451,21 @@ pub(crate) fn fetch_artist_album_titles(conn: &Connection, artist_id: i32) -> Re
 /// ```
 pub fn fetch_album_tracks(conn: &Connection, album_id: i32) -> Result<Vec<Track>> {
     let mut stmt = conn.prepare_cached(
-        "SELECT id, track_number, title, album_id, filename
+        "SELECT id, disc_number, track_number, title, album_id, filename
          FROM tracks
          WHERE album_id = ?
-         ORDER BY track_number, title
+         ORDER BY disc_number, track_number, title
     ",
     )?;
 
     let rows = stmt.query_map([album_id], |row| {
         Ok(Track {
             id: row.get(0)?,
-            track_number: row.get(1)?,
-            title: row.get(2)?,
-            album_id: row.get(3)?,
-            filename: row.get(4)?,
+            disc_number: row.get(1)?,
+            track_number: row.get(2)?,
+            title: row.get(3)?,
+            album_id: row.get(4)?,
+            filename: row.get(5)?,
         })
     })?;
 
@@ -275,10 +476,184 @@ pub fn fetch_album_tracks(conn: &Connection, album_id: i32) -> Result<Vec<Track>
     Ok(results)
 }
 
+/// Fetches all genres from the database, sorted alphabetically.
+pub(crate) fn fetch_genres(conn: &Connection) -> Result<Vec<Genre>> {
+    let mut stmt = conn.prepare_cached("SELECT id, name FROM genres ORDER BY name")?;
+    let results = stmt
+        .query_map([], |row| {
+            Ok(Genre {
+                id: row.get(0)?,
+                name: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Fetches the artists with albums that have tracks in a genre.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+/// * `genre_id` - The unique identifier of the genre.
+pub(crate) fn fetch_genre_artists(conn: &Connection, genre_id: i32) -> Result<Vec<Artist>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT id, name
+         FROM artists
+         WHERE id IN (
+            SELECT al.artist_id FROM albums al
+            JOIN tracks tr ON tr.album_id = al.id
+            JOIN track_genres tg ON tg.track_id = tr.id
+            WHERE tg.genre_id = ?
+         )
+         ORDER BY COALESCE(sort_name, name)",
+    )?;
+    let results = stmt
+        .query_map([genre_id], |row| {
+            Ok(Artist {
+                id: row.get(0)?,
+                name: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Fetches the albums of an artist that have tracks in a genre.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+/// * `genre_id` - The unique identifier of the genre.
+/// * `artist_id` - The unique identifier of the artist.
+pub(crate) fn fetch_genre_albums(
+    conn: &Connection,
+    genre_id: i32,
+    artist_id: i32,
+) -> Result<Vec<Album>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT al.id, al.title, al.artist_id, al.cover_hash, ar.name
+         FROM albums al
+         JOIN artists ar ON al.artist_id = ar.id
+         WHERE al.artist_id = ?2 AND al.id IN (
+            SELECT tr.album_id FROM tracks tr
+            JOIN track_genres tg ON tg.track_id = tr.id
+            WHERE tg.genre_id = ?1
+         )
+         ORDER BY al.title",
+    )?;
+    let results = stmt
+        .query_map([genre_id, artist_id], album_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Fetches the decades that catalogued tracks were released in, as the first
+/// year of each, oldest first.
+pub(crate) fn fetch_decades(conn: &Connection) -> Result<Vec<i64>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT DISTINCT year / 10 * 10
+         FROM tracks
+         WHERE year > 0 AND is_podcast = 0
+         ORDER BY 1",
+    )?;
+    let results = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Fetches the years in a decade that catalogued tracks were released in,
+/// oldest first.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+/// * `decade` - The first year of the decade.
+pub(crate) fn fetch_decade_years(conn: &Connection, decade: i64) -> Result<Vec<i64>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT DISTINCT year
+         FROM tracks
+         WHERE year BETWEEN ?1 AND ?1 + 9 AND is_podcast = 0
+         ORDER BY year",
+    )?;
+    let results = stmt
+        .query_map([decade], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Fetches the albums with tracks released in a year.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+/// * `year` - The year of release.
+pub(crate) fn fetch_year_albums(conn: &Connection, year: i64) -> Result<Vec<Album>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT al.id, al.title, al.artist_id, al.cover_hash, ar.name
+         FROM albums al
+         JOIN artists ar ON al.artist_id = ar.id
+         WHERE al.id IN (
+            SELECT album_id FROM tracks WHERE year = ? AND is_podcast = 0
+         )
+         ORDER BY COALESCE(ar.sort_name, ar.name), al.title",
+    )?;
+    let results = stmt
+        .query_map([year], album_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Fetches the albums most recently added to the catalog, newest first.
+///
+/// An album counts as added when its newest track was.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+/// * `limit` - The maximum number of albums to fetch.
+pub(crate) fn fetch_recent_albums(conn: &Connection, limit: u32) -> Result<Vec<Album>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT al.id, al.title, al.artist_id, al.cover_hash, ar.name
+         FROM albums al
+         JOIN artists ar ON al.artist_id = ar.id
+         JOIN tracks tr ON tr.album_id = al.id
+         WHERE tr.is_podcast = 0
+         GROUP BY al.id
+         ORDER BY MAX(tr.created_at) DESC, al.id DESC
+         LIMIT ?",
+    )?;
+    let results = stmt
+        .query_map([limit], album_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Maps an SQLite row of album id, title, artist id, cover hash and artist
+/// name to an [`Album`].
+fn album_from_row(row: &rusqlite::Row) -> rusqlite::Result<Album> {
+    Ok(Album {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        artist_id: row.get(2)?,
+        cover_hash: row.get(3)?,
+        artist_name: row.get(4)?,
+    })
+}
+
 /// Fetches all track information for an artist.
 ///
 /// This function looks up all tracks for the artist with the given id by
-/// joining the `tracks`, `albums` and `artists` tables.
+/// joining the `tracks`, `albums` and `artists` tables, along with the tracks
+/// on other albums that the artist is credited on.
 ///
 /// # Arguments
 ///
@@ -303,15 +678,17 @@ pub fn fetch_album_tracks(conn: &Connection, album_id: i32) -> Result<Vec<Track>
 pub(crate) fn fetch_artist_trackinfo(conn: &Connection, artist_id: i32) -> Result<Vec<TrackInfo>> {
     let sql = "
         SELECT
-            ar.name, al.title,
+            COALESCE(tr.artist, ar.name), al.title,
             tr.id, tr.durable_id, tr.track_number, tr.title, tr.duration, tr.year, tr.genre, tr.filename,
-            COALESCE(ts.play_count, 0), COALESCE(ts.rating, 0), tr.created_at
+            COALESCE(ts.play_count, 0), COALESCE(ts.rating, 0), tr.created_at, tr.is_podcast, ts.stars, al.cover_hash,
+            tr.disc_number, COALESCE(tr.album_artist, ar.name), tr.composer
         FROM tracks tr
         JOIN albums al ON tr.album_id = al.id
         JOIN artists ar ON al.artist_id = ar.id
         LEFT JOIN track_stats ts ON tr.durable_id = ts.durable_id
-        WHERE ar.id = ?
-        ORDER BY al.title, tr.track_number
+        WHERE ar.id = ?1
+            OR EXISTS (SELECT 1 FROM track_artists ta WHERE ta.track_id = tr.id AND ta.artist_id = ?1)
+        ORDER BY al.title, tr.disc_number, tr.track_number
     ";
 
     let mut stmt = conn.prepare_cached(sql)?;
@@ -322,6 +699,76 @@ pub(crate) fn fetch_artist_trackinfo(conn: &Connection, artist_id: i32) -> Resul
     Ok(results)
 }
 
+/// Fetches all track information for a genre, optionally only for one artist.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+/// * `genre_id` - The unique identifier of the genre.
+/// * `artist_id` - The unique identifier of the artist, or `None` for all
+///   artists.
+pub(crate) fn fetch_genre_trackinfo(
+    conn: &Connection,
+    genre_id: i32,
+    artist_id: Option<i32>,
+) -> Result<Vec<TrackInfo>> {
+    let sql = "
+        SELECT
+            COALESCE(tr.artist, ar.name), al.title,
+            tr.id, tr.durable_id, tr.track_number, tr.title, tr.duration, tr.year, tr.genre, tr.filename,
+            COALESCE(ts.play_count, 0), COALESCE(ts.rating, 0), tr.created_at, tr.is_podcast, ts.stars, al.cover_hash,
+            tr.disc_number, COALESCE(tr.album_artist, ar.name), tr.composer
+        FROM tracks tr
+        JOIN albums al ON tr.album_id = al.id
+        JOIN artists ar ON al.artist_id = ar.id
+        JOIN track_genres tg ON tg.track_id = tr.id
+        LEFT JOIN track_stats ts ON tr.durable_id = ts.durable_id
+        WHERE tg.genre_id = ?1 AND (?2 IS NULL OR al.artist_id = ?2)
+        ORDER BY COALESCE(ar.sort_name, ar.name), al.title, tr.disc_number, tr.track_number
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    let results = stmt
+        .query_map(params![genre_id, artist_id], TrackInfo::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Fetches all track information for the tracks released in a range of years.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+/// * `from` - The first year of the range.
+/// * `to` - The last year of the range.
+pub(crate) fn fetch_years_trackinfo(
+    conn: &Connection,
+    from: i64,
+    to: i64,
+) -> Result<Vec<TrackInfo>> {
+    let sql = "
+        SELECT
+            COALESCE(tr.artist, ar.name), al.title,
+            tr.id, tr.durable_id, tr.track_number, tr.title, tr.duration, tr.year, tr.genre, tr.filename,
+            COALESCE(ts.play_count, 0), COALESCE(ts.rating, 0), tr.created_at, tr.is_podcast, ts.stars, al.cover_hash,
+            tr.disc_number, COALESCE(tr.album_artist, ar.name), tr.composer
+        FROM tracks tr
+        JOIN albums al ON tr.album_id = al.id
+        JOIN artists ar ON al.artist_id = ar.id
+        LEFT JOIN track_stats ts ON tr.durable_id = ts.durable_id
+        WHERE tr.year BETWEEN ? AND ? AND tr.is_podcast = 0
+        ORDER BY tr.year, COALESCE(ar.sort_name, ar.name), al.title, tr.disc_number, tr.track_number
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    let results = stmt
+        .query_map([from, to], TrackInfo::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
 /// Fetches all track information for an album.
 ///
 /// This function looks up all tracks for the album with the given id by
@@ -350,15 +797,16 @@ pub(crate) fn fetch_artist_trackinfo(conn: &Connection, artist_id: i32) -> Resul
 pub(crate) fn fetch_album_track_info(conn: &Connection, album_id: i32) -> Result<Vec<TrackInfo>> {
     let sql = "
         SELECT
-            ar.name, al.title,
+            COALESCE(tr.artist, ar.name), al.title,
             tr.id, tr.durable_id, tr.track_number, tr.title, tr.duration, tr.year, tr.genre, tr.filename,
-            COALESCE(ts.play_count, 0), COALESCE(ts.rating, 0), tr.created_at
+            COALESCE(ts.play_count, 0), COALESCE(ts.rating, 0), tr.created_at, tr.is_podcast, ts.stars, al.cover_hash,
+            tr.disc_number, COALESCE(tr.album_artist, ar.name), tr.composer
         FROM tracks tr
         JOIN albums al ON tr.album_id = al.id
         JOIN artists ar ON al.artist_id = ar.id
         LEFT JOIN track_stats ts ON tr.durable_id = ts.durable_id
         WHERE al.id = ?
-        ORDER BY tr.track_number
+        ORDER BY tr.disc_number, tr.track_number
     ";
 
     let mut stmt = conn.prepare_cached(sql)?;
@@ -399,9 +847,10 @@ pub(crate) fn fetch_album_track_info(conn: &Connection, album_id: i32) -> Result
 pub(crate) fn fetch_track_info(conn: &Connection, track_id: i32) -> Result<TrackInfo> {
     let sql = "
         SELECT
-            ar.name, al.title,
+            COALESCE(tr.artist, ar.name), al.title,
             tr.id, tr.durable_id, tr.track_number, tr.title, tr.duration, tr.year, tr.genre, tr.filename,
-            COALESCE(ts.play_count, 0), COALESCE(ts.rating, 0), tr.created_at
+            COALESCE(ts.play_count, 0), COALESCE(ts.rating, 0), tr.created_at, tr.is_podcast, ts.stars, al.cover_hash,
+            tr.disc_number, COALESCE(tr.album_artist, ar.name), tr.composer
         FROM tracks tr
         JOIN albums al ON tr.album_id = al.id
         JOIN artists ar ON al.artist_id = ar.id
@@ -415,12 +864,42 @@ pub(crate) fn fetch_track_info(conn: &Connection, track_id: i32) -> Result<Track
     Ok(result)
 }
 
+/// Fetches the id and filename of every catalogued track that is not a
+/// podcast episode, sorted by filename.
+pub(crate) fn fetch_track_files(conn: &Connection) -> Result<Vec<(i32, String)>> {
+    let sql = "
+        SELECT id, filename
+        FROM tracks
+        WHERE is_podcast = 0
+        ORDER BY filename
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    let results = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+pub(crate) fn update_track_filename(
+    conn: &Connection,
+    track_id: i32,
+    filename: &str,
+) -> Result<()> {
+    let mut stmt = conn.prepare_cached("UPDATE tracks SET filename = ?1 WHERE id = ?2")?;
+    stmt.execute(params![filename, track_id])?;
+
+    Ok(())
+}
+
 pub(crate) fn search(conn: &Connection, query: &SearchQuery) -> Result<Vec<TrackInfo>> {
     let mut sql = String::from("
         SELECT
-            ar.name, al.title,
+            COALESCE(tr.artist, ar.name), al.title,
             tr.id, tr.durable_id, tr.track_number, tr.title, tr.duration, tr.year, tr.genre, tr.filename,
-            COALESCE(ts.play_count, 0), COALESCE(ts.rating, 0), tr.created_at
+            COALESCE(ts.play_count, 0), COALESCE(ts.rating, 0), tr.created_at, tr.is_podcast, ts.stars, al.cover_hash,
+            tr.disc_number, COALESCE(tr.album_artist, ar.name), tr.composer
         FROM tracks tr
         JOIN albums al ON tr.album_id = al.id
         JOIN artists ar ON al.artist_id = ar.id
@@ -431,16 +910,21 @@ pub(crate) fn search(conn: &Connection, query: &SearchQuery) -> Result<Vec<Track
     let mut params = Vec::new();
 
     if query.search.len() >= MIN_SEARCH_LEN {
-        filters.push("(ar.name LIKE ? OR al.title LIKE ? OR tr.title LIKE ?)".to_string());
+        filters.push(format!(
+            "(ar.name LIKE ? OR {TRACK_ARTIST_MATCH} OR al.title LIKE ? OR tr.title LIKE ?)"
+        ));
         let param = format!("%{}%", query.search);
         params.push(param.clone());
         params.push(param.clone());
         params.push(param.clone());
+        params.push(param.clone());
     }
 
     if query.artist.len() >= MIN_SEARCH_LEN {
-        filters.push("(ar.name LIKE ?)".to_string());
-        params.push(format!("%{}%", query.artist.to_lowercase()));
+        filters.push(format!("(ar.name LIKE ? OR {TRACK_ARTIST_MATCH})"));
+        let param = format!("%{}%", query.artist.to_lowercase());
+        params.push(param.clone());
+        params.push(param);
     }
 
     if query.album.len() >= MIN_SEARCH_LEN {
@@ -453,6 +937,11 @@ pub(crate) fn search(conn: &Connection, query: &SearchQuery) -> Result<Vec<Track
         params.push(format!("%{}%", query.track.to_lowercase()));
     }
 
+    for words in query.lyrics.iter().filter(|words| words.len() >= MIN_SEARCH_LEN) {
+        filters.push("(tr.lyrics LIKE ?)".to_string());
+        params.push(format!("%{}%", words.to_lowercase()));
+    }
+
     if let Some(recency) = &query.recency {
         let sql_threshold = match recency {
             Recency::LastDay => "strftime('%s', 'now', 'localtime', 'start of day', 'utc')",
@@ -462,15 +951,40 @@ pub(crate) fn search(conn: &Connection, query: &SearchQuery) -> Result<Vec<Track
         filters.push(format!("tr.created_at >= ({})", sql_threshold));
     }
 
+    // These only ever contain numbers, so are safe to embed in the SQL
+    for filter in &query.play_filters {
+        filters.push(match filter {
+            PlayFilter::MorePlaysThan(count) => format!("COALESCE(ts.play_count, 0) > {count}"),
+            PlayFilter::FewerPlaysThan(count) => format!("COALESCE(ts.play_count, 0) < {count}"),
+            PlayFilter::MoreSkipsThan(count) => format!("COALESCE(ts.skip_count, 0) > {count}"),
+            PlayFilter::FewerSkipsThan(count) => format!("COALESCE(ts.skip_count, 0) < {count}"),
+            PlayFilter::PlayedWithin(secs) => {
+                format!("ts.last_played >= strftime('%s', 'now') - {secs}")
+            }
+            PlayFilter::NotPlayedWithin(secs) => format!(
+                "(ts.last_played IS NULL OR ts.last_played < strftime('%s', 'now') - {secs})"
+            ),
+        });
+    }
+
     if !filters.is_empty() {
         sql.push_str(" WHERE ");
         sql.push_str(&filters.join(" AND "));
     }
 
     if query.recency.is_some() {
-        sql.push_str(" ORDER BY (SELECT MAX(t2.created_at) FROM tracks t2 WHERE t2.album_id = tr.album_id) DESC, tr.album_id, tr.track_number");
+        sql.push_str(" ORDER BY (SELECT MAX(t2.created_at) FROM tracks t2 WHERE t2.album_id = tr.album_id) DESC, tr.album_id, tr.disc_number, tr.track_number");
     } else {
-        sql.push_str(" ORDER BY ar.name, al.title, tr.track_number");
+        let order = match query.order {
+            SearchOrder::Library => "",
+            SearchOrder::MostPlayed => "COALESCE(ts.play_count, 0) DESC, ",
+            SearchOrder::MostSkipped => "COALESCE(ts.skip_count, 0) DESC, ",
+            SearchOrder::RecentlyPlayed => "COALESCE(ts.last_played, 0) DESC, ",
+            SearchOrder::LeastRecentlyPlayed => "COALESCE(ts.last_played, 0), ",
+        };
+        sql.push_str(&format!(
+            " ORDER BY {order}COALESCE(ar.sort_name, ar.name), al.title, tr.disc_number, tr.track_number"
+        ));
     }
 
     let mut stmt = conn.prepare_cached(&sql)?;
@@ -481,12 +995,15 @@ pub(crate) fn search(conn: &Connection, query: &SearchQuery) -> Result<Vec<Track
     Ok(results)
 }
 
+/// Counts a play of a track, also recording when it was played.
+///
+/// Returns the new play count.
 pub(crate) fn increment_play_count(conn: &Connection, durable_id: i64) -> Result<i64> {
     let sql = "
-        INSERT INTO track_stats (durable_id, play_count)
-        VALUES (?1, 1)
+        INSERT INTO track_stats (durable_id, play_count, last_played)
+        VALUES (?1, 1, strftime('%s', 'now'))
         ON CONFLICT (durable_id)
-        DO UPDATE SET play_count = play_count + 1
+        DO UPDATE SET play_count = play_count + 1, last_played = excluded.last_played
         RETURNING play_count
     ";
 
@@ -496,17 +1013,884 @@ pub(crate) fn increment_play_count(conn: &Connection, durable_id: i64) -> Result
     Ok(new_count)
 }
 
-pub(crate) fn update_rating(conn: &Connection, durable_id: i64, rating: Rating) -> Result<Rating> {
+/// Counts a track being skipped before it had played for long enough to count
+/// as a play.
+pub(crate) fn increment_skip_count(conn: &Connection, durable_id: i64) -> Result<()> {
+    let sql = "
+        INSERT INTO track_stats (durable_id, skip_count)
+        VALUES (?1, 1)
+        ON CONFLICT (durable_id)
+        DO UPDATE SET skip_count = skip_count + 1
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    stmt.execute(params![durable_id])?;
+
+    Ok(())
+}
+
+/// Rates a track, also recording when it was rated.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+/// * `durable_id` - The durable id of the track.
+/// * `rating` - The new rating.
+/// * `stars` - The new star rating, which should imply the same rating.
+///
+/// Returns the new rating and star rating.
+pub(crate) fn update_rating(
+    conn: &Connection,
+    durable_id: i64,
+    rating: Rating,
+    stars: Option<StarRating>,
+) -> Result<(Rating, Option<StarRating>)> {
     let sql = "
-        INSERT INTO track_stats (durable_id, rating)
-        VALUES (?1, ?2)
+        INSERT INTO track_stats (durable_id, rating, stars, rated_at)
+        VALUES (?1, ?2, ?3, strftime('%s', 'now'))
         ON CONFLICT (durable_id)
-        DO UPDATE SET rating = ?2
-        RETURNING rating
+        DO UPDATE SET rating = ?2, stars = ?3, rated_at = excluded.rated_at
+        RETURNING rating, stars
     ";
 
     let mut stmt = conn.prepare_cached(sql)?;
-    let new_rating: Rating = stmt.query_row(params![durable_id, rating], |row| row.get(0))?;
+    let new_rating = stmt.query_row(params![durable_id, rating, stars], |row| {
+        Ok((row.get(0)?, row.get(1)?))
+    })?;
 
     Ok(new_rating)
 }
+
+/// Fetches the saved playback position for a track, if there is one.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+/// * `durable_id` - The durable identifier of the track.
+pub(crate) fn fetch_resume_position(conn: &Connection, durable_id: i64) -> Result<Option<u64>> {
+    let sql = "SELECT position FROM resume_positions WHERE durable_id = ?";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    let position: Option<i64> = stmt
+        .query_row(params![durable_id], |row| row.get(0))
+        .optional()?;
+
+    Ok(position.and_then(|p| u64::try_from(p).ok()))
+}
+
+pub(crate) fn save_resume_position(
+    conn: &Connection,
+    durable_id: i64,
+    position: u64,
+) -> Result<()> {
+    let sql = "
+        INSERT INTO resume_positions (durable_id, position, updated_at)
+        VALUES (?1, ?2, strftime('%s', 'now'))
+        ON CONFLICT (durable_id)
+        DO UPDATE SET position = ?2, updated_at = strftime('%s', 'now')
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    stmt.execute(params![durable_id, position as i64])?;
+
+    Ok(())
+}
+
+pub(crate) fn clear_resume_position(conn: &Connection, durable_id: i64) -> Result<()> {
+    let mut stmt = conn.prepare_cached("DELETE FROM resume_positions WHERE durable_id = ?")?;
+    stmt.execute(params![durable_id])?;
+
+    Ok(())
+}
+
+/// Fetches all bookmarks, ordered by track and then by position within the
+/// track.
+///
+/// Bookmarks are linked to tracks by durable id, so bookmarks for tracks that
+/// are no longer in the catalog are simply not returned, and will reappear if
+/// the track is scanned again.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+///
+/// # Errors
+///
+/// Returns an error if the SQL query fails or if there is a type mismatch
+/// when mapping the database rows to the [`Bookmark`] struct.
+pub(crate) fn fetch_bookmarks(conn: &Connection) -> Result<Vec<Bookmark>> {
+    let sql = "
+        SELECT
+            COALESCE(tr.artist, ar.name), al.title,
+            tr.id, tr.durable_id, tr.track_number, tr.title, tr.duration, tr.year, tr.genre, tr.filename,
+            COALESCE(ts.play_count, 0), COALESCE(ts.rating, 0), tr.created_at, tr.is_podcast, ts.stars, al.cover_hash,
+            tr.disc_number, COALESCE(tr.album_artist, ar.name), tr.composer,
+            bm.id, bm.name, bm.position
+        FROM bookmarks bm
+        JOIN tracks tr ON bm.durable_id = tr.durable_id
+        JOIN albums al ON tr.album_id = al.id
+        JOIN artists ar ON al.artist_id = ar.id
+        LEFT JOIN track_stats ts ON tr.durable_id = ts.durable_id
+        ORDER BY COALESCE(ar.sort_name, ar.name), al.title, tr.disc_number, tr.track_number, bm.position
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    let results = stmt
+        .query_map([], Bookmark::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+pub(crate) fn add_bookmark(
+    conn: &Connection,
+    durable_id: i64,
+    name: &str,
+    position: u64,
+) -> Result<()> {
+    let sql = "
+        INSERT INTO bookmarks (durable_id, name, position, created_at)
+        VALUES (?1, ?2, ?3, strftime('%s', 'now'))
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    stmt.execute(params![durable_id, name, position as i64])?;
+
+    Ok(())
+}
+
+pub(crate) fn delete_bookmark(conn: &Connection, bookmark_id: i32) -> Result<()> {
+    let mut stmt = conn.prepare_cached("DELETE FROM bookmarks WHERE id = ?")?;
+    stmt.execute(params![bookmark_id])?;
+
+    Ok(())
+}
+
+/// Fetches the most recent plays from the play history, newest first.
+///
+/// As with bookmarks, plays of tracks that are no longer in the catalog are not
+/// returned.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+/// * `limit` - The maximum number of plays to return.
+///
+/// # Errors
+///
+/// Returns an error if the SQL query fails or if there is a type mismatch
+/// when mapping the database rows to the [`PlayRecord`] struct.
+pub(crate) fn fetch_play_history(conn: &Connection, limit: u32) -> Result<Vec<PlayRecord>> {
+    let sql = "
+        SELECT
+            COALESCE(tr.artist, ar.name), al.title,
+            tr.id, tr.durable_id, tr.track_number, tr.title, tr.duration, tr.year, tr.genre, tr.filename,
+            COALESCE(ts.play_count, 0), COALESCE(ts.rating, 0), tr.created_at, tr.is_podcast, ts.stars, al.cover_hash,
+            tr.disc_number, COALESCE(tr.album_artist, ar.name), tr.composer,
+            ph.started_at, ph.played_secs, ph.skipped
+        FROM play_history ph
+        JOIN tracks tr ON ph.durable_id = tr.durable_id
+        JOIN albums al ON tr.album_id = al.id
+        JOIN artists ar ON al.artist_id = ar.id
+        LEFT JOIN track_stats ts ON tr.durable_id = ts.durable_id
+        ORDER BY ph.started_at DESC, ph.id DESC
+        LIMIT ?
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    let results = stmt
+        .query_map(params![limit], PlayRecord::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Records a single play of a track in the play history.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+/// * `durable_id` - The durable id of the track that was played.
+/// * `started_at` - When playback started, as a Unix timestamp.
+/// * `played_secs` - How many seconds of the track were actually listened to.
+/// * `skipped` - Whether the track was skipped before it finished.
+pub(crate) fn add_play_history(
+    conn: &Connection,
+    durable_id: i64,
+    started_at: i64,
+    played_secs: u64,
+    skipped: bool,
+) -> Result<()> {
+    let sql = "
+        INSERT INTO play_history (durable_id, started_at, played_secs, skipped)
+        VALUES (?1, ?2, ?3, ?4)
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    stmt.execute(params![durable_id, started_at, played_secs as i64, skipped])?;
+
+    Ok(())
+}
+
+/// Fetches all internet radio stations, sorted by name.
+pub(crate) fn fetch_stations(conn: &Connection) -> Result<Vec<Station>> {
+    let sql = "
+        SELECT id, name, url
+        FROM stations
+        ORDER BY name COLLATE NOCASE
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    let results = stmt
+        .query_map([], Station::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Adds an internet radio station.
+///
+/// Adding a URL that is already known renames the existing station instead.
+pub(crate) fn add_station(conn: &Connection, name: &str, url: &str) -> Result<()> {
+    let sql = "
+        INSERT INTO stations (name, url, created_at)
+        VALUES (?1, ?2, strftime('%s', 'now'))
+        ON CONFLICT(url) DO UPDATE SET name = excluded.name
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    stmt.execute(params![name, url])?;
+
+    Ok(())
+}
+
+pub(crate) fn delete_station(conn: &Connection, station_id: i32) -> Result<()> {
+    let mut stmt = conn.prepare_cached("DELETE FROM stations WHERE id = ?")?;
+    stmt.execute(params![station_id])?;
+
+    Ok(())
+}
+
+/// Fetches a track by its durable id, if it is in the catalog.
+pub(crate) fn fetch_trackinfo_by_durable_id(
+    conn: &Connection,
+    durable_id: i64,
+) -> Result<Option<TrackInfo>> {
+    let sql = "
+        SELECT
+            COALESCE(tr.artist, ar.name), al.title,
+            tr.id, tr.durable_id, tr.track_number, tr.title, tr.duration, tr.year, tr.genre, tr.filename,
+            COALESCE(ts.play_count, 0), COALESCE(ts.rating, 0), tr.created_at, tr.is_podcast, ts.stars, al.cover_hash,
+            tr.disc_number, COALESCE(tr.album_artist, ar.name), tr.composer
+        FROM tracks tr
+        JOIN albums al ON tr.album_id = al.id
+        JOIN artists ar ON al.artist_id = ar.id
+        LEFT JOIN track_stats ts ON tr.durable_id = ts.durable_id
+        WHERE tr.durable_id = ?
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    let result = stmt
+        .query_row([durable_id], TrackInfo::from_row)
+        .optional()?;
+
+    Ok(result)
+}
+
+/// Fetches every track with a particular rating, for the favourites view.
+///
+/// Podcast episodes are never included.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+/// * `rating` - The rating of the tracks to fetch.
+/// * `order` - The order to fetch the tracks in.
+pub(crate) fn fetch_rated_tracks(
+    conn: &Connection,
+    rating: Rating,
+    order: FavouritesOrder,
+) -> Result<Vec<TrackInfo>> {
+    // Tracks rated before ratings were timestamped sort after all the others
+    let order_by = match order {
+        FavouritesOrder::RatedAt => {
+            "COALESCE(ts.rated_at, 0) DESC, COALESCE(ar.sort_name, ar.name), al.title, tr.disc_number, tr.track_number"
+        }
+        FavouritesOrder::Artist => {
+            "COALESCE(ar.sort_name, ar.name), al.title, tr.disc_number, tr.track_number"
+        }
+        FavouritesOrder::Album => {
+            "al.title, COALESCE(ar.sort_name, ar.name), tr.disc_number, tr.track_number"
+        }
+    };
+
+    let sql = format!(
+        "
+        SELECT
+            COALESCE(tr.artist, ar.name), al.title,
+            tr.id, tr.durable_id, tr.track_number, tr.title, tr.duration, tr.year, tr.genre, tr.filename,
+            COALESCE(ts.play_count, 0), COALESCE(ts.rating, 0), tr.created_at, tr.is_podcast, ts.stars, al.cover_hash,
+            tr.disc_number, COALESCE(tr.album_artist, ar.name), tr.composer
+        FROM tracks tr
+        JOIN albums al ON tr.album_id = al.id
+        JOIN artists ar ON al.artist_id = ar.id
+        JOIN track_stats ts ON tr.durable_id = ts.durable_id
+        WHERE tr.is_podcast = 0 AND ts.rating = ?
+        ORDER BY {order_by}
+        "
+    );
+
+    let mut stmt = conn.prepare_cached(&sql)?;
+    let results = stmt
+        .query_map([rating], TrackInfo::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Fetches a random sample of tracks for the Auto-DJ to choose from.
+///
+/// Podcast episodes, disliked tracks and tracks played too recently are never
+/// included.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+/// * `limit` - The maximum number of tracks to fetch.
+/// * `replay_gap_secs` - Tracks played within this many seconds are left out.
+pub(crate) fn fetch_auto_dj_candidates(
+    conn: &Connection,
+    limit: u32,
+    replay_gap_secs: u64,
+) -> Result<Vec<TrackInfo>> {
+    let sql = "
+        SELECT
+            COALESCE(tr.artist, ar.name), al.title,
+            tr.id, tr.durable_id, tr.track_number, tr.title, tr.duration, tr.year, tr.genre, tr.filename,
+            COALESCE(ts.play_count, 0), COALESCE(ts.rating, 0), tr.created_at, tr.is_podcast, ts.stars, al.cover_hash,
+            tr.disc_number, COALESCE(tr.album_artist, ar.name), tr.composer
+        FROM tracks tr
+        JOIN albums al ON tr.album_id = al.id
+        JOIN artists ar ON al.artist_id = ar.id
+        LEFT JOIN track_stats ts ON tr.durable_id = ts.durable_id
+        WHERE tr.is_podcast = 0 AND COALESCE(ts.rating, 0) >= 0
+            AND (ts.last_played IS NULL OR ts.last_played < strftime('%s', 'now') - ?1)
+        ORDER BY RANDOM()
+        LIMIT ?2
+    ";
+
+    let replay_gap_secs = i64::try_from(replay_gap_secs).unwrap_or(i64::MAX);
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    let results = stmt
+        .query_map(params![replay_gap_secs, limit], TrackInfo::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Queues a listen for submission to ListenBrainz.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+/// * `track` - The track that was listened to.
+/// * `listened_at` - When playback started, as a Unix timestamp.
+pub(crate) fn add_pending_listen(
+    conn: &Connection,
+    track: &TrackInfo,
+    listened_at: i64,
+) -> Result<()> {
+    let sql = "
+        INSERT INTO pending_listens
+            (listened_at, artist_name, track_title, album_title, track_number, duration)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    stmt.execute(params![
+        listened_at,
+        track.artist_name,
+        track.track_title,
+        track.album_title,
+        track.track_number,
+        track.duration
+    ])?;
+
+    Ok(())
+}
+
+/// Fetches the oldest listens waiting to be submitted to ListenBrainz.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+/// * `limit` - The maximum number of listens to fetch.
+pub(crate) fn fetch_pending_listens(conn: &Connection, limit: u32) -> Result<Vec<PendingListen>> {
+    let sql = "
+        SELECT id, listened_at, artist_name, track_title, album_title, track_number, duration
+        FROM pending_listens
+        ORDER BY id
+        LIMIT ?
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    let results = stmt
+        .query_map([limit], PendingListen::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Removes listens that have been submitted to ListenBrainz.
+pub(crate) fn delete_pending_listens(conn: &mut Connection, ids: &[i64]) -> Result<()> {
+    let tx = conn.transaction()?;
+    {
+        let mut stmt = tx.prepare_cached("DELETE FROM pending_listens WHERE id = ?")?;
+        for id in ids {
+            stmt.execute([id])?;
+        }
+    }
+    tx.commit()?;
+
+    Ok(())
+}
+
+/// Fetches the overall size and usage of the music library.
+///
+/// Podcast episodes are not included, and tracks with an unknown duration add
+/// nothing to the total durations.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+pub(crate) fn fetch_library_summary(conn: &Connection) -> Result<LibrarySummary> {
+    let sql = "
+        SELECT
+            COUNT(DISTINCT al.artist_id),
+            COUNT(DISTINCT tr.album_id),
+            COUNT(*),
+            COALESCE(SUM(MAX(tr.duration, 0)), 0),
+            COALESCE(SUM(ts.play_count), 0),
+            COALESCE(SUM(COALESCE(ts.play_count, 0) * MAX(tr.duration, 0)), 0),
+            COUNT(*) FILTER (WHERE COALESCE(ts.play_count, 0) = 0),
+            COUNT(*) FILTER (WHERE ts.rating > 0),
+            COUNT(*) FILTER (WHERE ts.rating < 0)
+        FROM tracks tr
+        JOIN albums al ON tr.album_id = al.id
+        LEFT JOIN track_stats ts ON tr.durable_id = ts.durable_id
+        WHERE tr.is_podcast = 0
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    let summary = stmt.query_row([], |row| {
+        let count = |idx: usize| -> rusqlite::Result<u64> {
+            let value: i64 = row.get(idx)?;
+            Ok(u64::try_from(value).unwrap_or(0))
+        };
+        Ok(LibrarySummary {
+            artists: count(0)?,
+            albums: count(1)?,
+            tracks: count(2)?,
+            duration: count(3)?,
+            plays: count(4)?,
+            listening_time: count(5)?,
+            never_played: count(6)?,
+            liked: count(7)?,
+            disliked: count(8)?,
+        })
+    })?;
+
+    Ok(summary)
+}
+
+/// Fetches the most played artists, with their total play counts.
+pub(crate) fn fetch_top_artists(conn: &Connection, limit: u32) -> Result<Vec<Tally>> {
+    let sql = "
+        SELECT ar.name, SUM(ts.play_count) AS plays
+        FROM tracks tr
+        JOIN albums al ON tr.album_id = al.id
+        JOIN artists ar ON al.artist_id = ar.id
+        JOIN track_stats ts ON tr.durable_id = ts.durable_id
+        WHERE tr.is_podcast = 0 AND ts.play_count > 0
+        GROUP BY ar.id
+        ORDER BY plays DESC, ar.name
+        LIMIT ?
+    ";
+
+    fetch_tallies(conn, sql, limit)
+}
+
+/// Fetches the most played albums, labelled "album - artist", with their total
+/// play counts.
+pub(crate) fn fetch_top_albums(conn: &Connection, limit: u32) -> Result<Vec<Tally>> {
+    let sql = "
+        SELECT al.title || ' - ' || ar.name, SUM(ts.play_count) AS plays
+        FROM tracks tr
+        JOIN albums al ON tr.album_id = al.id
+        JOIN artists ar ON al.artist_id = ar.id
+        JOIN track_stats ts ON tr.durable_id = ts.durable_id
+        WHERE tr.is_podcast = 0 AND ts.play_count > 0
+        GROUP BY al.id
+        ORDER BY plays DESC, al.title
+        LIMIT ?
+    ";
+
+    fetch_tallies(conn, sql, limit)
+}
+
+/// Fetches the most played tracks, labelled "title - artist", with their play
+/// counts.
+pub(crate) fn fetch_top_tracks(conn: &Connection, limit: u32) -> Result<Vec<Tally>> {
+    let sql = "
+        SELECT tr.title || ' - ' || ar.name, ts.play_count
+        FROM tracks tr
+        JOIN albums al ON tr.album_id = al.id
+        JOIN artists ar ON al.artist_id = ar.id
+        JOIN track_stats ts ON tr.durable_id = ts.durable_id
+        WHERE tr.is_podcast = 0 AND ts.play_count > 0
+        ORDER BY ts.play_count DESC, tr.title
+        LIMIT ?
+    ";
+
+    fetch_tallies(conn, sql, limit)
+}
+
+/// Fetches the most played genres, with their total play counts.
+///
+/// Tracks without a genre are ignored.
+pub(crate) fn fetch_genre_plays(conn: &Connection, limit: u32) -> Result<Vec<Tally>> {
+    let sql = "
+        SELECT tr.genre, SUM(ts.play_count) AS plays
+        FROM tracks tr
+        JOIN track_stats ts ON tr.durable_id = ts.durable_id
+        WHERE tr.is_podcast = 0 AND ts.play_count > 0 AND COALESCE(tr.genre, '') <> ''
+        GROUP BY tr.genre COLLATE NOCASE
+        ORDER BY plays DESC, tr.genre
+        LIMIT ?
+    ";
+
+    fetch_tallies(conn, sql, limit)
+}
+
+/// Counts the tracks in the library by file format, most common first.
+///
+/// The format is taken from the file extension, since that is all the catalog
+/// records.
+pub(crate) fn fetch_format_counts(conn: &Connection) -> Result<Vec<Tally>> {
+    let mut stmt = conn.prepare_cached("SELECT filename FROM tracks WHERE is_podcast = 0")?;
+    let mut rows = stmt.query([])?;
+
+    let mut counts: HashMap<String, u64> = HashMap::new();
+    while let Some(row) = rows.next()? {
+        let filename: String = row.get(0)?;
+        let format = Path::new(&filename)
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_uppercase())
+            .unwrap_or_else(|| "Unknown".to_string());
+        *counts.entry(format).or_default() += 1;
+    }
+
+    let mut formats: Vec<Tally> = counts.into_iter().collect();
+    formats.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    Ok(formats)
+}
+
+/// Counts the tracks added to the catalog in each of the most recent months,
+/// including the current month.
+///
+/// Every month is returned, oldest first, even if no tracks were added in it.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the SQLite connection.
+/// * `months` - The number of months to count.
+pub(crate) fn fetch_tracks_added_per_month(conn: &Connection, months: u32) -> Result<Vec<Tally>> {
+    let (year, month, _) = date_from_timestamp(now_timestamp());
+
+    // Work back to the first month to be counted
+    let first = year * 12 + i64::from(month) - 1 - i64::from(months.saturating_sub(1));
+    let labels: Vec<String> = (first..first + i64::from(months))
+        .map(|m| format!("{:04}-{:02}", m.div_euclid(12), m.rem_euclid(12) + 1))
+        .collect();
+
+    let start = days_from_civil(first.div_euclid(12), first.rem_euclid(12) as u32 + 1, 1) * 86_400;
+
+    let sql = "
+        SELECT strftime('%Y-%m', created_at, 'unixepoch') AS month, COUNT(*)
+        FROM tracks
+        WHERE is_podcast = 0 AND created_at >= ?
+        GROUP BY month
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    let counts = stmt
+        .query_map([start], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })?
+        .collect::<Result<HashMap<_, _>, _>>()?;
+
+    Ok(labels
+        .into_iter()
+        .map(|label| {
+            let count = counts.get(&label).copied().unwrap_or(0);
+            (label, u64::try_from(count).unwrap_or(0))
+        })
+        .collect())
+}
+
+/// Runs a query returning a label and a count in each row.
+fn fetch_tallies(conn: &Connection, sql: &str, limit: u32) -> Result<Vec<Tally>> {
+    let mut stmt = conn.prepare_cached(sql)?;
+    let results = stmt
+        .query_map([limit], |row| {
+            let count: i64 = row.get(1)?;
+            Ok((row.get(0)?, u64::try_from(count).unwrap_or(0)))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+pub(crate) fn fetch_podcasts(conn: &Connection) -> Result<Vec<Podcast>> {
+    let sql = "
+        SELECT title, feed_url
+        FROM podcasts
+        ORDER BY title COLLATE NOCASE
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    let results = stmt
+        .query_map([], Podcast::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Fetches the episodes of every subscribed podcast, newest first.
+pub(crate) fn fetch_episodes(conn: &Connection) -> Result<Vec<Episode>> {
+    let sql = "
+        SELECT
+            ep.id, ep.podcast_id, pc.title, pc.feed_url,
+            ep.guid, ep.title, ep.url, ep.published_at, ep.filename, ep.durable_id, ep.played
+        FROM episodes ep
+        JOIN podcasts pc ON ep.podcast_id = pc.id
+        ORDER BY COALESCE(ep.published_at, 0) DESC, ep.id DESC
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    let results = stmt
+        .query_map([], Episode::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+pub(crate) fn fetch_episode(conn: &Connection, episode_id: i32) -> Result<Episode> {
+    let sql = "
+        SELECT
+            ep.id, ep.podcast_id, pc.title, pc.feed_url,
+            ep.guid, ep.title, ep.url, ep.published_at, ep.filename, ep.durable_id, ep.played
+        FROM episodes ep
+        JOIN podcasts pc ON ep.podcast_id = pc.id
+        WHERE ep.id = ?
+    ";
+
+    let mut stmt = conn.prepare_cached(sql)?;
+    let result = stmt.query_one([episode_id], Episode::from_row)?;
+
+    Ok(result)
+}
+
+/// Saves a podcast feed, subscribing to it if necessary.
+///
+/// Episodes already known are left as they are, so that their download and
+/// played state is kept.
+///
+/// # Arguments
+///
+/// * `conn` - A mutable reference to the SQLite connection.
+/// * `feed_url` - The URL the feed was fetched from.
+/// * `feed` - The parsed feed.
+pub(crate) fn save_podcast(conn: &mut Connection, feed_url: &str, feed: &Feed) -> Result<()> {
+    let tx = conn.transaction()?;
+
+    let title = if feed.title.is_empty() {
+        feed_url
+    } else {
+        &feed.title
+    };
+
+    tx.execute(
+        "INSERT INTO podcasts (title, feed_url, created_at)
+         VALUES (?1, ?2, strftime('%s', 'now'))
+         ON CONFLICT(feed_url) DO UPDATE SET title = excluded.title",
+        params![title, feed_url],
+    )?;
+
+    let podcast_id: i64 = tx.query_row(
+        "SELECT id FROM podcasts WHERE feed_url = ?",
+        params![feed_url],
+        |row| row.get(0),
+    )?;
+
+    {
+        let mut stmt = tx.prepare_cached(
+            "INSERT OR IGNORE INTO episodes (podcast_id, guid, title, url, published_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+        )?;
+
+        for item in &feed.items {
+            stmt.execute(params![
+                podcast_id,
+                item.guid,
+                item.title,
+                item.url,
+                item.published_at
+            ])?;
+        }
+    }
+
+    tx.commit()?;
+
+    Ok(())
+}
+
+/// Unsubscribes from a podcast, removing any of its downloaded episodes from
+/// the catalog.
+///
+/// Downloaded files are left in place.
+pub(crate) fn delete_podcast(conn: &mut Connection, podcast_id: i32) -> Result<()> {
+    let tx = conn.transaction()?;
+
+    tx.execute(
+        "DELETE FROM tracks WHERE durable_id IN (
+            SELECT durable_id FROM episodes WHERE podcast_id = ? AND durable_id IS NOT NULL
+        )",
+        params![podcast_id],
+    )?;
+    tx.execute("DELETE FROM podcasts WHERE id = ?", params![podcast_id])?;
+
+    tx.commit()?;
+
+    Ok(())
+}
+
+pub(crate) fn set_episode_downloaded(
+    conn: &Connection,
+    episode_id: i32,
+    filename: &str,
+    durable_id: i64,
+) -> Result<()> {
+    let mut stmt =
+        conn.prepare_cached("UPDATE episodes SET filename = ?1, durable_id = ?2 WHERE id = ?3")?;
+    stmt.execute(params![filename, durable_id, episode_id])?;
+
+    Ok(())
+}
+
+pub(crate) fn set_episode_played(conn: &Connection, episode_id: i32, played: bool) -> Result<()> {
+    let mut stmt = conn.prepare_cached("UPDATE episodes SET played = ?1 WHERE id = ?2")?;
+    stmt.execute(params![played, episode_id])?;
+
+    Ok(())
+}
+
+/// Marks the episode for a catalog track as played.
+///
+/// Returns `true` if the track was a podcast episode.
+pub(crate) fn set_track_episode_played(conn: &Connection, durable_id: i64) -> Result<bool> {
+    let mut stmt = conn.prepare_cached("UPDATE episodes SET played = 1 WHERE durable_id = ?")?;
+    let updated = stmt.execute(params![durable_id])?;
+
+    Ok(updated > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        create_schema(&conn).unwrap();
+        conn
+    }
+
+    /// Adds a track to the catalog, returning its durable id.
+    fn add_track(conn: &Connection, title: &str, duration: i64) -> i64 {
+        conn.execute("INSERT OR IGNORE INTO artists (name) VALUES ('Artist')", [])
+            .unwrap();
+        conn.execute(
+            "INSERT OR IGNORE INTO albums (artist_id, title) VALUES (1, 'Album')",
+            [],
+        )
+        .unwrap();
+
+        let durable_id = conn.query_row("SELECT COUNT(*) + 1 FROM tracks", [], |row| row.get(0)).unwrap();
+        conn.execute(
+            "INSERT INTO tracks (durable_id, album_id, track_number, title, duration, filename, created_at)
+            VALUES (?1, 1, ?1, ?2, ?3, ?2, 0)",
+            params![durable_id, title, duration],
+        )
+        .unwrap();
+
+        durable_id
+    }
+
+    fn set_last_played(conn: &Connection, durable_id: i64, secs_ago: i64) {
+        conn.execute(
+            "INSERT INTO track_stats (durable_id, play_count, last_played)
+            VALUES (?1, 1, strftime('%s', 'now') - ?2)",
+            params![durable_id, secs_ago],
+        )
+        .unwrap();
+    }
+
+    fn titles(tracks: &[TrackInfo]) -> Vec<&str> {
+        let mut titles: Vec<&str> = tracks.iter().map(|track| track.track_title.as_str()).collect();
+        titles.sort();
+        titles
+    }
+
+    #[test]
+    fn auto_dj_candidates_leave_out_recently_played_tracks() {
+        let conn = open_db();
+        add_track(&conn, "Never Played", 100);
+        let recent = add_track(&conn, "Played Recently", 100);
+        let old = add_track(&conn, "Played Long Ago", 100);
+        set_last_played(&conn, recent, 60);
+        set_last_played(&conn, old, 7200);
+
+        let candidates = fetch_auto_dj_candidates(&conn, 10, 3600).unwrap();
+
+        assert_eq!(titles(&candidates), ["Never Played", "Played Long Ago"]);
+    }
+
+    #[test]
+    fn auto_dj_candidates_include_recently_played_tracks_without_gap() {
+        let conn = open_db();
+        let recent = add_track(&conn, "Played Recently", 100);
+        set_last_played(&conn, recent, 60);
+
+        let candidates = fetch_auto_dj_candidates(&conn, 10, 0).unwrap();
+
+        assert_eq!(titles(&candidates), ["Played Recently"]);
+    }
+
+    #[test]
+    fn library_summary_ignores_unknown_durations() {
+        let conn = open_db();
+        let played = add_track(&conn, "Played", 100);
+        let unknown = add_track(&conn, "Unknown", -1);
+        set_last_played(&conn, played, 0);
+        set_last_played(&conn, unknown, 0);
+
+        let summary = fetch_library_summary(&conn).unwrap();
+
+        assert_eq!(summary.tracks, 2);
+        assert_eq!(summary.duration, 100);
+        assert_eq!(summary.plays, 2);
+        assert_eq!(summary.listening_time, 100);
+    }
+}
@@ -24,7 +24,9 @@ use rusqlite::{
     types::{FromSql, FromSqlResult, ToSqlOutput, ValueRef},
 };
 
-use crate::model::{Rating, TrackInfo};
+use crate::model::{
+    Bookmark, Episode, PendingListen, PlayRecord, Podcast, Rating, StarRating, Station, TrackInfo,
+};
 
 impl TrackInfo {
     /// Maps an SQLite row to a [`TrackInfo`] instance.
@@ -51,6 +53,135 @@ impl TrackInfo {
             play_count: row.get(10)?,
             rating: row.get(11)?,
             created_at: row.get(12)?,
+            is_podcast: row.get(13)?,
+            stars: row.get(14)?,
+            cover_hash: row.get(15)?,
+            disc_number: row.get(16)?,
+            album_artist: row.get(17)?,
+            composer: row.get(18)?,
+        })
+    }
+}
+
+impl Bookmark {
+    /// Maps an SQLite row to a [`Bookmark`] instance.
+    ///
+    /// The row must contain the [`TrackInfo`] columns first, followed by the
+    /// bookmark id, name and position.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`rusqlite::Error`] if:
+    /// * The row does not contain enough columns.
+    /// * The data in a column cannot be converted to the required Rust type.
+    pub(crate) fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let position: i64 = row.get(21)?;
+        Ok(Self {
+            id: row.get(19)?,
+            name: row.get(20)?,
+            position: u64::try_from(position).unwrap_or(0),
+            track: TrackInfo::from_row(row)?,
+        })
+    }
+}
+
+impl PlayRecord {
+    /// Maps an SQLite row to a [`PlayRecord`] instance.
+    ///
+    /// The row must contain the [`TrackInfo`] columns first, followed by the
+    /// start time, seconds played and skipped flag.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`rusqlite::Error`] if:
+    /// * The row does not contain enough columns.
+    /// * The data in a column cannot be converted to the required Rust type.
+    pub(crate) fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let played_secs: i64 = row.get(20)?;
+        Ok(Self {
+            started_at: row.get(19)?,
+            played_secs: u64::try_from(played_secs).unwrap_or(0),
+            skipped: row.get(21)?,
+            track: TrackInfo::from_row(row)?,
+        })
+    }
+}
+
+impl PendingListen {
+    /// Maps an SQLite row to a [`PendingListen`] instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`rusqlite::Error`] if:
+    /// * The row does not contain enough columns.
+    /// * The data in a column cannot be converted to the required Rust type.
+    pub(crate) fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            listened_at: row.get(1)?,
+            artist_name: row.get(2)?,
+            track_title: row.get(3)?,
+            album_title: row.get(4)?,
+            track_number: row.get(5)?,
+            duration: row.get(6)?,
+        })
+    }
+}
+
+impl Station {
+    /// Maps an SQLite row to a [`Station`] instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`rusqlite::Error`] if:
+    /// * The row does not contain enough columns.
+    /// * The data in a column cannot be converted to the required Rust type.
+    pub(crate) fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            url: row.get(2)?,
+        })
+    }
+}
+
+impl Podcast {
+    /// Maps an SQLite row to a [`Podcast`] instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`rusqlite::Error`] if:
+    /// * The row does not contain enough columns.
+    /// * The data in a column cannot be converted to the required Rust type.
+    pub(crate) fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            title: row.get(0)?,
+            feed_url: row.get(1)?,
+        })
+    }
+}
+
+impl Episode {
+    /// Maps an SQLite row to an [`Episode`] instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`rusqlite::Error`] if:
+    /// * The row does not contain enough columns.
+    /// * The data in a column cannot be converted to the required Rust type.
+    pub(crate) fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            podcast_id: row.get(1)?,
+            podcast_title: row.get(2)?,
+            feed_url: row.get(3)?,
+            guid: row.get(4)?,
+            title: row.get(5)?,
+            url: row.get(6)?,
+            published_at: row.get(7)?,
+            filename: row.get(8)?,
+            durable_id: row.get(9)?,
+            played: row.get(10)?,
         })
     }
 }
@@ -66,6 +197,22 @@ impl ToSql for Rating {
     }
 }
 
+/// Star ratings are stored as a number of half stars, or `NULL` if unrated.
+impl ToSql for StarRating {
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.half_stars()))
+    }
+}
+
+impl FromSql for StarRating {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        u8::try_from(value.as_i64()?)
+            .ok()
+            .and_then(StarRating::from_half_stars)
+            .ok_or(rusqlite::types::FromSqlError::InvalidType)
+    }
+}
+
 impl FromSql for Rating {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
         match value.as_i64()? {
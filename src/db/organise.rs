@@ -0,0 +1,493 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Renaming and moving catalogued files to paths given by a template.
+//!
+//! Files are named from their tags, under the media directory they are already
+//! in. Every move is planned before any file is touched, so that the plan can
+//! be previewed, and moves that would overwrite a file or that collide with
+//! another move are skipped.
+//!
+//! A lyrics sidecar file next to a track is moved along with it.
+
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::mpsc::Sender,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, anyhow};
+use rusqlite::Connection;
+
+use crate::{
+    db,
+    events::{AppEvent, OrganiseEvent},
+    model::{
+        organise::{FileMove, MoveStatus},
+        tags::{TagField, TrackTags},
+    },
+    tags,
+    util::path_template::PathTemplate,
+};
+
+/// The fields that can be used in a template.
+const TEMPLATE_FIELDS: &[&str] = &[
+    "albumartist",
+    "artist",
+    "album",
+    "title",
+    "track",
+    "disc",
+    "year",
+    "genre",
+    "ext",
+];
+
+/// Plans the moves needed to put every catalogued file in a media directory at
+/// the path given by a template.
+///
+/// Podcast episodes, files outside of the media directories and files that are
+/// already in the right place are left out.
+///
+/// # Errors
+///
+/// Returns an error if the template is not valid, or the catalog can not be
+/// read.
+pub(crate) fn plan_moves(
+    conn: &Connection,
+    template: &str,
+    media_dirs: &[String],
+) -> Result<Vec<FileMove>> {
+    let template = PathTemplate::parse(template, TEMPLATE_FIELDS)?;
+
+    let mut moves = vec![];
+
+    for (track_id, filename) in db::fetch_track_files(conn)? {
+        let from = PathBuf::from(filename);
+
+        let Some(root) = media_dirs
+            .iter()
+            .map(Path::new)
+            .find(|dir| from.starts_with(dir))
+        else {
+            continue;
+        };
+
+        let (to, status) = match tags::read_track_tags(&from) {
+            Ok(tags) => {
+                let path = template.render(|name| field_value(name, &tags, &from));
+                if path.as_os_str().is_empty() {
+                    let reason = "Template gives an empty path".to_string();
+                    (from.clone(), MoveStatus::Skipped(reason))
+                } else {
+                    (root.join(path), MoveStatus::Pending)
+                }
+            }
+            Err(e) => (from.clone(), MoveStatus::Skipped(format!("{:#}", e))),
+        };
+
+        if to == from && status == MoveStatus::Pending {
+            continue;
+        }
+
+        moves.push(FileMove {
+            track_id,
+            root: root.to_path_buf(),
+            from,
+            to,
+            status,
+        });
+    }
+
+    find_conflicts(&mut moves);
+
+    Ok(moves)
+}
+
+/// Makes the planned moves, updating the catalog to match.
+///
+/// Progress is sent as [`OrganiseEvent`]s. The catalog is updated in a single
+/// transaction, and if that fails the files are moved back.
+///
+/// # Returns
+///
+/// Returns the ids of the tracks that were moved.
+///
+/// # Errors
+///
+/// Returns an error if the catalog could not be updated.
+pub(crate) fn apply_moves(
+    conn: &mut Connection,
+    moves: &[FileMove],
+    event_tx: &Sender<AppEvent>,
+) -> Result<Vec<i32>> {
+    event_tx.send(AppEvent::Organise(OrganiseEvent::Started))?;
+
+    let mut last_update = Instant::now();
+    let update_interval = Duration::from_millis(100);
+
+    let mut moved = vec![];
+
+    for (index, file_move) in moves.iter().enumerate() {
+        if file_move.status != MoveStatus::Pending {
+            continue;
+        }
+
+        match move_file(&file_move.from, &file_move.to) {
+            Ok(()) => moved.push(index),
+            Err(e) => {
+                let error = format!("{:#}", e);
+                event_tx.send(AppEvent::Organise(OrganiseEvent::Failed(index, error)))?;
+            }
+        }
+
+        if last_update.elapsed() >= update_interval {
+            let _ = event_tx.send(AppEvent::Organise(OrganiseEvent::Progress(index + 1)));
+            last_update = Instant::now();
+        }
+    }
+
+    if let Err(e) = update_filenames(conn, moves, &moved) {
+        for &index in &moved {
+            let file_move = &moves[index];
+            if move_file(&file_move.to, &file_move.from).is_ok() {
+                remove_empty_dirs(&file_move.to, &file_move.root);
+            }
+
+            let error = "Moved back, the catalog could not be updated".to_string();
+            event_tx.send(AppEvent::Organise(OrganiseEvent::Failed(index, error)))?;
+        }
+        event_tx.send(AppEvent::Organise(OrganiseEvent::Finished(0)))?;
+
+        return Err(e);
+    }
+
+    for &index in &moved {
+        remove_empty_dirs(&moves[index].from, &moves[index].root);
+    }
+
+    event_tx.send(AppEvent::Organise(OrganiseEvent::Progress(moves.len())))?;
+    event_tx.send(AppEvent::Organise(OrganiseEvent::Finished(moved.len())))?;
+
+    Ok(moved.iter().map(|&index| moves[index].track_id).collect())
+}
+
+fn update_filenames(conn: &mut Connection, moves: &[FileMove], moved: &[usize]) -> Result<()> {
+    let tx = conn.transaction()?;
+
+    for &index in moved {
+        let file_move = &moves[index];
+        let filename = file_move
+            .to
+            .to_str()
+            .context("Path contains invalid UTF-8")?;
+        db::update_track_filename(&tx, file_move.track_id, filename)?;
+    }
+
+    tx.commit().context("Failed to commit transaction")
+}
+
+/// The value of a template field for a file, with a fallback for the fields
+/// that are needed to tell files apart.
+fn field_value(name: &str, tags: &TrackTags, path: &Path) -> String {
+    let tag = |field| Some(tags.get(field).trim()).filter(|value| !value.is_empty());
+
+    match name {
+        "albumartist" => tag(TagField::AlbumArtist)
+            .or(tag(TagField::Artist))
+            .unwrap_or("Unknown Artist")
+            .to_string(),
+        "artist" => tag(TagField::Artist)
+            .unwrap_or("Unknown Artist")
+            .to_string(),
+        "album" => tag(TagField::Album).unwrap_or("Unknown Album").to_string(),
+        "title" => tag(TagField::Title).map_or_else(
+            || {
+                path.file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            },
+            str::to_string,
+        ),
+        "track" => tag(TagField::TrackNumber).unwrap_or_default().to_string(),
+        "disc" => tag(TagField::DiscNumber).unwrap_or_default().to_string(),
+        "year" => tag(TagField::Year).unwrap_or_default().to_string(),
+        "genre" => tag(TagField::Genre).unwrap_or_default().to_string(),
+        "ext" => path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+/// Skips moves that would overwrite an existing file, or that have the same
+/// destination as another move.
+///
+/// Destinations are compared ignoring case, as they may be on a case
+/// insensitive file system. A file that is in the way is not moved out of it
+/// first, even if it is one of the files being moved.
+fn find_conflicts(moves: &mut [FileMove]) {
+    let destination_key = |file_move: &FileMove| file_move.to.to_string_lossy().to_lowercase();
+
+    let mut destinations: HashMap<String, usize> = HashMap::new();
+    for file_move in moves.iter() {
+        if file_move.status == MoveStatus::Pending {
+            *destinations.entry(destination_key(file_move)).or_default() += 1;
+        }
+    }
+
+    for file_move in moves.iter_mut() {
+        if file_move.status != MoveStatus::Pending {
+            continue;
+        }
+
+        if destinations[&destination_key(file_move)] > 1 {
+            file_move.status = MoveStatus::Skipped("Same destination as another file".into());
+        } else if is_occupied(&file_move.from, &file_move.to) {
+            file_move.status = MoveStatus::Skipped("Destination already exists".into());
+        } else if has_sidecar(&file_move.from)
+            && is_occupied(&sidecar_path(&file_move.from), &sidecar_path(&file_move.to))
+        {
+            file_move.status = MoveStatus::Skipped("Lyrics destination already exists".into());
+        }
+    }
+}
+
+/// Returns `true` if there is already a file at the destination, other than
+/// the file itself when only the case of its name is changing.
+fn is_occupied(from: &Path, to: &Path) -> bool {
+    to.exists() && fs::canonicalize(from).ok() != fs::canonicalize(to).ok()
+}
+
+/// The lyrics sidecar file for a track.
+fn sidecar_path(path: &Path) -> PathBuf {
+    path.with_extension("lrc")
+}
+
+fn has_sidecar(path: &Path) -> bool {
+    sidecar_path(path).is_file()
+}
+
+/// Moves a track, and its lyrics sidecar file if it has one.
+///
+/// If the sidecar can not be moved the track is moved back, so that the two
+/// are never separated.
+fn move_file(from: &Path, to: &Path) -> Result<()> {
+    let sidecar = has_sidecar(from).then(|| (sidecar_path(from), sidecar_path(to)));
+
+    // Checked before the track is moved, so that it rarely needs moving back
+    if let Some((sidecar_from, sidecar_to)) = &sidecar
+        && is_occupied(sidecar_from, sidecar_to)
+    {
+        return Err(anyhow!("Destination already exists: {}", sidecar_to.display()));
+    }
+
+    move_single_file(from, to)?;
+
+    if let Some((sidecar_from, sidecar_to)) = &sidecar
+        && let Err(e) = move_single_file(sidecar_from, sidecar_to)
+    {
+        let _ = move_single_file(to, from);
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// Moves a file, creating the directories it is moved to, and copying it if it
+/// can not be renamed across file systems.
+fn move_single_file(from: &Path, to: &Path) -> Result<()> {
+    // Checked again, since files may have changed since the moves were planned
+    if is_occupied(from, to) {
+        return Err(anyhow!("Destination already exists: {}", to.display()));
+    }
+
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    match fs::rename(from, to) {
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+            fs::copy(from, to)?;
+            fs::remove_file(from)?;
+        }
+        result => result.with_context(|| format!("Failed to move file: {}", from.display()))?,
+    }
+
+    Ok(())
+}
+
+/// Removes the directories a file was moved out of, for as long as they are
+/// left empty, stopping at the media directory it was in.
+fn remove_empty_dirs(path: &Path, root: &Path) {
+    for dir in path.ancestors().skip(1) {
+        if !dir.starts_with(root) || dir == root || fs::remove_dir(dir).is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates an empty directory unique to the test.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("chooui-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn touch(path: &Path) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, path.to_string_lossy().as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn moves_lyrics_with_track() {
+        let dir = test_dir("organise-lyrics");
+        let from = dir.join("old/track.mp3");
+        let to = dir.join("new/Artist/01 Title.mp3");
+        touch(&from);
+        touch(&from.with_extension("lrc"));
+
+        move_file(&from, &to).unwrap();
+
+        assert!(!from.exists());
+        assert!(!from.with_extension("lrc").exists());
+        assert!(to.is_file());
+        assert!(to.with_extension("lrc").is_file());
+    }
+
+    #[test]
+    fn moves_track_without_lyrics() {
+        let dir = test_dir("organise-no-lyrics");
+        let from = dir.join("track.mp3");
+        let to = dir.join("Artist/01 Title.mp3");
+        touch(&from);
+
+        move_file(&from, &to).unwrap();
+
+        assert!(to.is_file());
+        assert!(!to.with_extension("lrc").exists());
+    }
+
+    #[test]
+    fn does_not_move_track_when_lyrics_destination_exists() {
+        let dir = test_dir("organise-lyrics-occupied");
+        let from = dir.join("track.mp3");
+        let to = dir.join("Artist/01 Title.mp3");
+        touch(&from);
+        touch(&from.with_extension("lrc"));
+        touch(&to.with_extension("lrc"));
+
+        assert!(move_file(&from, &to).is_err());
+
+        assert!(from.is_file());
+        assert!(from.with_extension("lrc").is_file());
+        assert!(!to.exists());
+    }
+
+    #[test]
+    fn skips_moves_when_lyrics_destination_exists() {
+        let dir = test_dir("organise-conflicts");
+        let from = dir.join("track.mp3");
+        let to = dir.join("Artist/01 Title.mp3");
+        touch(&from);
+        touch(&from.with_extension("lrc"));
+        touch(&to.with_extension("lrc"));
+
+        let mut moves = vec![FileMove {
+            track_id: 1,
+            root: dir.clone(),
+            from,
+            to,
+            status: MoveStatus::Pending,
+        }];
+        find_conflicts(&mut moves);
+
+        assert!(matches!(moves[0].status, MoveStatus::Skipped(_)));
+    }
+
+    #[test]
+    fn removes_empty_dirs_up_to_media_dir() {
+        let dir = test_dir("organise-empty-dirs");
+        let root = dir.join("music");
+        let from = root.join("Artist/Album/track.mp3");
+        fs::create_dir_all(from.parent().unwrap()).unwrap();
+
+        remove_empty_dirs(&from, &root);
+
+        assert!(!root.join("Artist").exists());
+        assert!(root.is_dir());
+    }
+
+    #[test]
+    fn keeps_dirs_that_are_not_empty() {
+        let dir = test_dir("organise-full-dirs");
+        let root = dir.join("music");
+        let from = root.join("Artist/Album/track.mp3");
+        fs::create_dir_all(from.parent().unwrap()).unwrap();
+        touch(&root.join("Artist/other.mp3"));
+
+        remove_empty_dirs(&from, &root);
+
+        assert!(!root.join("Artist/Album").exists());
+        assert!(root.join("Artist").is_dir());
+    }
+
+    #[test]
+    fn never_removes_media_dir_or_its_parents() {
+        let dir = test_dir("organise-media-dir");
+        let root = dir.join("library/music");
+        let from = root.join("track.mp3");
+        fs::create_dir_all(&root).unwrap();
+
+        // The last file has been moved out of the media directory
+        remove_empty_dirs(&from, &root);
+
+        assert!(root.is_dir());
+    }
+
+    #[test]
+    fn moves_back_and_removes_new_dirs_when_catalog_update_fails() {
+        let dir = test_dir("organise-rollback");
+        let from = dir.join("track.mp3");
+        let to = dir.join("Artist/Album/01 Title.mp3");
+        touch(&from);
+
+        let moves = vec![FileMove {
+            track_id: 1,
+            root: dir.clone(),
+            from: from.clone(),
+            to,
+            status: MoveStatus::Pending,
+        }];
+
+        // Without a catalog, updating the filenames fails
+        let mut conn = Connection::open_in_memory().unwrap();
+        let (event_tx, _event_rx) = std::sync::mpsc::channel();
+
+        assert!(apply_moves(&mut conn, &moves, &event_tx).is_err());
+
+        assert!(from.is_file());
+        assert!(!dir.join("Artist").exists());
+    }
+}
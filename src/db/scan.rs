@@ -37,21 +37,94 @@
 
 use anyhow::{Context, Result, anyhow};
 use lofty::config::ParsingMode;
+use lofty::file::{FileType, TaggedFile};
+use lofty::mpeg::MpegFile;
 use lofty::probe::Probe;
-use lofty::tag::ItemKey;
+use lofty::tag::{ItemKey, Tag};
 use lofty::{config::ParseOptions, prelude::*};
 use rusqlite::{Connection, Transaction, params};
 use std::{
     collections::HashMap,
     fs::OpenOptions,
     io::Write,
-    path::Path,
+    path::{Path, PathBuf},
     sync::mpsc::Sender,
     time::{Duration, Instant},
 };
 use walkdir::WalkDir;
 
-use crate::events::{AppEvent, CatalogEvent};
+use crate::{
+    covers, db,
+    events::{AppEvent, CatalogEvent},
+    model::{Episode, StarRating, TrackInfo},
+    tags,
+    util::date::date_from_timestamp,
+};
+
+/// Genre recorded in the catalog for downloaded podcast episodes.
+const PODCAST_GENRE: &str = "Podcast";
+
+/// Artist that compilations are catalogued under.
+const VARIOUS_ARTISTS: &str = "Various Artists";
+
+/// Separators between the artists credited in an artist tag, matched ignoring
+/// case.
+///
+/// Slashes only separate artists when spaced, as they are part of names such
+/// as "AC/DC", and multiple ID3v2.4 artists are joined with a spaced slash.
+const ARTIST_SEPARATORS: [&str; 10] = [
+    " (feat. ",
+    " [feat. ",
+    " (ft. ",
+    " [ft. ",
+    " (featuring ",
+    " feat. ",
+    " ft. ",
+    " featuring ",
+    ";",
+    " / ",
+];
+
+/// Separators between the genres in a genre tag.
+const GENRE_SEPARATORS: [char; 3] = [';', '/', ','];
+
+/// Track metadata to be recorded in the catalog.
+struct TrackMetadata {
+    /// The artist the track is catalogued under, its album artist if it has
+    /// one or else its main artist.
+    artist_name: String,
+    /// The name to sort the artist by, such as "Beatles, The".
+    artist_sort: Option<String>,
+    album_artist: Option<String>,
+    /// The artist as tagged, which may credit several artists.
+    track_artist: Option<String>,
+    /// Every artist credited on the track.
+    track_artists: Vec<String>,
+    /// Set for tracks flagged as part of a compilation that have no album
+    /// artist.
+    compilation: bool,
+    composer: Option<String>,
+    album_title: String,
+    track_title: String,
+    disc_number: Option<u32>,
+    track_number: Option<u32>,
+    year: Option<i64>,
+    duration: i64,
+    genre: String,
+    /// Every genre of the track.
+    genres: Vec<String>,
+    durable_id: i64,
+    is_podcast: bool,
+    stars: Option<StarRating>,
+    cover: Option<Vec<u8>>,
+    lyrics: Option<String>,
+}
+
+type ArtistCache = HashMap<String, i64>;
+type AlbumCache = HashMap<(i64, String), i64>;
+
+/// Albums whose cover could not be stored, with the reason for the scan log.
+type FailedCovers = HashMap<i64, String>;
 
 /// Recursively scans a directory for MP3 files and synchronizes the database.
 ///
@@ -80,17 +153,21 @@ pub(crate) fn process_music_library(
 ) -> Result<i64> {
     event_tx.send(AppEvent::Catalog(CatalogEvent::Started))?;
 
-    let mut artist_cache: HashMap<String, i64> = HashMap::new();
-    let mut album_cache: HashMap<(i64, String), i64> = HashMap::new();
+    let mut artist_cache = ArtistCache::new();
+    let mut album_cache = AlbumCache::new();
+    let mut failed_covers = FailedCovers::new();
 
     let tx = conn.transaction()?;
 
+    tx.execute("DELETE FROM track_artists", [])?;
+    tx.execute("DELETE FROM track_genres", [])?;
     tx.execute("DELETE FROM tracks", [])?;
     tx.execute("DELETE FROM albums", [])?;
     tx.execute("DELETE FROM artists", [])?;
+    tx.execute("DELETE FROM genres", [])?;
 
     tx.execute(
-        "DELETE FROM sqlite_sequence WHERE name IN ('artists', 'albums', 'tracks')",
+        "DELETE FROM sqlite_sequence WHERE name IN ('artists', 'albums', 'tracks', 'genres')",
         [],
     )?;
 
@@ -105,6 +182,22 @@ pub(crate) fn process_music_library(
         .truncate(true)
         .open("scan_log.txt")?;
 
+    // Downloaded podcast episodes are indexed first, so they are recorded as
+    // episodes even if the podcast directory is also a media directory
+    for episode in db::fetch_episodes(&tx)? {
+        let Some(filename) = &episode.filename else {
+            continue;
+        };
+
+        let path = Path::new(filename);
+        if let Err(e) = index_episode(&tx, &episode, path, &mut artist_cache, &mut album_cache) {
+            let log_entry = format!("{} | {:#}\n", path.display(), e);
+            if let Err(write_err) = error_log.write_all(log_entry.as_bytes()) {
+                eprintln!("Critical: Could not write to error log file: {}", write_err);
+            }
+        }
+    }
+
     for root in paths {
         let mut path_count = 0;
 
@@ -123,7 +216,13 @@ pub(crate) fn process_music_library(
         {
             let path = entry.path();
 
-            let track_result = process_track(&tx, path, &mut artist_cache, &mut album_cache);
+            let track_result = process_track(
+                &tx,
+                path,
+                &mut artist_cache,
+                &mut album_cache,
+                &mut failed_covers,
+            );
             if let Err(e) = track_result {
                 // let log_entry = format!("{} | {:#}\n", path.display(), e);
                 // let log_entry = format!("{} | {:?}\n", path.display(), e);
@@ -154,6 +253,14 @@ pub(crate) fn process_music_library(
         )))?;
     }
 
+    for log_entry in failed_covers.values() {
+        if let Err(write_err) = error_log.write_all(log_entry.as_bytes()) {
+            eprintln!("Critical: Could not write to error log file: {}", write_err);
+        }
+    }
+
+    group_compilations(&tx, &mut artist_cache, &mut album_cache)?;
+
     tx.commit().context("Failed to commit transaction")?;
 
     let count: i64 = conn.query_row("SELECT COUNT(*) FROM tracks", [], |row| row.get(0))?;
@@ -163,30 +270,238 @@ pub(crate) fn process_music_library(
     Ok(count)
 }
 
+/// Adds a single downloaded podcast episode to the catalog.
+///
+/// # Returns
+///
+/// Returns the durable id of the episode's catalog track.
+pub(crate) fn add_episode_to_catalog(
+    conn: &mut Connection,
+    episode: &Episode,
+    path: &Path,
+) -> Result<i64> {
+    let tx = conn.transaction()?;
+    let durable_id = index_episode(
+        &tx,
+        episode,
+        path,
+        &mut ArtistCache::new(),
+        &mut AlbumCache::new(),
+    )?;
+    tx.commit()?;
+
+    Ok(durable_id)
+}
+
+/// Records a podcast episode in the catalog, using the podcast title as both
+/// artist and album.
+///
+/// Episode tags are often missing or inconsistent, so the feed is used for
+/// metadata instead and only the duration is read from the file.
+fn index_episode(
+    tx: &Transaction,
+    episode: &Episode,
+    path: &Path,
+    artist_cache: &mut ArtistCache,
+    album_cache: &mut AlbumCache,
+) -> Result<i64> {
+    let options = ParseOptions::new().parsing_mode(ParsingMode::Relaxed);
+    let tagged_file = Probe::open(path)?.options(options).read()?;
+    let duration = i64::try_from(tagged_file.properties().duration().as_secs()).unwrap_or(-1);
+
+    // Episodes are identified by their feed, which is stable even if the
+    // episode is later retitled
+    let durable_id = xxhash_rust::xxh3::xxh3_64(
+        format!("{}|{}", episode.feed_url, episode.guid).as_bytes(),
+    ) as i64;
+
+    let metadata = TrackMetadata {
+        artist_name: episode.podcast_title.clone(),
+        artist_sort: None,
+        album_artist: None,
+        track_artist: None,
+        track_artists: Vec::new(),
+        compilation: false,
+        composer: None,
+        album_title: episode.podcast_title.clone(),
+        track_title: episode.title.clone(),
+        disc_number: None,
+        track_number: Some(0),
+        year: episode
+            .published_at
+            .map(|published_at| date_from_timestamp(published_at).0),
+        duration,
+        genre: PODCAST_GENRE.to_string(),
+        genres: Vec::new(),
+        durable_id,
+        is_podcast: true,
+        stars: None,
+        cover: None,
+        lyrics: None,
+    };
+
+    insert_track(tx, path, &metadata, artist_cache, album_cache)?;
+
+    Ok(durable_id)
+}
+
 fn process_track(
     tx: &Transaction,
     path: &Path,
-    artist_cache: &mut HashMap<String, i64>,
-    album_cache: &mut HashMap<(i64, String), i64>,
+    artist_cache: &mut ArtistCache,
+    album_cache: &mut AlbumCache,
+    failed_covers: &mut FailedCovers,
 ) -> Result<()> {
+    let metadata = read_track_metadata(path)?;
+
+    let album_id = insert_track(tx, path, &metadata, artist_cache, album_cache)?;
+    store_album_cover(tx, album_id, path, metadata.cover.as_deref(), failed_covers)
+}
+
+/// Re-reads the tags of a catalogued track, updating its catalog record in
+/// place.
+///
+/// The track keeps its id, and if the new tags give it a different durable id
+/// then its statistics, resume position, bookmarks and play history are moved
+/// over to it.
+///
+/// # Errors
+///
+/// Returns an error if the file can not be read, or if the new tags give the
+/// track the same durable id as another catalogued track.
+pub(crate) fn reindex_track(conn: &mut Connection, track: &TrackInfo) -> Result<()> {
+    let path = Path::new(&track.filename);
+    let mut metadata = read_track_metadata(path)?;
+
+    let tx = conn.transaction()?;
+
+    let old_durable_id: i64 = tx.query_row(
+        "SELECT durable_id FROM tracks WHERE id = ?",
+        params![track.track_id],
+        |r| r.get(0),
+    )?;
+
+    // Without the flag, only the rest of the album shows the track to be part
+    // of a compilation
+    if metadata.album_artist.is_none() && !metadata.compilation {
+        metadata.compilation = in_compilation(&tx, path, &metadata.album_title, track.track_id)?;
+    }
+
+    let mut artist_cache = ArtistCache::new();
+    let album_id = find_album(&tx, &metadata, &mut artist_cache, &mut AlbumCache::new())?;
+
+    tx.execute(
+        "UPDATE tracks SET album_id = ?, durable_id = ?, disc_number = ?, track_number = ?, title = ?, genre = ?, year = ?, lyrics = ?, artist = ?, album_artist = ?, composer = ? WHERE id = ?",
+        params![
+            album_id,
+            metadata.durable_id,
+            metadata.disc_number,
+            metadata.track_number,
+            metadata.track_title,
+            metadata.genre,
+            metadata.year,
+            metadata.lyrics,
+            metadata.track_artist,
+            metadata.album_artist,
+            metadata.composer,
+            track.track_id
+        ],
+    )
+    .with_context(|| format!("Failed to update track: {}", path.display()))?;
+
+    tx.execute(
+        "DELETE FROM track_artists WHERE track_id = ?",
+        params![track.track_id],
+    )?;
+    link_track_artists(&tx, i64::from(track.track_id), &metadata, &mut artist_cache)?;
+
+    tx.execute(
+        "DELETE FROM track_genres WHERE track_id = ?",
+        params![track.track_id],
+    )?;
+    link_track_genres(&tx, i64::from(track.track_id), &metadata)?;
+
+    // A cover that can not be read is reported by the next scan, it should not
+    // stop the track being updated
+    store_album_cover(
+        &tx,
+        album_id,
+        path,
+        metadata.cover.as_deref(),
+        &mut FailedCovers::new(),
+    )?;
+
+    if metadata.durable_id != old_durable_id {
+        for table in ["track_stats", "resume_positions", "bookmarks", "play_history"] {
+            tx.execute(
+                &format!("UPDATE OR REPLACE {table} SET durable_id = ?1 WHERE durable_id = ?2"),
+                params![metadata.durable_id, old_durable_id],
+            )?;
+        }
+    }
+
+    remove_orphans(&tx)?;
+
+    tx.commit()?;
+
+    Ok(())
+}
+
+fn read_track_metadata(path: &Path) -> Result<TrackMetadata> {
     let options = ParseOptions::new().parsing_mode(ParsingMode::Relaxed);
 
-    let tagged_file = Probe::open(path)?.options(options).read()?;
+    // Ratings and synchronised lyrics are only kept in format specific tags, so
+    // MP3 files are read as such to get at them
+    let probe = Probe::open(path)?.options(options);
+    let (tagged_file, stars, synced_lyrics) = match probe.file_type() {
+        Some(FileType::Mpeg) => {
+            let mpeg_file = MpegFile::read_from(&mut probe.into_inner(), options)?;
+            let stars = mpeg_file.id3v2().and_then(tags::read_star_rating);
+            let synced_lyrics = mpeg_file.id3v2().and_then(tags::read_synced_lyrics);
+            (TaggedFile::from(mpeg_file), stars, synced_lyrics)
+        }
+        _ => (probe.read()?, None, None),
+    };
 
     let tag = tagged_file
         .primary_tag()
         .or_else(|| tagged_file.first_tag())
         .ok_or_else(|| anyhow!("No tags found in file"))?;
 
-    let artist_name = tag
-        .get(ItemKey::AlbumArtist)
-        .and_then(|item| item.value().text())
-        .map(|s| s.to_string())
-        .unwrap_or_else(|| {
-            tag.artist()
-                .map(|c| c.to_string())
-                .unwrap_or_else(|| "Unknown Artist".into())
-        });
+    let album_artist = tag_text(tag, ItemKey::AlbumArtist);
+    let composer = tag_text(tag, ItemKey::Composer);
+
+    let track_artist = {
+        let artists: Vec<&str> = tag.get_strings(ItemKey::TrackArtist).collect();
+        (!artists.is_empty()).then(|| artists.join(" / "))
+    };
+    let track_artists = track_artist
+        .as_deref()
+        .map(split_artists)
+        .unwrap_or_default();
+
+    // The sort name goes with whichever artist the track is catalogued under,
+    // and is not known for the main artist of a track with several
+    let (artist_name, artist_sort) = match (&album_artist, track_artists.as_slice()) {
+        (Some(album_artist), _) => (
+            album_artist.clone(),
+            tag_text(tag, ItemKey::AlbumArtistSortOrder),
+        ),
+        (None, [artist]) => (artist.clone(), tag_text(tag, ItemKey::TrackArtistSortOrder)),
+        (None, [artist, ..]) => (artist.clone(), None),
+        (None, []) => ("Unknown Artist".to_string(), None),
+    };
+
+    let compilation =
+        album_artist.is_none() && tag.get_string(ItemKey::FlagCompilation) == Some("1");
+
+    // Tracks are identified by the artist they were always catalogued under,
+    // so that they keep their statistics
+    let durable_artist = album_artist.clone().unwrap_or_else(|| {
+        tag.artist()
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| "Unknown Artist".into())
+    });
 
     let album_title = tag
         .album()
@@ -199,18 +514,30 @@ fn process_track(
             .unwrap_or_else(|| "Unknown Track".into())
     });
 
-    let year = tag.date().map(|ts| ts.year);
+    let year = tag.date().map(|ts| i64::from(ts.year));
     let duration = i64::try_from(tagged_file.properties().duration().as_secs()).unwrap_or(-1);
     let genre = tag
         .genre()
         .map(|c| c.to_string())
         .unwrap_or_else(|| "".into());
+    let disc_number = tag.disk();
+    let genres = split_genres(tag.get_strings(ItemKey::Genre));
     let track_number = tag.track();
+    let cover = covers::embedded_cover(tag);
+
+    // Only the words are catalogued, for searching, the lyrics themselves are
+    // read again when they are shown
+    let lyrics = tags::read_lrc_file(path)
+        .ok()
+        .flatten()
+        .or(synced_lyrics)
+        .or_else(|| tags::read_tag_lyrics(tag))
+        .map(|lyrics| lyrics.text());
 
     let durable_id = xxhash_rust::xxh3::xxh3_64(
         format!(
             "{}|{}|{}|{}",
-            artist_name,
+            durable_artist,
             album_title,
             track_number.unwrap_or_default(),
             track_title
@@ -218,62 +545,445 @@ fn process_track(
         .as_bytes(),
     ) as i64;
 
-    let artist_id = if let Some(&id) = artist_cache.get(&artist_name) {
-        id
-    } else {
-        tx.execute(
-            "INSERT OR IGNORE INTO artists (name) VALUES (?)",
-            params![artist_name],
-        )?;
-        let id: i64 = tx.query_row(
-            "SELECT id FROM artists WHERE name = ?",
-            params![artist_name],
-            |r| r.get(0),
-        )?;
-        artist_cache.insert(artist_name, id);
-        id
-    };
+    Ok(TrackMetadata {
+        artist_name,
+        artist_sort,
+        album_artist,
+        track_artist,
+        track_artists,
+        compilation,
+        composer,
+        album_title,
+        track_title,
+        disc_number,
+        track_number,
+        year,
+        duration,
+        genre,
+        genres,
+        durable_id,
+        is_podcast: false,
+        stars,
+        cover,
+        lyrics,
+    })
+}
 
-    let album_key = (artist_id, album_title.clone());
-    let album_id = if let Some(&id) = album_cache.get(&album_key) {
-        id
-    } else {
-        tx.execute(
-            "INSERT OR IGNORE INTO albums (artist_id, title) VALUES (?, ?)",
-            params![artist_id, album_title],
-        )?;
-        let id: i64 = tx.query_row(
-            "SELECT id FROM albums WHERE artist_id = ? AND title = ?",
-            params![artist_id, album_title],
-            |r| r.get(0),
-        )?;
-        album_cache.insert(album_key, id);
-        id
-    };
+/// Gets the text of a tag item, if it is present.
+fn tag_text(tag: &Tag, key: ItemKey) -> Option<String> {
+    tag.get(key)
+        .and_then(|item| item.value().text())
+        .map(str::to_string)
+}
+
+/// Records a track in the catalog.
+///
+/// # Returns
+///
+/// Returns the id of the album the track belongs to.
+fn insert_track(
+    tx: &Transaction,
+    path: &Path,
+    metadata: &TrackMetadata,
+    artist_cache: &mut ArtistCache,
+    album_cache: &mut AlbumCache,
+) -> Result<i64> {
+    let album_id = find_album(tx, metadata, artist_cache, album_cache)?;
 
     let filename = path
         .to_str()
         .context("Path contains invalid UTF-8")?
         .to_string();
 
-    let metadata = std::fs::metadata(path)?;
-    let created_at = metadata
+    let file_metadata = std::fs::metadata(path)?;
+    let created_at = file_metadata
         .created()
-        .or_else(|_| metadata.modified())
+        .or_else(|_| file_metadata.modified())
         .unwrap_or_else(|_| std::time::SystemTime::now())
         .duration_since(std::time::UNIX_EPOCH)
         .map(|d| d.as_secs() as i64)
         .unwrap_or(0);
 
-    tx.execute(
-        "INSERT OR IGNORE INTO tracks (album_id, durable_id, track_number, title, duration, genre, year, filename, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
-        params![album_id, durable_id, track_number, track_title, duration, genre, year, filename, created_at],
+    let inserted = tx.execute(
+        "INSERT OR IGNORE INTO tracks (album_id, durable_id, disc_number, track_number, title, duration, genre, year, filename, created_at, is_podcast, lyrics, artist, album_artist, composer) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        params![
+            album_id,
+            metadata.durable_id,
+            metadata.disc_number,
+            metadata.track_number,
+            metadata.track_title,
+            metadata.duration,
+            metadata.genre,
+            metadata.year,
+            filename,
+            created_at,
+            metadata.is_podcast,
+            metadata.lyrics,
+            metadata.track_artist,
+            metadata.album_artist,
+            metadata.composer
+        ],
     )?;
 
+    if inserted > 0 {
+        let track_id = tx.last_insert_rowid();
+        link_track_artists(tx, track_id, metadata, artist_cache)?;
+        link_track_genres(tx, track_id, metadata)?;
+    }
+
     tx.execute(
         "DELETE FROM track_stats WHERE durable_id NOT IN (SELECT durable_id FROM tracks)",
         [],
     )?;
 
+    // Ratings from tags only fill in tracks that have not been rated here
+    if let Some(stars) = metadata.stars {
+        tx.execute(
+            "INSERT INTO track_stats (durable_id, rating, stars) VALUES (?1, ?2, ?3)
+             ON CONFLICT (durable_id)
+             DO UPDATE SET rating = excluded.rating, stars = excluded.stars
+             WHERE rating = 0 AND stars IS NULL",
+            params![metadata.durable_id, stars.rating(), stars],
+        )?;
+    }
+
+    Ok(album_id)
+}
+
+/// Finds the catalogued album for a track, adding the album and its artist to
+/// the catalog if they are not already there.
+fn find_album(
+    tx: &Transaction,
+    metadata: &TrackMetadata,
+    artist_cache: &mut ArtistCache,
+    album_cache: &mut AlbumCache,
+) -> Result<i64> {
+    let artist_id = if metadata.compilation {
+        find_artist(tx, VARIOUS_ARTISTS, artist_cache)?
+    } else {
+        let artist_id = find_artist(tx, &metadata.artist_name, artist_cache)?;
+
+        // Sort names only fill in artists that do not have one yet
+        if let Some(artist_sort) = &metadata.artist_sort {
+            tx.execute(
+                "UPDATE artists SET sort_name = ? WHERE id = ? AND sort_name IS NULL",
+                params![artist_sort, artist_id],
+            )?;
+        }
+
+        artist_id
+    };
+
+    find_artist_album(tx, artist_id, &metadata.album_title, album_cache)
+}
+
+/// Finds a catalogued artist, adding the artist to the catalog if they are not
+/// already there.
+fn find_artist(tx: &Transaction, artist_name: &str, artist_cache: &mut ArtistCache) -> Result<i64> {
+    if let Some(&id) = artist_cache.get(artist_name) {
+        return Ok(id);
+    }
+
+    tx.execute(
+        "INSERT OR IGNORE INTO artists (name) VALUES (?)",
+        params![artist_name],
+    )?;
+    let id: i64 = tx.query_row(
+        "SELECT id FROM artists WHERE name = ?",
+        params![artist_name],
+        |r| r.get(0),
+    )?;
+    artist_cache.insert(artist_name.to_string(), id);
+
+    Ok(id)
+}
+
+/// Finds a catalogued album of an artist, adding the album to the catalog if
+/// it is not already there.
+fn find_artist_album(
+    tx: &Transaction,
+    artist_id: i64,
+    album_title: &str,
+    album_cache: &mut AlbumCache,
+) -> Result<i64> {
+    let album_key = (artist_id, album_title.to_string());
+    if let Some(&id) = album_cache.get(&album_key) {
+        return Ok(id);
+    }
+
+    tx.execute(
+        "INSERT OR IGNORE INTO albums (artist_id, title) VALUES (?, ?)",
+        params![artist_id, album_title],
+    )?;
+    let id: i64 = tx.query_row(
+        "SELECT id FROM albums WHERE artist_id = ? AND title = ?",
+        params![artist_id, album_title],
+        |r| r.get(0),
+    )?;
+    album_cache.insert(album_key, id);
+
+    Ok(id)
+}
+
+/// Credits each of the artists of a track to it.
+fn link_track_artists(
+    tx: &Transaction,
+    track_id: i64,
+    metadata: &TrackMetadata,
+    artist_cache: &mut ArtistCache,
+) -> Result<()> {
+    for artist_name in &metadata.track_artists {
+        let artist_id = find_artist(tx, artist_name, artist_cache)?;
+        tx.execute(
+            "INSERT OR IGNORE INTO track_artists (track_id, artist_id) VALUES (?, ?)",
+            params![track_id, artist_id],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Files a track under each of its genres, adding any genres that are not
+/// already in the catalog.
+fn link_track_genres(tx: &Transaction, track_id: i64, metadata: &TrackMetadata) -> Result<()> {
+    for genre in &metadata.genres {
+        tx.execute(
+            "INSERT OR IGNORE INTO genres (name) VALUES (?)",
+            params![genre],
+        )?;
+        tx.execute(
+            "INSERT OR IGNORE INTO track_genres (track_id, genre_id)
+             SELECT ?, id FROM genres WHERE name = ?",
+            params![track_id, genre],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Moves the tracks of compilations to albums by "Various Artists".
+///
+/// Tracks without an album artist are catalogued under their own artist, which
+/// splits a compilation into an album for each of its artists. Such albums are
+/// found from their tracks sharing a directory and album title.
+fn group_compilations(
+    tx: &Transaction,
+    artist_cache: &mut ArtistCache,
+    album_cache: &mut AlbumCache,
+) -> Result<()> {
+    type AlbumTracks = (String, Vec<(i64, i64, i64)>);
+
+    let mut groups: HashMap<(PathBuf, String), AlbumTracks> = HashMap::new();
+    {
+        let mut stmt = tx.prepare(
+            "SELECT tr.id, tr.filename, al.id, al.title, al.artist_id
+             FROM tracks tr
+             JOIN albums al ON tr.album_id = al.id
+             WHERE tr.album_artist IS NULL AND tr.is_podcast = 0",
+        )?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let filename: String = row.get(1)?;
+            let album_title: String = row.get(3)?;
+            let directory = Path::new(&filename)
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default();
+
+            groups
+                .entry((directory, album_title.to_lowercase()))
+                .or_insert_with(|| (album_title, Vec::new()))
+                .1
+                .push((row.get(0)?, row.get(2)?, row.get(4)?));
+        }
+    }
+
+    for (album_title, tracks) in groups.into_values() {
+        let artist_id = tracks[0].2;
+        if tracks.iter().all(|&(_, _, id)| id == artist_id) {
+            continue;
+        }
+
+        let various_artists_id = find_artist(tx, VARIOUS_ARTISTS, artist_cache)?;
+        let album_id = find_artist_album(tx, various_artists_id, &album_title, album_cache)?;
+
+        for (track_id, old_album_id, _) in tracks {
+            tx.execute(
+                "UPDATE tracks SET album_id = ? WHERE id = ?",
+                params![album_id, track_id],
+            )?;
+
+            // The cover is kept from whichever of the split albums had one
+            tx.execute(
+                "UPDATE albums SET cover_hash = (SELECT cover_hash FROM albums WHERE id = ?2)
+                 WHERE id = ?1 AND cover_hash IS NULL",
+                params![album_id, old_album_id],
+            )?;
+        }
+    }
+
+    remove_orphans(tx)
+}
+
+/// Returns `true` if the other catalogued tracks of an album in the same
+/// directory as a track are catalogued as a compilation.
+fn in_compilation(tx: &Transaction, path: &Path, album_title: &str, track_id: i32) -> Result<bool> {
+    let mut stmt = tx.prepare(
+        "SELECT tr.filename
+         FROM tracks tr
+         JOIN albums al ON tr.album_id = al.id
+         JOIN artists ar ON al.artist_id = ar.id
+         WHERE ar.name = ? AND al.title = ? AND tr.id != ?",
+    )?;
+    let filenames = stmt
+        .query_map(params![VARIOUS_ARTISTS, album_title, track_id], |r| {
+            r.get::<_, String>(0)
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(filenames
+        .iter()
+        .any(|filename| Path::new(filename).parent() == path.parent()))
+}
+
+/// Removes albums left without tracks, artists left without albums or tracks,
+/// and genres left without tracks.
+fn remove_orphans(tx: &Transaction) -> Result<()> {
+    tx.execute(
+        "DELETE FROM albums WHERE id NOT IN (SELECT album_id FROM tracks)",
+        [],
+    )?;
+    tx.execute(
+        "DELETE FROM artists
+         WHERE id NOT IN (SELECT artist_id FROM albums)
+         AND id NOT IN (SELECT artist_id FROM track_artists)",
+        [],
+    )?;
+    tx.execute(
+        "DELETE FROM genres WHERE id NOT IN (SELECT genre_id FROM track_genres)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Splits the values of a genre tag into the genres they name, ignoring
+/// repeats.
+fn split_genres<'a>(values: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut genres: Vec<String> = Vec::new();
+    for genre in values.flat_map(|value| value.split(GENRE_SEPARATORS)) {
+        let genre = genre.trim();
+        if !genre.is_empty() && !genres.iter().any(|g| g.eq_ignore_ascii_case(genre)) {
+            genres.push(genre.to_string());
+        }
+    }
+
+    genres
+}
+
+/// Splits an artist tag into the names of the artists it credits.
+///
+/// Bracketed credits, as in "Artist (feat. Other)", lose their brackets.
+fn split_artists(artist: &str) -> Vec<String> {
+    // Lowercasing only ASCII keeps the offsets of the separators the same
+    let lower = artist.to_ascii_lowercase();
+
+    let mut names: Vec<String> = Vec::new();
+    let mut start = 0;
+    let mut closing = None;
+    loop {
+        let next = ARTIST_SEPARATORS
+            .iter()
+            .filter_map(|sep| lower[start..].find(sep).map(|i| (start + i, *sep)))
+            .min_by_key(|&(i, _)| i);
+        let end = next.map_or(artist.len(), |(i, _)| i);
+
+        let mut name = artist[start..end].trim();
+        if let Some(bracket) = closing
+            && let Some(stripped) = name.strip_suffix(bracket)
+        {
+            name = stripped.trim_end();
+            closing = None;
+        }
+        if !name.is_empty() && !names.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+            names.push(name.to_string());
+        }
+
+        let Some((i, sep)) = next else {
+            break;
+        };
+        start = i + sep.len();
+        match sep.trim_start().chars().next() {
+            Some('(') => closing = Some(')'),
+            Some('[') => closing = Some(']'),
+            _ => {}
+        }
+    }
+
+    names
+}
+
+/// Records the cover of an album that does not have one yet, from the pictures
+/// embedded in one of its tracks, or else from an image in the directory of
+/// that track.
+///
+/// A cover that can not be read does not stop the track being catalogued.
+/// The album is instead added to `failed_covers`, and its other tracks are not
+/// tried again.
+fn store_album_cover(
+    tx: &Transaction,
+    album_id: i64,
+    path: &Path,
+    embedded_cover: Option<&[u8]>,
+    failed_covers: &mut FailedCovers,
+) -> Result<()> {
+    if failed_covers.contains_key(&album_id) {
+        return Ok(());
+    }
+
+    let has_cover: bool = tx.query_row(
+        "SELECT cover_hash IS NOT NULL FROM albums WHERE id = ?",
+        params![album_id],
+        |r| r.get(0),
+    )?;
+    if has_cover {
+        return Ok(());
+    }
+
+    match cache_album_cover(path, embedded_cover) {
+        Ok(Some(cover_hash)) => {
+            tx.execute(
+                "UPDATE albums SET cover_hash = ? WHERE id = ?",
+                params![cover_hash, album_id],
+            )?;
+        }
+        Ok(None) => {}
+        Err(e) => {
+            failed_covers.insert(album_id, format!("{} | {:#}\n", path.display(), e));
+        }
+    }
+
     Ok(())
 }
+
+/// Adds the cover for a track to the thumbnail cache, preferring its embedded
+/// picture and falling back to an image in its directory.
+///
+/// # Returns
+///
+/// Returns the hash identifying the cover, or `None` if the track has no
+/// cover.
+fn cache_album_cover(path: &Path, embedded_cover: Option<&[u8]>) -> Result<Option<String>> {
+    let embedded_error = match embedded_cover.map(covers::cache_cover) {
+        Some(Ok(cover_hash)) => return Ok(Some(cover_hash)),
+        Some(Err(e)) => Some(e),
+        None => None,
+    };
+
+    // A damaged embedded picture is reported only if there is no folder image
+    // to use instead
+    match covers::read_folder_cover(path)? {
+        Some(data) => covers::cache_cover(&data).map(Some),
+        None => embedded_error.map_or(Ok(None), Err),
+    }
+}
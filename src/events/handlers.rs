@@ -13,16 +13,40 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::time::Duration;
+
 use anyhow::Result;
 
 use crate::{
     App, MainView, PlayMode, RepeatMode,
-    events::{AppEvent, CatalogEvent},
-    model::{Album, Artist, SearchQuery, Track, TrackInfo},
+    browser::{BrowseHierarchy, MediaBrowserPane},
+    components::TrackTable,
+    config,
+    events::{AppEvent, CatalogEvent, OrganiseEvent},
+    model::{
+        Album, Artist, Bookmark, Episode, Genre, PlayRecord, Rating, SearchQuery, StarRating,
+        Station, Track, TrackInfo,
+        auto_dj::{self, AutoDjPick, AutoDjRequest},
+        equalizer::ChannelMix,
+        lyrics::Lyrics,
+        queue::ShuffleMode,
+        sleep::{SleepAction, SleepTarget},
+        stats::LibraryStats,
+        tags::TrackTags,
+    },
     player::PlayerState,
     tasks::AppTask,
+    util::format::{TimeFormat, format_time},
 };
 
+/// How often, in seconds of playback, the position of a resumable track is
+/// saved.
+const RESUME_SAVE_INTERVAL_SECS: u64 = 10;
+
+/// Jumping to the previous bookmark skips any this close behind the current
+/// position, so repeated jumps keep moving backwards.
+const PREVIOUS_BOOKMARK_GRACE_SECS: u64 = 3;
+
 pub(super) fn handle_catalog_event(app: &mut App, catalog_event: CatalogEvent) {
     match catalog_event {
         CatalogEvent::Started => app.catalog.prepare_scan(&app.config.media_dirs),
@@ -33,17 +57,63 @@ pub(super) fn handle_catalog_event(app: &mut App, catalog_event: CatalogEvent) {
     }
 }
 
-pub(super) fn handle_catalog_updated(app: &mut App) {
-    app.task_tx.send(AppTask::GetBrowserArtists).unwrap();
+pub(super) fn handle_organise_event(app: &mut App, event: OrganiseEvent) {
+    match event {
+        OrganiseEvent::Planned(moves) => {
+            app.organise.set_planned(moves);
+            app.organise_view.ensure_table_selection();
+        }
+        OrganiseEvent::Started => app.organise.start_moving(),
+        OrganiseEvent::Progress(count) => app.organise.update_progress(count),
+        OrganiseEvent::Failed(index, error) => app.organise.move_failed(index, error),
+        OrganiseEvent::Finished(moved) => app.organise.finish(moved),
+    }
+}
+
+/// Makes the moves last previewed by `:organise`.
+pub(super) fn handle_apply_organise(app: &mut App) -> Result<()> {
+    match app.organise.planned_moves() {
+        Some(moves) => app.task_tx.send(AppTask::Organise(moves))?,
+        None => app.event_tx.send(AppEvent::Error(
+            "Nothing to organise, preview the moves with :organise first".to_string(),
+        ))?,
+    }
+
+    Ok(())
+}
+
+pub(super) fn handle_catalog_updated(app: &mut App) -> Result<()> {
+    refresh_browser(app)
 }
 
-pub(super) fn handle_set_main_view(app: &mut App, main_view: MainView) {
+/// Fetches the first pane of the media browser for its current hierarchy, the
+/// other panes following on from its selection.
+pub(super) fn refresh_browser(app: &mut App) -> Result<()> {
+    let task = match app.media_browser.hierarchy {
+        BrowseHierarchy::Artist => AppTask::GetBrowserArtists,
+        BrowseHierarchy::Genre => AppTask::GetBrowserGenres,
+        BrowseHierarchy::Decade => AppTask::GetBrowserDecades,
+        BrowseHierarchy::RecentlyAdded => AppTask::GetBrowserRecentAlbums,
+    };
+    app.task_tx.send(task)?;
+
+    Ok(())
+}
+
+pub(super) fn handle_set_main_view(app: &mut App, main_view: MainView) -> Result<()> {
     app.playlist_view.is_active = matches!(main_view, MainView::Playlist);
     app.search_view.is_active = matches!(main_view, MainView::Search);
     app.favourites_view.is_active = matches!(main_view, MainView::Favourites);
     app.catalog_view.is_active = matches!(main_view, MainView::Catalog);
     app.equalizer_view.is_active = matches!(main_view, MainView::Equalizer);
     app.help_view.is_active = matches!(main_view, MainView::Help);
+    app.bookmarks_view.is_active = matches!(main_view, MainView::Bookmarks);
+    app.history_view.is_active = matches!(main_view, MainView::History);
+    app.stats_view.is_active = matches!(main_view, MainView::Stats);
+    app.stations_view.is_active = matches!(main_view, MainView::Stations);
+    app.podcasts_view.is_active = matches!(main_view, MainView::Podcasts);
+    app.organise_view.is_active = matches!(main_view, MainView::Organise);
+    app.lyrics_view.is_active = matches!(main_view, MainView::Lyrics);
 
     if matches!(main_view, MainView::Browse) {
         app.favourites_view.is_active = false;
@@ -52,8 +122,22 @@ pub(super) fn handle_set_main_view(app: &mut App, main_view: MainView) {
         app.search_view.is_active = false;
         app.equalizer_view.is_active = false;
         app.help_view.is_active = false;
+        app.bookmarks_view.is_active = false;
+        app.history_view.is_active = false;
+        app.stats_view.is_active = false;
+        app.stations_view.is_active = false;
+        app.podcasts_view.is_active = false;
+        app.organise_view.is_active = false;
+        app.lyrics_view.is_active = false;
     }
     app.main_view = main_view;
+
+    // Ratings may have changed since the favourites were last fetched
+    if app.favourites_view.is_active {
+        app.favourites_view.refresh(&app.task_tx)?;
+    }
+
+    Ok(())
 }
 
 pub(super) fn handle_new_search_query(app: &mut App, query: SearchQuery) -> Result<()> {
@@ -75,25 +159,110 @@ pub(super) fn handle_add_selection_to_playlist(app: &mut App) {
     app.queue.add_tracks(tracks);
 }
 
-pub(super) fn handle_play_track(app: &mut App, track: TrackInfo) -> Result<()> {
+pub(super) fn handle_play_track(app: &mut App, track: TrackInfo, start: Duration) -> Result<()> {
+    // Remember where the outgoing track got to, if it was still playing
+    if app.player_state != PlayerState::Stopped {
+        save_resume_position(app, true)?;
+    }
+
+    // The outgoing track, if any, was cut short
+    finish_play(app, true)?;
+    app.history.start(
+        &track,
+        app.config.play_count_percent,
+        app.config.play_count_secs,
+    );
+    app.task_tx.send(AppTask::SubmitPlayingNow(track.clone()))?;
+    app.task_tx.send(AppTask::GetLyrics(track.clone()))?;
+    app.lyrics = None;
+    app.lyrics_view.reset();
+
     // app.play_mode = PlayMode::PlayOne;
-    app.audio_player.play_track(track.clone())?;
+    app.audio_player.play_track(track.clone(), start)?;
     app.now_playing = Some(track);
+    app.now_streaming = None;
+    app.resume_saved_time = Some(start.as_secs());
+
+    Ok(())
+}
+
+pub(super) fn handle_play_station(app: &mut App, station: Station) -> Result<()> {
+    if app.player_state != PlayerState::Stopped {
+        save_resume_position(app, true)?;
+    }
+    finish_play(app, true)?;
+
+    app.audio_player.play_station(station.clone())?;
+    app.now_playing = None;
+    app.lyrics = None;
+    app.now_streaming = Some(station);
+    app.player_time = None;
+    app.player_position = None;
+    app.resume_saved_time = None;
+
+    Ok(())
+}
+
+pub(super) fn handle_stop_playback(app: &mut App) -> Result<()> {
+    save_resume_position(app, true)?;
+    finish_play(app, true)?;
+    app.audio_player.stop()?;
+
+    Ok(())
+}
+
+/// Records the play of the current track in the play history, if a track is
+/// being played.
+///
+/// # Arguments
+///
+/// * `app` - The application state.
+/// * `skipped` - Whether the track was stopped before it finished.
+fn finish_play(app: &mut App, skipped: bool) -> Result<()> {
+    if let Some(session) = app.history.finish() {
+        app.task_tx.send(AppTask::RecordPlay(session, skipped))?;
+    }
+
+    Ok(())
+}
+
+/// Saves the playback position of the current track, if it is resumable.
+///
+/// Unless forced, the position is only saved periodically as playback
+/// progresses.
+fn save_resume_position(app: &mut App, force: bool) -> Result<()> {
+    let Some(track) = &app.now_playing else {
+        return Ok(());
+    };
+
+    if !track.is_resumable(app.config.resume_threshold_secs, &app.config.resume_genres) {
+        return Ok(());
+    }
+
+    let Some(time) = app.player_time else {
+        return Ok(());
+    };
+
+    let due = app
+        .resume_saved_time
+        .is_none_or(|saved| time.abs_diff(saved) >= RESUME_SAVE_INTERVAL_SECS);
+
+    if force || due {
+        app.task_tx
+            .send(AppTask::SaveResumePosition(track.durable_id, time))?;
+        app.resume_saved_time = Some(time);
+    }
 
     Ok(())
 }
 
 pub(super) fn handle_play_playlist(app: &mut App) -> Result<()> {
     app.play_mode = PlayMode::Playlist;
-    if app.current_queue_idx.is_none() {
-        let lock = app.queue.tracks();
-        let tracks = lock.lock().unwrap();
-        if !tracks.is_empty() {
-            app.current_queue_idx = Some(0);
-            if let Some(track) = tracks.get(0).cloned() {
-                app.task_tx.send(AppTask::PlayTrack(track))?;
-            }
+    if app.queue.current_index().is_none() {
+        if let Some(track) = app.queue.play_first() {
+            app.task_tx.send(AppTask::PlayTrack(track))?;
         }
+        top_up_queue(app)?;
     } else {
         app.audio_player.play()?;
     }
@@ -108,29 +277,62 @@ pub(super) fn handle_shuffle_queue(app: &mut App) -> Result<()> {
 }
 
 pub(super) fn handle_reset_queue(app: &mut App) -> Result<()> {
-    app.queue.reset();
+    app.queue.set_shuffle_mode(ShuffleMode::Off);
 
     Ok(())
 }
 
+pub(super) fn handle_set_shuffle_mode(app: &mut App, mode: ShuffleMode) {
+    app.queue.set_shuffle_mode(mode);
+}
+
 pub(super) fn handle_add_tracks_to_playlist(app: &mut App, tracks: Vec<TrackInfo>) -> Result<()> {
     app.queue.add_tracks(tracks);
     app.event_tx
         .send(AppEvent::SetMainView(MainView::Playlist))?;
     app.search_view.track_table.clear_selection();
+    app.favourites_view.track_table.clear_selection();
     app.playlist_view.track_table.ensure_table_selection();
 
     Ok(())
 }
 
 pub(super) fn handle_artist_selection_changed(app: &mut App, id: i32) -> Result<()> {
-    app.task_tx.send(AppTask::GetBrowserAlbums(id))?;
+    // Browsing by genre only lists the artist's albums in that genre
+    let task = match app.media_browser.hierarchy {
+        BrowseHierarchy::Genre => match app.media_browser.selected_genre_id() {
+            Some(genre_id) => AppTask::GetBrowserGenreAlbums(genre_id, id),
+            None => return Ok(()),
+        },
+        _ => AppTask::GetBrowserAlbums(id),
+    };
+    app.task_tx.send(task)?;
 
     Ok(())
 }
 
 pub(super) fn handle_album_selection_changed(app: &mut App, id: i32) -> Result<()> {
-    app.task_tx.send(AppTask::GetBrowserTracks(id))?;
+    if app.media_browser.shows_pane(MediaBrowserPane::Track) {
+        app.task_tx.send(AppTask::GetBrowserTracks(id))?;
+    }
+
+    Ok(())
+}
+
+pub(super) fn handle_genre_selection_changed(app: &mut App, id: i32) -> Result<()> {
+    app.task_tx.send(AppTask::GetBrowserGenreArtists(id))?;
+
+    Ok(())
+}
+
+pub(super) fn handle_decade_selection_changed(app: &mut App, decade: i64) -> Result<()> {
+    app.task_tx.send(AppTask::GetBrowserYears(decade))?;
+
+    Ok(())
+}
+
+pub(super) fn handle_year_selection_changed(app: &mut App, year: i64) -> Result<()> {
+    app.task_tx.send(AppTask::GetBrowserYearAlbums(year))?;
 
     Ok(())
 }
@@ -139,6 +341,36 @@ pub(super) fn handle_add_tracks_to_queue(app: &mut App, tracks: Vec<TrackInfo>)
     app.queue.add_tracks(tracks);
 }
 
+pub(super) fn handle_set_browser_genres(app: &mut App, genres: Vec<Genre>) -> Result<()> {
+    let first_id = genres.first().map(|g| g.id);
+    app.media_browser.set_genres(genres);
+    if let Some(id) = first_id {
+        app.event_tx.send(AppEvent::GenreSelectionChanged(id))?;
+    }
+
+    Ok(())
+}
+
+pub(super) fn handle_set_browser_decades(app: &mut App, decades: Vec<i64>) -> Result<()> {
+    let first = decades.first().copied();
+    app.media_browser.set_decades(decades);
+    if let Some(decade) = first {
+        app.event_tx.send(AppEvent::DecadeSelectionChanged(decade))?;
+    }
+
+    Ok(())
+}
+
+pub(super) fn handle_set_browser_years(app: &mut App, years: Vec<i64>) -> Result<()> {
+    let first = years.first().copied();
+    app.media_browser.set_years(years);
+    if let Some(year) = first {
+        app.event_tx.send(AppEvent::YearSelectionChanged(year))?;
+    }
+
+    Ok(())
+}
+
 pub(super) fn handle_set_browser_artists(app: &mut App, artists: Vec<Artist>) -> Result<()> {
     let first_id = artists.first().map(|a| a.id);
     app.media_browser.set_artists(artists);
@@ -191,10 +423,39 @@ pub(super) fn handle_volume_changed(app: &mut App, vol: u32) {
 
 pub(super) fn handle_track_finished(app: &mut App) -> Result<()> {
     app.player_time = app.player_duration;
+    finish_play(app, false)?;
+
+    // A resumable track that played to the end starts from the beginning next
+    // time
+    if let Some(track) = &app.now_playing
+        && track.is_resumable(app.config.resume_threshold_secs, &app.config.resume_genres)
+    {
+        app.task_tx
+            .send(AppTask::ClearResumePosition(track.durable_id))?;
+    }
+
+    if let Some(track) = &app.now_playing
+        && track.is_podcast
+    {
+        app.task_tx
+            .send(AppTask::TrackEpisodePlayed(track.durable_id))?;
+    }
+
+    if app.sleep_timer.expires_on_track_finished(is_last_in_queue(app)) {
+        return expire_sleep_timer(app);
+    }
+
     next_track(app)
 }
 
 pub(super) fn handle_next_track(app: &mut App) -> Result<()> {
+    // Moving on before the track counted as played is a skip
+    if app.history.is_uncounted()
+        && let Some(track) = &app.now_playing
+    {
+        app.task_tx.send(AppTask::SkipTrack(track.durable_id))?;
+    }
+
     next_track(app)
 }
 
@@ -204,36 +465,18 @@ pub(super) fn handle_previous_track(app: &mut App) -> Result<()> {
 
 fn next_track(app: &mut App) -> Result<()> {
     if app.play_mode == PlayMode::Playlist {
-        let lock = app.queue.tracks();
-        let tracks = lock.lock().unwrap();
-        let total_tracks = tracks.len();
-
-        if total_tracks == 0 {
-            app.current_queue_idx = None;
+        if app.queue.current_index().is_none() {
             return Ok(());
         }
 
-        if let Some(idx) = app.current_queue_idx.as_mut() {
-            if app.repeat_mode != RepeatMode::RepeatOne {
-                let next_idx = *idx + 1;
-                if next_idx < total_tracks {
-                    *idx = next_idx;
-                } else if app.repeat_mode == RepeatMode::RepeatAll {
-                    *idx = 0;
-                } else {
-                    app.current_queue_idx = None;
-                }
-            }
-
-            if let Some(valid_idx) = app.current_queue_idx {
-                if let Some(track) = tracks.get(valid_idx).cloned() {
-                    app.task_tx.send(AppTask::PlayTrack(track))?;
-                }
-            } else {
-                app.now_playing = None;
-                app.audio_player.stop()?;
-            }
+        if let Some(track) = app.queue.next(app.repeat_mode) {
+            app.task_tx.send(AppTask::PlayTrack(track))?;
+        } else {
+            app.now_playing = None;
+            app.audio_player.stop()?;
         }
+
+        top_up_queue(app)?;
     } else if app.play_mode == PlayMode::PlayOne {
         if let Some(track) = app.now_playing.clone() {
             match app.repeat_mode {
@@ -252,27 +495,8 @@ fn next_track(app: &mut App) -> Result<()> {
 
 fn previous_track(app: &mut App) -> Result<()> {
     if app.play_mode == PlayMode::Playlist {
-        let lock = app.queue.tracks();
-        let tracks = lock.lock().unwrap();
-        let total_tracks = tracks.len();
-
-        if total_tracks == 0 {
-            app.current_queue_idx = None;
-            return Ok(());
-        }
-
-        if let Some(idx) = app.current_queue_idx.as_mut() {
-            if app.repeat_mode != RepeatMode::RepeatOne {
-                if *idx > 0 {
-                    *idx -= 1;
-                } else if app.repeat_mode == RepeatMode::RepeatAll {
-                    *idx = total_tracks.saturating_sub(1);
-                }
-            }
-
-            if let Some(track) = tracks.get(*idx).cloned() {
-                app.task_tx.send(AppTask::PlayTrack(track))?;
-            }
+        if let Some(track) = app.queue.previous(app.repeat_mode) {
+            app.task_tx.send(AppTask::PlayTrack(track))?;
         }
     } else if app.play_mode == PlayMode::PlayOne {
         if let Some(track) = app.now_playing.clone() {
@@ -283,8 +507,15 @@ fn previous_track(app: &mut App) -> Result<()> {
     Ok(())
 }
 
-pub(super) fn handle_time_changed(app: &mut App, seconds: f64) {
+pub(super) fn handle_time_changed(app: &mut App, seconds: f64) -> Result<()> {
     app.player_time = Some(seconds as u64);
+    let time = Duration::try_from_secs_f64(seconds).unwrap_or_default();
+    app.lyrics_view.time_changed(app.lyrics.as_ref(), time);
+    if app.history.time_changed(seconds)
+        && let Some(track) = app.now_playing.clone()
+    {
+        app.task_tx.send(AppTask::CountPlay(track))?;
+    }
     if let Some(duration) = app.player_duration {
         app.player_position = if duration > 0 {
             Some(seconds / duration as f64)
@@ -292,83 +523,277 @@ pub(super) fn handle_time_changed(app: &mut App, seconds: f64) {
             None
         };
     }
+
+    save_resume_position(app, false)
 }
 
-pub(super) fn handle_tick(_app: &mut App) {}
+pub(super) fn handle_tick(app: &mut App) -> Result<()> {
+    update_sleep_timer(app)
+}
 
-pub(super) fn handle_find_selected_artist(app: &mut App) -> Result<()> {
+pub(super) fn handle_terminal_resized(app: &mut App) {
+    // The terminal is cleared on the next draw, taking any images with it
+    app.cover_art.invalidate();
+}
+
+fn update_sleep_timer(app: &mut App) -> Result<()> {
+    if !app.sleep_timer.is_active() {
+        return Ok(());
+    }
+
+    if app.sleep_timer.deadline_passed() {
+        return expire_sleep_timer(app);
+    }
+
+    let track_remaining = track_remaining(app);
+    let queue_remaining =
+        track_remaining.map(|remaining| remaining + queued_duration_after_current(app));
+
+    if let Some(gain) = app.sleep_timer.update(track_remaining, queue_remaining) {
+        app.audio_player.set_fade(gain)?;
+    }
+
+    Ok(())
+}
+
+fn expire_sleep_timer(app: &mut App) -> Result<()> {
+    let action = app.sleep_timer.action();
+    app.sleep_timer.cancel();
+
+    finish_play(app, true)?;
+    app.audio_player.stop()?;
+    if app.sleep_timer.reset_fade() {
+        app.audio_player.set_fade(1.0)?;
+    }
+
+    if action == SleepAction::Quit {
+        app.event_tx.send(AppEvent::ExitApplication)?;
+    }
+
+    Ok(())
+}
+
+/// Time left in the currently playing track, if anything is playing.
+fn track_remaining(app: &App) -> Option<Duration> {
+    if app.player_state == PlayerState::Stopped || app.now_playing.is_none() {
+        return None;
+    }
+
+    // Use the position rather than the whole-second time for a smoother fade
+    let duration = app.player_duration? as f64;
+    let position = app.player_position.unwrap_or(0.0).clamp(0.0, 1.0);
+    Some(Duration::from_secs_f64(duration * (1.0 - position)))
+}
+
+/// Total duration of the tracks that will play from the queue after the
+/// current one.
+fn queued_duration_after_current(app: &App) -> Duration {
+    if app.play_mode != PlayMode::Playlist {
+        return Duration::ZERO;
+    }
+
+    Duration::from_secs(app.queue.remaining_duration() as u64)
+}
+
+fn is_last_in_queue(app: &App) -> bool {
+    if app.play_mode != PlayMode::Playlist {
+        return true;
+    }
+
+    app.queue.is_last()
+}
+
+/// The track table in the current main view, if it has one that selected
+/// tracks can be taken from.
+fn active_track_table(app: &App) -> Option<&TrackTable> {
     match app.main_view {
-        MainView::Search => {
-            if let Some(track) = app.search_view.track_table.clone_selected_track() {
-                let artist = track.artist_name;
-                let query = SearchQuery::for_artist(artist);
-                app.task_tx.send(AppTask::Search(query))?;
-            }
-        }
-        _ => {}
+        MainView::Search => Some(&app.search_view.track_table),
+        MainView::Favourites => Some(&app.favourites_view.track_table),
+        _ => None,
+    }
+}
+
+pub(super) fn handle_find_selected_artist(app: &mut App) -> Result<()> {
+    if let Some(track) = active_track_table(app).and_then(TrackTable::clone_selected_track) {
+        let artist = track.artist_name;
+        let query = SearchQuery::for_artist(artist);
+        app.task_tx.send(AppTask::Search(query))?;
     }
 
     Ok(())
 }
 
 pub(super) fn handle_find_selected_album(app: &mut App) -> Result<()> {
-    match app.main_view {
-        MainView::Search => {
-            if let Some(track) = app.search_view.track_table.clone_selected_track() {
-                let album = track.album_title;
-                let query = SearchQuery::for_album(album);
-                app.task_tx.send(AppTask::Search(query))?;
-            }
-        }
-        _ => {}
+    if let Some(track) = active_track_table(app).and_then(TrackTable::clone_selected_track) {
+        let album = track.album_title;
+        let query = SearchQuery::for_album(album);
+        app.task_tx.send(AppTask::Search(query))?;
     }
 
     Ok(())
 }
 
 pub(super) fn handle_add_selected_artist_to_queue(app: &mut App) {
-    match app.main_view {
-        MainView::Search => {
-            let tracks = app.search_view.track_table.clone_selected_artist_tracks();
-            app.queue.add_tracks(tracks);
-        }
-        _ => {}
+    if let Some(table) = active_track_table(app) {
+        let tracks = table.clone_selected_artist_tracks();
+        app.queue.add_tracks(tracks);
     }
 }
 
 pub(super) fn handle_add_selected_album_to_queue(app: &mut App) {
-    match app.main_view {
-        MainView::Search => {
-            let tracks = app.search_view.track_table.clone_selected_album_tracks();
-            app.queue.add_tracks(tracks);
-        }
-        _ => {}
+    if let Some(table) = active_track_table(app) {
+        let tracks = table.clone_selected_album_tracks();
+        app.queue.add_tracks(tracks);
     }
 }
 
 pub(super) fn handle_add_selected_track_to_queue(app: &mut App) {
-    match app.main_view {
-        MainView::Search => {
-            if let Some(track) = app.search_view.track_table.clone_selected_track() {
-                app.queue.add_tracks(vec![track]);
-            }
-        }
-        _ => {}
+    if let Some(track) = active_track_table(app).and_then(TrackTable::clone_selected_track) {
+        app.queue.add_tracks(vec![track]);
     }
 }
 
+/// Gives the selected track in the current view stars, or the playing track
+/// if there is no selected track.
+pub(super) fn handle_rate_selected_stars(app: &mut App, stars: Option<StarRating>) -> Result<()> {
+    let track = active_track_table(app)
+        .and_then(TrackTable::clone_selected_track)
+        .or_else(|| app.now_playing.clone());
+
+    if let Some(track) = track {
+        app.task_tx.send(AppTask::RateTrackStars(track, stars))?;
+    }
+
+    Ok(())
+}
+
 pub(super) fn handle_clear_queue(app: &mut App) {
-    app.current_queue_idx = None;
     app.queue.clear();
 }
 
+pub(super) fn handle_play_queue_entry(app: &mut App, row: usize) -> Result<()> {
+    if let Some(track) = app.queue.play_entry(row) {
+        app.play_mode = PlayMode::Playlist;
+        app.task_tx.send(AppTask::PlayTrack(track))?;
+    }
+
+    top_up_queue(app)?;
+
+    Ok(())
+}
+
+pub(super) fn handle_play_queue_entries_next(app: &mut App, rows: Vec<usize>) {
+    let positions = app.queue.play_next(&rows);
+    update_current_row(app, &positions);
+}
+
+pub(super) fn handle_move_queue_entries(app: &mut App, rows: Vec<usize>, direction: i8) {
+    let positions = app.queue.move_entries(&rows, direction);
+    update_current_row(app, &positions);
+}
+
+pub(super) fn handle_remove_queue_entries(app: &mut App, rows: Vec<usize>) {
+    app.queue.remove_entries(&rows);
+
+    let len = app.queue.tracks().lock().unwrap().len();
+    let table = &mut app.playlist_view.track_table;
+    table.clear_selection();
+    if len == 0 {
+        table.set_current_row(None);
+    } else if let Some(row) = rows.first() {
+        table.set_current_row(Some((*row).min(len - 1)));
+    }
+}
+
+pub(super) fn handle_undo_queue(app: &mut App) {
+    let message = match app.queue.undo() {
+        Some(operation) => format!("Undid {}", operation),
+        None => "Nothing to undo".to_string(),
+    };
+
+    app.playlist_view.track_table.clear_selection();
+    app.status.set_message(Some(message));
+}
+
+pub(super) fn handle_redo_queue(app: &mut App) {
+    let message = match app.queue.redo() {
+        Some(operation) => format!("Redid {}", operation),
+        None => "Nothing to redo".to_string(),
+    };
+
+    app.playlist_view.track_table.clear_selection();
+    app.status.set_message(Some(message));
+}
+
+/// Keeps the highlighted playlist row on the same entry after the queue has
+/// been rearranged.
+fn update_current_row(app: &mut App, positions: &[Option<usize>]) {
+    let table = &mut app.playlist_view.track_table;
+    if let Some(row) = table.current_row().and_then(|row| positions.get(row).copied().flatten()) {
+        table.set_current_row(Some(row));
+    }
+}
+
 pub(super) fn handle_set_repeat_mode(app: &mut App, mode: RepeatMode) {
     app.repeat_mode = mode;
 }
 
+pub(super) fn handle_set_sleep_timer(
+    app: &mut App,
+    target: SleepTarget,
+    action: SleepAction,
+) -> Result<()> {
+    if let Err(e) = app.sleep_timer.start(target, action) {
+        app.event_tx.send(AppEvent::Error(e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+pub(super) fn handle_cancel_sleep_timer(app: &mut App) -> Result<()> {
+    app.sleep_timer.cancel();
+    if app.sleep_timer.reset_fade() {
+        app.audio_player.set_fade(1.0)?;
+    }
+
+    Ok(())
+}
+
 pub(super) fn handle_track_updated(app: &mut App, track: TrackInfo) {
     app.queue.track_updated(&track);
     app.search.track_updated(&track);
+    app.favourites.track_updated(&track);
+    app.favourites_view.track_table.ensure_table_selection();
+}
+
+pub(super) fn handle_tracks_updated(app: &mut App, tracks: Vec<TrackInfo>) {
+    for track in tracks {
+        handle_track_updated(app, track);
+    }
+}
+
+pub(super) fn handle_favourites_ready(app: &mut App, rating: Rating, tracks: Vec<TrackInfo>) {
+    app.favourites.set_tracks(rating, tracks);
+    app.favourites_view.track_table.ensure_table_selection();
+}
+
+pub(super) fn handle_tags_ready(app: &mut App, tracks: Vec<TrackInfo>, tags: Vec<TrackTags>) {
+    app.tag_editor.open(tracks, &tags);
+}
+
+pub(super) fn handle_lyrics_ready(app: &mut App, track_id: i32, lyrics: Option<Lyrics>) {
+    // Lyrics arriving after the track has changed are no longer wanted
+    if app.now_playing.as_ref().map(|track| track.track_id) != Some(track_id) {
+        return;
+    }
+
+    app.lyrics = lyrics;
+    app.lyrics_view.reset();
+
+    if let Some(time) = app.player_time {
+        app.lyrics_view
+            .time_changed(app.lyrics.as_ref(), Duration::from_secs(time));
+    }
 }
 
 pub(super) fn handle_reset_equalizer(app: &mut App) -> Result<()> {
@@ -397,3 +822,166 @@ pub(super) fn handle_update_equalizer_amp(app: &mut App, index: usize, value: f6
 
     Ok(())
 }
+
+pub(super) fn handle_set_channel_mix(app: &mut App, channel_mix: ChannelMix) -> Result<()> {
+    app.equalizer.channel_mix = channel_mix;
+    app.audio_player.set_channel_mix(channel_mix)?;
+
+    // Unlike the equalizer bands, channel mixing compensates for the listener
+    // rather than the music, so it is remembered between sessions
+    app.config.channel_mix = channel_mix;
+    if let Err(e) = config::save_config(&app.config) {
+        app.event_tx
+            .send(AppEvent::Error(format!("Failed to save config: {}", e)))?;
+    }
+
+    Ok(())
+}
+
+pub(super) fn handle_bookmarks_ready(app: &mut App, bookmarks: Vec<Bookmark>) {
+    app.bookmarks.set_bookmarks(bookmarks);
+    app.bookmarks_view.ensure_table_selection();
+}
+
+pub(super) fn handle_history_ready(app: &mut App, records: Vec<PlayRecord>) {
+    app.history.set_records(records);
+    app.history_view.ensure_table_selection();
+}
+
+pub(super) fn handle_stats_ready(app: &mut App, stats: LibraryStats) {
+    app.stats = Some(stats);
+}
+
+pub(super) fn handle_add_bookmark(app: &mut App, name: Option<String>) -> Result<()> {
+    let (Some(track), Some(time)) = (app.now_playing.clone(), app.player_time) else {
+        return Ok(());
+    };
+
+    let name = name.unwrap_or_else(|| format_time(time, TimeFormat::Minutes));
+    app.task_tx.send(AppTask::AddBookmark(track, name, time))?;
+
+    Ok(())
+}
+
+pub(super) fn handle_jump_to_bookmark(app: &mut App, bookmark: Bookmark) -> Result<()> {
+    let playing_track = app
+        .now_playing
+        .as_ref()
+        .is_some_and(|track| track.durable_id == bookmark.track.durable_id);
+
+    if playing_track && app.player_state != PlayerState::Stopped {
+        app.audio_player
+            .seek_absolute(Duration::from_secs(bookmark.position))?;
+    } else {
+        app.task_tx
+            .send(AppTask::PlayTrackFrom(bookmark.track, bookmark.position))?;
+    }
+
+    Ok(())
+}
+
+pub(super) fn handle_next_bookmark(app: &mut App) -> Result<()> {
+    let Some(track) = &app.now_playing else {
+        return Ok(());
+    };
+
+    let time = app.player_time.unwrap_or(0);
+    if let Some(bookmark) = app.bookmarks.next_in_track(track.durable_id, time) {
+        app.audio_player
+            .seek_absolute(Duration::from_secs(bookmark.position))?;
+    }
+
+    Ok(())
+}
+
+pub(super) fn handle_previous_bookmark(app: &mut App) -> Result<()> {
+    let Some(track) = &app.now_playing else {
+        return Ok(());
+    };
+
+    let time = app
+        .player_time
+        .unwrap_or(0)
+        .saturating_sub(PREVIOUS_BOOKMARK_GRACE_SECS);
+    if let Some(bookmark) = app.bookmarks.previous_in_track(track.durable_id, time) {
+        app.audio_player
+            .seek_absolute(Duration::from_secs(bookmark.position))?;
+    }
+
+    Ok(())
+}
+
+pub(super) fn handle_stations_ready(app: &mut App, stations: Vec<Station>) {
+    app.stations.set_stations(stations);
+    app.stations_view.ensure_table_selection();
+}
+
+pub(super) fn handle_episodes_ready(app: &mut App, episodes: Vec<Episode>) {
+    app.podcasts.set_episodes(episodes);
+    app.podcasts_view.ensure_table_selection();
+}
+
+pub(super) fn handle_error(app: &mut App, message: String) {
+    app.status.set_message(Some(message));
+}
+
+pub(super) fn handle_set_auto_dj(app: &mut App, enabled: bool) -> Result<()> {
+    app.auto_dj.set_enabled(enabled);
+
+    app.config.auto_dj = enabled;
+    if let Err(e) = config::save_config(&app.config) {
+        app.event_tx
+            .send(AppEvent::Error(format!("Failed to save config: {}", e)))?;
+    }
+
+    let message = if enabled { "Auto-DJ on" } else { "Auto-DJ off" };
+    app.status.set_message(Some(message.to_string()));
+
+    top_up_queue(app)
+}
+
+pub(super) fn handle_auto_dj_picks(app: &mut App, picks: Vec<AutoDjPick>) -> Result<()> {
+    app.auto_dj.add_picks(&picks);
+
+    if picks.is_empty() {
+        app.status
+            .set_message(Some("Auto-DJ found nothing to add".to_string()));
+        return Ok(());
+    }
+
+    let message = format!("Auto-DJ added {} tracks", picks.len());
+    let first_idx = app.queue.tracks().lock().unwrap().len();
+    app.queue
+        .add_tracks(picks.into_iter().map(|pick| pick.track).collect());
+    app.status.set_message(Some(message));
+
+    // The queue may have run out while waiting for the picks
+    if app.play_mode == PlayMode::Playlist
+        && app.now_playing.is_none()
+        && app.queue.current_index().is_none()
+        && let Some(track) = app.queue.play_entry(first_idx)
+    {
+        app.task_tx.send(AppTask::PlayTrack(track))?;
+    }
+
+    Ok(())
+}
+
+/// Asks the Auto-DJ for more tracks if it is enabled and the queue is about to
+/// run out.
+fn top_up_queue(app: &mut App) -> Result<()> {
+    if app.play_mode != PlayMode::Playlist || !app.auto_dj.needs_tracks(app.queue.remaining_count()) {
+        return Ok(());
+    }
+
+    let request = AutoDjRequest {
+        recent: app.queue.recent(auto_dj::RECENT_PLAYS),
+        queued: app.queue.durable_ids(),
+        artist_gap: app.config.auto_dj_artist_gap,
+        replay_gap_secs: app.config.auto_dj_replay_gap_secs,
+    };
+    app.task_tx.send(AppTask::AutoDj(request))?;
+    app.auto_dj.request_sent();
+
+    Ok(())
+}
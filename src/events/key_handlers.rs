@@ -31,7 +31,14 @@
 
 use anyhow::Result;
 
-use crate::{App, MainView, browser::MediaBrowserPane, events::AppEvent, tasks::AppTask};
+use crate::{
+    App, MainView,
+    browser::{BrowseHierarchy, MediaBrowserPane},
+    events::AppEvent,
+    tasks::AppTask,
+};
+
+use super::handlers::refresh_browser;
 
 pub(super) fn exit_application(app: &mut App) -> Result<()> {
     app.event_tx.send(AppEvent::ExitApplication)?;
@@ -43,8 +50,44 @@ pub(super) fn set_view(app: &mut App, view: MainView) -> Result<()> {
     Ok(())
 }
 
+pub(super) fn next_browse_hierarchy(app: &mut App) -> Result<()> {
+    app.media_browser.next_hierarchy();
+    refresh_browser(app)
+}
+
 pub(super) fn move_selection(app: &mut App, direction: i8) -> Result<()> {
     match app.media_browser.active_pane {
+        MediaBrowserPane::Genre => {
+            if direction > 0 {
+                app.media_browser.next_genre()
+            } else {
+                app.media_browser.previous_genre()
+            };
+            if let Some(id) = app.media_browser.selected_genre_id() {
+                app.event_tx.send(AppEvent::GenreSelectionChanged(id))?;
+            }
+        }
+        MediaBrowserPane::Decade => {
+            if direction > 0 {
+                app.media_browser.next_decade()
+            } else {
+                app.media_browser.previous_decade()
+            };
+            if let Some(decade) = app.media_browser.selected_decade() {
+                app.event_tx
+                    .send(AppEvent::DecadeSelectionChanged(decade))?;
+            }
+        }
+        MediaBrowserPane::Year => {
+            if direction > 0 {
+                app.media_browser.next_year()
+            } else {
+                app.media_browser.previous_year()
+            };
+            if let Some(year) = app.media_browser.selected_year() {
+                app.event_tx.send(AppEvent::YearSelectionChanged(year))?;
+            }
+        }
         MediaBrowserPane::Artist => {
             if direction > 0 {
                 app.media_browser.next_artist()
@@ -80,19 +123,25 @@ pub(super) fn move_selection(app: &mut App, direction: i8) -> Result<()> {
 }
 
 pub(super) fn add_selected_to_queue(app: &mut App) -> Result<()> {
-    let task = match app.media_browser.active_pane {
-        MediaBrowserPane::Artist => app
-            .media_browser
-            .selected_artist_id()
-            .map(AppTask::AddArtistToQueue),
-        MediaBrowserPane::Album => app
-            .media_browser
-            .selected_album_id()
-            .map(AppTask::AddAlbumToQueue),
-        MediaBrowserPane::Track => app
-            .media_browser
-            .selected_track_id()
-            .map(AppTask::AddTrackToQueue),
+    let browser = &app.media_browser;
+    let task = match browser.active_pane {
+        MediaBrowserPane::Genre => browser
+            .selected_genre_id()
+            .map(|genre_id| AppTask::AddGenreToQueue(genre_id, None)),
+        MediaBrowserPane::Decade => browser
+            .selected_decade()
+            .map(|decade| AppTask::AddYearsToQueue(decade, decade + 9)),
+        MediaBrowserPane::Year => browser
+            .selected_year()
+            .map(|year| AppTask::AddYearsToQueue(year, year)),
+        // Browsing by genre, only the artist's tracks in that genre are added
+        MediaBrowserPane::Artist if browser.hierarchy == BrowseHierarchy::Genre => browser
+            .selected_genre_id()
+            .zip(browser.selected_artist_id())
+            .map(|(genre_id, artist_id)| AppTask::AddGenreToQueue(genre_id, Some(artist_id))),
+        MediaBrowserPane::Artist => browser.selected_artist_id().map(AppTask::AddArtistToQueue),
+        MediaBrowserPane::Album => browser.selected_album_id().map(AppTask::AddAlbumToQueue),
+        MediaBrowserPane::Track => browser.selected_track_id().map(AppTask::AddTrackToQueue),
     };
     if let Some(t) = task {
         app.task_tx.send(t)?;
@@ -102,5 +151,4 @@ pub(super) fn add_selected_to_queue(app: &mut App) -> Result<()> {
 
 pub(super) fn clear_queue(app: &mut App) {
     app.queue.clear();
-    app.current_queue_idx = None;
 }
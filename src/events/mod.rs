@@ -39,12 +39,23 @@ use std::io::Stdout;
 use std::time::Duration;
 
 use anyhow::Result;
-use crossterm::event::{Event, KeyCode, KeyEvent};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{Terminal, prelude::CrosstermBackend};
 
 use crate::{
     App, MainView, RepeatMode,
-    model::{Album, Artist, SearchQuery, Track, TrackInfo},
+    model::{
+        Album, Artist, Bookmark, Episode, Genre, PlayRecord, Rating, SearchQuery, StarRating,
+        Station, Track, TrackInfo,
+        auto_dj::AutoDjPick,
+        equalizer::ChannelMix,
+        lyrics::Lyrics,
+        organise::FileMove,
+        queue::ShuffleMode,
+        sleep::{SleepAction, SleepTarget},
+        stats::LibraryStats,
+        tags::TrackTags,
+    },
     player::PlayerState,
     render::draw,
 };
@@ -54,14 +65,19 @@ pub(crate) enum AppEvent {
     Key(KeyEvent),
 
     Catalog(CatalogEvent),
+    Organise(OrganiseEvent),
+    ApplyOrganise,
     CatalogUpdated,
 
     SetMainView(MainView),
 
-    PlayTrack(TrackInfo),
+    PlayTrack(TrackInfo, Duration),
     PlayPlaylist,
     ShuffleQueue,
     ResetQueue,
+    SetShuffleMode(ShuffleMode),
+    SetAutoDj(bool),
+    AutoDjPicks(Vec<AutoDjPick>),
 
     AddTracksToPlaylist(Vec<TrackInfo>),
     AddSelectionToPlaylist,
@@ -72,7 +88,13 @@ pub(crate) enum AppEvent {
     ArtistSelectionChanged(i32),
     AlbumSelectionChanged(i32),
     TrackSelectionChanged(i32),
+    GenreSelectionChanged(i32),
+    DecadeSelectionChanged(i64),
+    YearSelectionChanged(i64),
 
+    SetBrowserGenres(Vec<Genre>),
+    SetBrowserDecades(Vec<i64>),
+    SetBrowserYears(Vec<i64>),
     SetBrowserArtists(Vec<Artist>),
     SetBrowserAlbums(Vec<Album>),
     SetBrowserTracks(Vec<Track>),
@@ -100,6 +122,7 @@ pub(crate) enum AppEvent {
     TrackFinished,
 
     Tick,
+    TerminalResized,
 
     ExitApplication,
 
@@ -116,14 +139,40 @@ pub(crate) enum AppEvent {
     AddSelectedArtistToQueue,
     AddSelectedAlbumToQueue,
     AddSelectedTrackToQueue,
+    RateSelectedStars(Option<StarRating>),
 
     ClearQueue,
+    PlayQueueEntry(usize),
+    PlayQueueEntriesNext(Vec<usize>),
+    MoveQueueEntries(Vec<usize>, i8),
+    RemoveQueueEntries(Vec<usize>),
+    UndoQueue,
+    RedoQueue,
 
     SetRepeatMode(RepeatMode),
 
+    SetSleepTimer(SleepTarget, SleepAction),
+    CancelSleepTimer,
+
+    BookmarksReady(Vec<Bookmark>),
+    AddBookmark(Option<String>),
+    JumpToBookmark(Bookmark),
+    NextBookmark,
+    PreviousBookmark,
+    HistoryReady(Vec<PlayRecord>),
+    FavouritesReady(Rating, Vec<TrackInfo>),
+    TagsReady(Vec<TrackInfo>, Vec<TrackTags>),
+    LyricsReady(i32, Option<Lyrics>),
+    StatsReady(LibraryStats),
+    StationsReady(Vec<Station>),
+    PlayStation(Station),
+    EpisodesReady(Vec<Episode>),
+
     TrackUpdated(TrackInfo),
+    TracksUpdated(Vec<TrackInfo>),
     ResetEqualizer,
     UpdateEqualizerAmp(usize, f64),
+    SetChannelMix(ChannelMix),
 }
 
 #[derive(Debug)]
@@ -135,6 +184,15 @@ pub(crate) enum CatalogEvent {
     Finished(i64),
 }
 
+#[derive(Debug)]
+pub(crate) enum OrganiseEvent {
+    Planned(Vec<FileMove>),
+    Started,
+    Progress(usize),
+    Failed(usize, String),
+    Finished(usize),
+}
+
 /// Runs the main application loop, handling events and rendering the UI in the
 /// terminal.
 ///
@@ -152,15 +210,20 @@ pub(crate) fn process_events(
         match event {
             AppEvent::Key(key) => process_key_event(app, key)?,
             AppEvent::Catalog(catalog_event) => handle_catalog_event(app, catalog_event),
-            AppEvent::CatalogUpdated => handle_catalog_updated(app),
-            AppEvent::SetMainView(view) => handle_set_main_view(app, view),
+            AppEvent::Organise(organise_event) => handle_organise_event(app, organise_event),
+            AppEvent::ApplyOrganise => handle_apply_organise(app)?,
+            AppEvent::CatalogUpdated => handle_catalog_updated(app)?,
+            AppEvent::SetMainView(view) => handle_set_main_view(app, view)?,
             AppEvent::NewSearchQuery(q) => handle_new_search_query(app, q)?,
             AppEvent::SearchResultsReady(res) => handle_search_results_ready(app, res)?,
             AppEvent::AddSelectionToPlaylist => handle_add_selection_to_playlist(app),
-            AppEvent::PlayTrack(track) => handle_play_track(app, track)?,
+            AppEvent::PlayTrack(track, start) => handle_play_track(app, track, start)?,
             AppEvent::PlayPlaylist => handle_play_playlist(app)?,
             AppEvent::ShuffleQueue => handle_shuffle_queue(app)?,
             AppEvent::ResetQueue => handle_reset_queue(app)?,
+            AppEvent::SetShuffleMode(mode) => handle_set_shuffle_mode(app, mode),
+            AppEvent::SetAutoDj(enabled) => handle_set_auto_dj(app, enabled)?,
+            AppEvent::AutoDjPicks(picks) => handle_auto_dj_picks(app, picks)?,
             AppEvent::AddTracksToPlaylist(tracks) => handle_add_tracks_to_playlist(app, tracks)?,
             AppEvent::ArtistSelectionChanged(id) => handle_artist_selection_changed(app, id)?,
             AppEvent::AlbumSelectionChanged(id) => handle_album_selection_changed(app, id)?,
@@ -168,7 +231,7 @@ pub(crate) fn process_events(
             AppEvent::Play => app.audio_player.play()?,
             AppEvent::Pause => app.audio_player.pause()?,
             AppEvent::TogglePause => app.audio_player.toggle_pause()?,
-            AppEvent::StopPlayback => app.audio_player.stop()?,
+            AppEvent::StopPlayback => handle_stop_playback(app)?,
             AppEvent::NextTrack => handle_next_track(app)?,
             AppEvent::PreviousTrack => handle_previous_track(app)?,
             AppEvent::Seek(pos) => app.audio_player.seek_absolute(pos)?,
@@ -182,6 +245,14 @@ pub(crate) fn process_events(
             AppEvent::ToggleMute => {
                 app.audio_player.toggle_mute()?;
             }
+            AppEvent::GenreSelectionChanged(id) => handle_genre_selection_changed(app, id)?,
+            AppEvent::DecadeSelectionChanged(decade) => {
+                handle_decade_selection_changed(app, decade)?
+            }
+            AppEvent::YearSelectionChanged(year) => handle_year_selection_changed(app, year)?,
+            AppEvent::SetBrowserGenres(genres) => handle_set_browser_genres(app, genres)?,
+            AppEvent::SetBrowserDecades(decades) => handle_set_browser_decades(app, decades)?,
+            AppEvent::SetBrowserYears(years) => handle_set_browser_years(app, years)?,
             AppEvent::SetBrowserArtists(artists) => handle_set_browser_artists(app, artists)?,
             AppEvent::SetBrowserAlbums(albums) => handle_set_browser_albums(app, albums)?,
             AppEvent::SetBrowserTracks(tracks) => handle_set_browser_tracks(app, tracks)?,
@@ -191,21 +262,50 @@ pub(crate) fn process_events(
             AppEvent::DurationChanged(duration) => handle_duration_changed(app, duration),
             AppEvent::VolumeChanged(volume) => handle_volume_changed(app, volume),
             AppEvent::TrackFinished => handle_track_finished(app)?,
-            AppEvent::TimeChanged(secs) => handle_time_changed(app, secs),
+            AppEvent::TimeChanged(secs) => handle_time_changed(app, secs)?,
             AppEvent::FindSelectedArtist => handle_find_selected_artist(app)?,
             AppEvent::FindSelectedAlbum => handle_find_selected_album(app)?,
             AppEvent::AddSelectedArtistToQueue => handle_add_selected_artist_to_queue(app),
             AppEvent::AddSelectedAlbumToQueue => handle_add_selected_album_to_queue(app),
             AppEvent::AddSelectedTrackToQueue => handle_add_selected_track_to_queue(app),
+            AppEvent::RateSelectedStars(stars) => handle_rate_selected_stars(app, stars)?,
             AppEvent::ClearQueue => handle_clear_queue(app),
+            AppEvent::PlayQueueEntry(row) => handle_play_queue_entry(app, row)?,
+            AppEvent::PlayQueueEntriesNext(rows) => handle_play_queue_entries_next(app, rows),
+            AppEvent::MoveQueueEntries(rows, direction) => {
+                handle_move_queue_entries(app, rows, direction)
+            }
+            AppEvent::RemoveQueueEntries(rows) => handle_remove_queue_entries(app, rows),
+            AppEvent::UndoQueue => handle_undo_queue(app),
+            AppEvent::RedoQueue => handle_redo_queue(app),
+            AppEvent::Error(message) => handle_error(app, message),
             AppEvent::SetRepeatMode(mode) => handle_set_repeat_mode(app, mode),
+            AppEvent::SetSleepTimer(target, action) => handle_set_sleep_timer(app, target, action)?,
+            AppEvent::CancelSleepTimer => handle_cancel_sleep_timer(app)?,
+            AppEvent::BookmarksReady(bookmarks) => handle_bookmarks_ready(app, bookmarks),
+            AppEvent::AddBookmark(name) => handle_add_bookmark(app, name)?,
+            AppEvent::JumpToBookmark(bookmark) => handle_jump_to_bookmark(app, bookmark)?,
+            AppEvent::NextBookmark => handle_next_bookmark(app)?,
+            AppEvent::PreviousBookmark => handle_previous_bookmark(app)?,
+            AppEvent::HistoryReady(records) => handle_history_ready(app, records),
+            AppEvent::StatsReady(stats) => handle_stats_ready(app, stats),
+            AppEvent::StationsReady(stations) => handle_stations_ready(app, stations),
+            AppEvent::PlayStation(station) => handle_play_station(app, station)?,
+            AppEvent::EpisodesReady(episodes) => handle_episodes_ready(app, episodes),
+            AppEvent::FavouritesReady(rating, tracks) => handle_favourites_ready(app, rating, tracks),
+            AppEvent::TagsReady(tracks, tags) => handle_tags_ready(app, tracks, tags),
+            AppEvent::LyricsReady(track_id, lyrics) => handle_lyrics_ready(app, track_id, lyrics),
             AppEvent::TrackUpdated(track) => handle_track_updated(app, track),
+            AppEvent::TracksUpdated(tracks) => handle_tracks_updated(app, tracks),
             AppEvent::ResetEqualizer => handle_reset_equalizer(app)?,
             AppEvent::UpdateEqualizerAmp(index, value) => handle_update_equalizer_amp(app, index, value)?,
-            AppEvent::Tick | _ => handle_tick(app),
+            AppEvent::SetChannelMix(channel_mix) => handle_set_channel_mix(app, channel_mix)?,
+            AppEvent::TerminalResized => handle_terminal_resized(app),
+            AppEvent::Tick | _ => handle_tick(app)?,
         }
 
-        terminal.draw(|f| draw(f, app))?;
+        let frame = terminal.draw(|f| draw(f, app))?;
+        app.cover_art.flush(frame.buffer)?;
     }
     Ok(())
 }
@@ -232,7 +332,16 @@ pub(crate) fn process_events(
 /// Returns an error if a command fails to send to a background worker or if
 /// a requested action cannot be executed.
 pub(super) fn process_key_event(app: &mut App, key: KeyEvent) -> Result<()> {
+    // Any key press dismisses the last status message
+    app.status.set_message(None);
+
     let event = Event::Key(key);
+
+    // The tag editor is a popup, so it takes all keys while it is open
+    if app.tag_editor.is_active {
+        return app.tag_editor.process_event(&event, &app.task_tx);
+    }
+
     let handled = app
         .commander
         .handle_event(event.clone(), &mut app.task_tx, &mut app.event_tx);
@@ -252,12 +361,57 @@ pub(super) fn process_key_event(app: &mut App, key: KeyEvent) -> Result<()> {
             .process_event(event, &app.task_tx, &app.event_tx)?; // FIXME why not &event for all of these?
     }
 
+    if app.favourites_view.is_active {
+        let event = Event::Key(key);
+        app.favourites_view
+            .process_event(&event, &app.task_tx, &app.event_tx)?;
+    }
+
     if app.equalizer_view.is_active {
         let event = Event::Key(key);
         app.equalizer_view
             .process_event(&event, &app.task_tx, &app.event_tx, &app.equalizer)?;
     }
 
+    if app.bookmarks_view.is_active {
+        let event = Event::Key(key);
+        app.bookmarks_view
+            .process_event(&event, &app.task_tx, &app.event_tx)?;
+    }
+
+    if app.history_view.is_active {
+        let event = Event::Key(key);
+        app.history_view.process_event(&event, &app.event_tx)?;
+    }
+
+    if app.stats_view.is_active {
+        let event = Event::Key(key);
+        app.stats_view.process_event(&event, &app.task_tx)?;
+    }
+
+    if app.stations_view.is_active {
+        let event = Event::Key(key);
+        app.stations_view
+            .process_event(&event, &app.task_tx, &app.event_tx)?;
+    }
+
+    if app.podcasts_view.is_active {
+        let event = Event::Key(key);
+        app.podcasts_view
+            .process_event(&event, &app.task_tx, &app.event_tx)?;
+    }
+
+    if app.organise_view.is_active {
+        let event = Event::Key(key);
+        app.organise_view.process_event(&event);
+    }
+
+    if app.lyrics_view.is_active {
+        let event = Event::Key(key);
+        app.lyrics_view
+            .process_event(&event, app.lyrics.as_ref(), &app.event_tx)?;
+    }
+
     process_global_key_event(app, key)?;
 
     Ok(())
@@ -275,12 +429,18 @@ fn process_global_key_event(app: &mut App, key: KeyEvent) -> Result<()> {
         (KeyCode::Char('4'), _) => set_view(app, MainView::Browse)?,
         (KeyCode::Char('5'), _) => set_view(app, MainView::Equalizer)?,
         (KeyCode::Char('6'), _) => set_view(app, MainView::Catalog)?,
+        (KeyCode::Char('7'), _) => set_view(app, MainView::Bookmarks)?,
+        (KeyCode::Char('8'), _) => set_view(app, MainView::Stations)?,
+        (KeyCode::Char('9'), _) => set_view(app, MainView::Podcasts)?,
 
         // Browser Navigation
         (KeyCode::Char('j'), _) | (KeyCode::Down, _) => move_selection(app, 1)?,
         (KeyCode::Char('k'), _) | (KeyCode::Up, _) => move_selection(app, -1)?,
         (KeyCode::Char('h'), _) | (KeyCode::Left, _) => app.media_browser.previous_pane(),
         (KeyCode::Char('l'), _) | (KeyCode::Right, _) => app.media_browser.next_pane(),
+        (KeyCode::Char('b'), KeyModifiers::NONE) if app.main_view == MainView::Browse => {
+            next_browse_hierarchy(app)?
+        }
 
         // Audio: Seeking
         (KeyCode::Char(','), _) => app.event_tx.send(AppEvent::SeekBy(-5))?,
@@ -306,6 +466,8 @@ fn process_global_key_event(app: &mut App, key: KeyEvent) -> Result<()> {
         (KeyCode::Char('c'), _) => {
             clear_queue(app);
         }
+        (KeyCode::Char('u'), KeyModifiers::NONE) => app.event_tx.send(AppEvent::UndoQueue)?,
+        (KeyCode::Char('r'), KeyModifiers::CONTROL) => app.event_tx.send(AppEvent::RedoQueue)?,
 
         _ => {}
     }
@@ -38,13 +38,17 @@
 mod browser;
 mod commander;
 mod components;
+mod covers;
 mod config;
 mod db;
 mod events;
 mod model;
 mod player;
+mod podcast;
 mod render;
+mod scrobble;
 mod status;
+mod tags;
 mod tasks;
 mod theme;
 mod util;
@@ -68,11 +72,21 @@ use std::{
 use crate::{
     browser::MediaBrowser,
     commander::Commander,
-    components::{CatalogView, EqualizerView, FavouritesView, HelpView, PlaylistView, SearchView},
+    components::{
+        BookmarksView, CatalogView, EqualizerView, FavouritesView, HelpView, HistoryView,
+        LyricsView, OrganiseView, PlaylistView, PodcastsView, SearchView, StationsView, StatsView,
+        TagEditor,
+    },
     config::AppConfig,
     events::{AppEvent, process_events},
-    model::{TrackInfo, catalog::Catalog, equalizer::Equalizer, queue::Queue, search::Search},
+    model::{
+        Station, TrackInfo, auto_dj::AutoDj, bookmarks::Bookmarks, catalog::Catalog,
+        equalizer::Equalizer, favourites::Favourites, history::History, lyrics::Lyrics,
+        organise::Organise, podcasts::Podcasts, queue::Queue, search::Search, sleep::SleepTimer,
+        stations::Stations, stats::LibraryStats,
+    },
     player::{AudioPlayer, PlayerState},
+    render::cover::CoverArt,
     status::Status,
     tasks::AppTask,
     theme::Theme,
@@ -86,6 +100,13 @@ enum MainView {
     Browse,
     Equalizer,
     Catalog,
+    Bookmarks,
+    History,
+    Stats,
+    Stations,
+    Podcasts,
+    Organise,
+    Lyrics,
     Help,
 }
 
@@ -98,7 +119,7 @@ enum PlayMode {
     Playlist,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 enum RepeatMode {
     NoRepeat,
     RepeatOne,
@@ -124,10 +145,19 @@ struct App {
     pub status: Status,
     pub queue: Queue,
     pub search: Search,
+    pub favourites: Favourites,
     pub equalizer: Equalizer,
     pub catalog: Catalog,
+    pub sleep_timer: SleepTimer,
+    pub auto_dj: AutoDj,
+    pub bookmarks: Bookmarks,
+    pub history: History,
+    pub stations: Stations,
+    pub podcasts: Podcasts,
+    pub organise: Organise,
+    pub stats: Option<LibraryStats>,
+    pub lyrics: Option<Lyrics>,
 
-    pub current_queue_idx: Option<usize>,
 
     pub playlist_view: PlaylistView,
     pub search_view: SearchView,
@@ -135,17 +165,28 @@ struct App {
     pub equalizer_view: EqualizerView,
     pub catalog_view: CatalogView,
     pub help_view: HelpView,
+    pub bookmarks_view: BookmarksView,
+    pub history_view: HistoryView,
+    pub stats_view: StatsView,
+    pub stations_view: StationsView,
+    pub podcasts_view: PodcastsView,
+    pub organise_view: OrganiseView,
+    pub lyrics_view: LyricsView,
 
     pub commander: Commander,
+    pub tag_editor: TagEditor,
     pub media_browser: MediaBrowser,
+    pub cover_art: CoverArt,
 
     pub player_state: PlayerState,
     pub now_playing: Option<TrackInfo>,
+    pub now_streaming: Option<Station>,
     pub player_track_name: Option<String>,
     pub player_duration: Option<u64>,
     pub player_time: Option<u64>,
     pub player_position: Option<f64>,
     pub volume: Option<u32>,
+    pub resume_saved_time: Option<u64>,
 }
 
 impl App {
@@ -163,7 +204,33 @@ impl App {
         let search = Search::new();
         let search_tracks = search.tracks();
 
-        let equalizer = Equalizer::new();
+        let favourites = Favourites::new();
+        let favourite_tracks = favourites.tracks();
+
+        let audio_player = AudioPlayer::new(audio_player_event_tx, config.channel_mix)?;
+
+        let equalizer = Equalizer::new(config.channel_mix);
+
+        let sleep_timer = SleepTimer::new(Duration::from_secs(config.sleep_fade_secs));
+
+        let auto_dj = AutoDj::new(config.auto_dj);
+
+        let bookmarks = Bookmarks::new();
+        let bookmarks_list = bookmarks.bookmarks();
+
+        let history = History::new();
+        let history_records = history.records();
+
+        let stations = Stations::new();
+        let stations_list = stations.stations();
+
+        let podcasts = Podcasts::new();
+        let episodes = podcasts.episodes();
+
+        let organise = Organise::new();
+        let file_moves = organise.moves();
+
+        let cover_art = CoverArt::new(util::term::detect_graphics_protocol(&config.cover_protocol));
 
         Ok(Self {
             config,
@@ -174,28 +241,48 @@ impl App {
             task_tx,
             play_mode: PlayMode::PlayOne,
             repeat_mode: RepeatMode::NoRepeat,
-            audio_player: AudioPlayer::new(audio_player_event_tx)?,
+            audio_player,
             status,
             queue,
             search,
+            favourites,
             equalizer,
             catalog: Catalog::new(),
-            current_queue_idx: None,
+            sleep_timer,
+            auto_dj,
+            bookmarks,
+            history,
+            stations,
+            podcasts,
+            organise,
+            stats: None,
+            lyrics: None,
             playlist_view: PlaylistView::new(playlist_tracks),
             search_view: SearchView::new(search_tracks),
-            favourites_view: FavouritesView::new(),
+            favourites_view: FavouritesView::new(favourite_tracks),
             equalizer_view: EqualizerView::new(),
             catalog_view: CatalogView::new(),
             help_view: HelpView::new(),
+            bookmarks_view: BookmarksView::new(bookmarks_list),
+            history_view: HistoryView::new(history_records),
+            stats_view: StatsView::new(),
+            stations_view: StationsView::new(stations_list),
+            podcasts_view: PodcastsView::new(episodes),
+            organise_view: OrganiseView::new(file_moves),
+            lyrics_view: LyricsView::new(),
             commander: Commander::new(),
+            tag_editor: TagEditor::new(),
             media_browser: MediaBrowser::new(),
+            cover_art,
             player_state: PlayerState::Stopped,
             now_playing: None,
+            now_streaming: None,
             player_track_name: None,
             player_duration: None,
             player_time: None,
             player_position: None,
             volume: None,
+            resume_saved_time: None,
         })
     }
 }
@@ -284,12 +371,18 @@ fn run(
     let event_tx = app.event_tx.clone();
     tasks::spawn_task_worker(&app.config, task_rx, event_tx);
 
-    // Spawn a thread to translate raw key events to application events.
+    // Spawn a thread to translate raw key and resize events to application events.
     let tx_keys = app.event_tx.clone();
     thread::spawn(move || {
         loop {
-            if let Ok(event::Event::Key(key)) = event::read() {
-                tx_keys.send(AppEvent::Key(key)).ok();
+            match event::read() {
+                Ok(event::Event::Key(key)) => {
+                    tx_keys.send(AppEvent::Key(key)).ok();
+                }
+                Ok(event::Event::Resize(_, _)) => {
+                    tx_keys.send(AppEvent::TerminalResized).ok();
+                }
+                _ => {}
             }
         }
     });
@@ -306,6 +399,13 @@ fn run(
 
     // Initial trigger to populate the media browser with data from the catalog
     app.task_tx.send(AppTask::GetBrowserArtists).unwrap();
+    app.task_tx.send(AppTask::GetBookmarks).unwrap();
+    app.task_tx.send(AppTask::GetHistory).unwrap();
+    app.task_tx.send(AppTask::GetStations).unwrap();
+    app.task_tx.send(AppTask::GetEpisodes).unwrap();
+
+    // Submit any listens left over from when ListenBrainz could not be reached
+    app.task_tx.send(AppTask::SubmitListens).unwrap();
 
     // Application event loop, process events until the user quits
     process_events(terminal, app)
@@ -0,0 +1,272 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Auto-DJ track selection.
+//!
+//! This module provides state for the Auto-DJ, which tops up the play queue
+//! when it is about to run out, and the rules it uses to choose tracks from
+//! the catalog. Every track it chooses comes with a reason.
+
+use std::collections::{HashMap, HashSet};
+
+use rand::{RngExt, rng};
+
+use crate::model::{Rating, TrackInfo};
+
+/// The queue is topped up once no more than this many entries are left to
+/// play after the current one.
+const LOW_WATER_MARK: usize = 1;
+
+/// Number of tracks added to the queue each time it is topped up.
+const BATCH_SIZE: usize = 5;
+
+/// Number of recently played tracks used to guide the choice of tracks.
+pub(crate) const RECENT_PLAYS: usize = 10;
+
+/// Tracks released within this many years of the recent plays count as the
+/// same era.
+const ERA_YEARS: i64 = 5;
+
+/// Everything the Auto-DJ needs to know to choose tracks.
+#[derive(Debug)]
+pub(crate) struct AutoDjRequest {
+    /// Recently played tracks, oldest first.
+    pub(crate) recent: Vec<TrackInfo>,
+
+    /// Durable ids of the tracks already in the queue.
+    pub(crate) queued: HashSet<i64>,
+
+    /// Number of tracks that must pass before an artist is repeated.
+    pub(crate) artist_gap: usize,
+
+    /// Tracks played within this many seconds are left out of the candidates.
+    pub(crate) replay_gap_secs: u64,
+}
+
+/// A track chosen by the Auto-DJ, with the reason it was chosen.
+#[derive(Debug, Clone)]
+pub(crate) struct AutoDjPick {
+    pub(crate) track: TrackInfo,
+    pub(crate) reason: String,
+}
+
+pub(crate) struct AutoDj {
+    enabled: bool,
+    pending: bool,
+    reasons: HashMap<i64, String>,
+}
+
+impl AutoDj {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            pending: false,
+            reasons: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Whether more tracks should be requested, given the number of entries
+    /// left to play in the queue.
+    pub(crate) fn needs_tracks(&self, remaining: usize) -> bool {
+        self.enabled && !self.pending && remaining <= LOW_WATER_MARK
+    }
+
+    pub(crate) fn request_sent(&mut self) {
+        self.pending = true;
+    }
+
+    pub(crate) fn add_picks(&mut self, picks: &[AutoDjPick]) {
+        self.pending = false;
+        self.reasons.extend(
+            picks
+                .iter()
+                .map(|pick| (pick.track.durable_id, pick.reason.clone())),
+        );
+    }
+
+    /// Why a track was chosen, if it was chosen by the Auto-DJ.
+    pub(crate) fn reason(&self, durable_id: i64) -> Option<&str> {
+        self.reasons.get(&durable_id).map(String::as_str)
+    }
+}
+
+/// Chooses tracks to add to the queue from a set of candidates.
+///
+/// Candidates score points for sharing a genre with the recent plays, for
+/// being from the same era, for being liked and for never having been played,
+/// with a little randomness so that the same tracks are not chosen every
+/// time. Tracks already in the queue are never chosen, nor is an artist
+/// repeated within the configured number of tracks.
+pub(crate) fn pick_tracks(request: &AutoDjRequest, candidates: Vec<TrackInfo>) -> Vec<AutoDjPick> {
+    let mut rng = rng();
+
+    let genres: HashSet<String> = request
+        .recent
+        .iter()
+        .filter_map(|track| track.genre.as_deref())
+        .map(str::to_lowercase)
+        .collect();
+
+    let years: Vec<i64> = request
+        .recent
+        .iter()
+        .filter_map(|track| track.year)
+        .collect();
+    let era = (!years.is_empty()).then(|| years.iter().sum::<i64>() / years.len() as i64);
+
+    let mut scored: Vec<(f64, AutoDjPick)> = candidates
+        .into_iter()
+        .filter(|track| !request.queued.contains(&track.durable_id))
+        .filter(|track| track.rating != Rating::Dislike)
+        .map(|track| {
+            let mut score = rng.random::<f64>() * 1.5;
+            let mut reasons = Vec::new();
+
+            if let Some(genre) = &track.genre
+                && genres.contains(&genre.to_lowercase())
+            {
+                score += 2.0;
+                reasons.push(format!("same genre ({})", genre));
+            }
+
+            if let (Some(era), Some(year)) = (era, track.year)
+                && (year - era).abs() <= ERA_YEARS
+            {
+                score += 1.0;
+                reasons.push(format!("same era ({}s)", year / 10 * 10));
+            }
+
+            if track.rating == Rating::Like {
+                score += 2.0;
+                reasons.push("liked".to_string());
+            }
+
+            if track.play_count == 0 {
+                score += 0.5;
+                reasons.push("never played".to_string());
+            }
+
+            // Only true if recently played tracks were left out of the
+            // candidates
+            if reasons.is_empty() && request.replay_gap_secs > 0 {
+                reasons.push("not played recently".to_string());
+            }
+
+            if reasons.is_empty() {
+                reasons.push("random pick".to_string());
+            }
+
+            let reason = reasons.join(", ");
+            (score, AutoDjPick { track, reason })
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut artists: Vec<String> = request
+        .recent
+        .iter()
+        .map(|track| track.artist_name.to_lowercase())
+        .collect();
+
+    // Take the best scoring track whose artist has not been heard too
+    // recently, each pick changing which artists that rules out
+    let mut picks = Vec::new();
+    while picks.len() < BATCH_SIZE {
+        let recent_artists = &artists[artists.len().saturating_sub(request.artist_gap)..];
+        let Some(pos) = scored
+            .iter()
+            .position(|(_, pick)| !recent_artists.contains(&pick.track.artist_name.to_lowercase()))
+        else {
+            break;
+        };
+
+        let (_, pick) = scored.remove(pos);
+        artists.push(pick.track.artist_name.to_lowercase());
+        picks.push(pick);
+    }
+
+    picks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(id: i32) -> TrackInfo {
+        TrackInfo {
+            track_id: id,
+            durable_id: i64::from(id),
+            track_title: format!("Track {}", id),
+            track_number: id,
+            duration: 100,
+            genre: None,
+            year: None,
+            album_title: "Album".to_string(),
+            artist_name: format!("Artist {}", id),
+            filename: format!("{}.mp3", id),
+            play_count: 1,
+            rating: Rating::Neutral,
+            stars: None,
+            created_at: 0,
+            is_podcast: false,
+            cover_hash: None,
+            disc_number: None,
+            album_artist: None,
+            composer: None,
+        }
+    }
+
+    fn request(replay_gap_secs: u64) -> AutoDjRequest {
+        AutoDjRequest {
+            recent: Vec::new(),
+            queued: HashSet::new(),
+            artist_gap: 0,
+            replay_gap_secs,
+        }
+    }
+
+    #[test]
+    fn not_played_recently_only_given_when_checked() {
+        let picks = pick_tracks(&request(3600), vec![track(1)]);
+        assert_eq!(picks[0].reason, "not played recently");
+
+        let picks = pick_tracks(&request(0), vec![track(1)]);
+        assert_eq!(picks[0].reason, "random pick");
+    }
+
+    #[test]
+    fn queued_and_disliked_tracks_are_not_picked() {
+        let mut request = request(0);
+        request.queued.insert(1);
+        let disliked = TrackInfo {
+            rating: Rating::Dislike,
+            ..track(2)
+        };
+
+        let picks = pick_tracks(&request, vec![track(1), disliked, track(3)]);
+
+        let ids: Vec<i32> = picks.iter().map(|pick| pick.track.track_id).collect();
+        assert_eq!(ids, [3]);
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Track bookmark management.
+//!
+//! This module provides state for the bookmarks view, managing the list of
+//! named positions saved within tracks.
+
+use std::sync::{Arc, Mutex};
+
+use crate::model::Bookmark;
+
+pub(crate) struct Bookmarks {
+    bookmarks: Arc<Mutex<Vec<Bookmark>>>,
+}
+
+impl Bookmarks {
+    pub(crate) fn new() -> Self {
+        Self {
+            bookmarks: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    pub(crate) fn set_bookmarks(&mut self, bookmarks: Vec<Bookmark>) {
+        let mut lock = self.bookmarks.lock().unwrap();
+        *lock = bookmarks;
+    }
+
+    pub(crate) fn bookmarks(&self) -> Arc<Mutex<Vec<Bookmark>>> {
+        Arc::clone(&self.bookmarks)
+    }
+
+    /// Finds the first bookmark in a track after the given position.
+    pub(crate) fn next_in_track(&self, durable_id: i64, position: u64) -> Option<Bookmark> {
+        let bookmarks = self.bookmarks.lock().unwrap();
+        bookmarks
+            .iter()
+            .filter(|b| b.track.durable_id == durable_id && b.position > position)
+            .min_by_key(|b| b.position)
+            .cloned()
+    }
+
+    /// Finds the last bookmark in a track before the given position.
+    pub(crate) fn previous_in_track(&self, durable_id: i64, position: u64) -> Option<Bookmark> {
+        let bookmarks = self.bookmarks.lock().unwrap();
+        bookmarks
+            .iter()
+            .filter(|b| b.track.durable_id == durable_id && b.position < position)
+            .max_by_key(|b| b.position)
+            .cloned()
+    }
+}
@@ -1,12 +1,45 @@
 use std::sync::{Arc, Mutex};
 
+use serde::{Deserialize, Serialize};
+
 const BANDS: usize = 18;
 
 const MIN_AMP: f64 = -20.0;
 const MAX_AMP: f64 = 20.0;
 
+pub(crate) const MIN_BALANCE: i32 = -100;
+pub(crate) const MAX_BALANCE: i32 = 100;
+
 pub(crate) struct Equalizer {
     pub(crate) amps: Arc<Mutex<Amps>>,
+    pub(crate) channel_mix: ChannelMix,
+}
+
+/// Channel-level mixing applied after the equalizer.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(default)]
+pub struct ChannelMix {
+    /// Stereo balance, from -100 (left only) through 0 (centre) to 100 (right
+    /// only).
+    pub balance: i32,
+
+    /// Downmix both channels to mono.
+    pub mono: bool,
+
+    /// Swap the left and right channels.
+    pub swap: bool,
+}
+
+impl ChannelMix {
+    /// The gain to apply to each of the left and right channels for the
+    /// current balance.
+    ///
+    /// The favoured channel stays at full volume while the other is
+    /// attenuated, so centring the balance never makes anything louder.
+    pub(crate) fn balance_gains(&self) -> (f32, f32) {
+        let balance = self.balance.clamp(MIN_BALANCE, MAX_BALANCE) as f32 / MAX_BALANCE as f32;
+        ((1.0 - balance).min(1.0), (1.0 + balance).min(1.0))
+    }
 }
 
 pub(crate) struct Amps {
@@ -15,12 +48,13 @@ pub(crate) struct Amps {
 }
 
 impl Equalizer {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(channel_mix: ChannelMix) -> Self {
         Self {
             amps: Arc::new(Mutex::new(Amps {
                 preamp: 0.0,
                 gains: [0.0; BANDS],
             })),
+            channel_mix,
         }
     }
 
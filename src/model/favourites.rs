@@ -0,0 +1,70 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Favourite tracks management.
+//!
+//! This module provides state for the favourites view, managing the list of
+//! tracks that have been given a particular rating. Normally these are the
+//! liked tracks, but disliked tracks can be listed instead to clean them up.
+
+use std::sync::{Arc, Mutex};
+
+use crate::model::{Rating, TrackInfo};
+
+pub(crate) struct Favourites {
+    tracks: Arc<Mutex<Vec<TrackInfo>>>,
+    rating: Rating,
+}
+
+impl Favourites {
+    pub(crate) fn new() -> Self {
+        Self {
+            tracks: Arc::new(Mutex::new(vec![])),
+            rating: Rating::Like,
+        }
+    }
+
+    pub(crate) fn set_tracks(&mut self, rating: Rating, tracks: Vec<TrackInfo>) {
+        self.rating = rating;
+        let mut lock = self.tracks.lock().unwrap();
+        *lock = tracks;
+    }
+
+    pub(crate) fn tracks(&self) -> Arc<Mutex<Vec<TrackInfo>>> {
+        Arc::clone(&self.tracks)
+    }
+
+    /// The rating shared by every listed track.
+    pub(crate) fn rating(&self) -> Rating {
+        self.rating
+    }
+
+    /// Updates a listed track, dropping it from the list if it no longer has
+    /// the listed rating.
+    ///
+    /// Tracks that have only just been given the listed rating are not added,
+    /// they appear the next time the list is fetched.
+    pub(crate) fn track_updated(&self, track: &TrackInfo) {
+        let mut tracks = self.tracks.lock().unwrap();
+
+        if track.rating != self.rating {
+            tracks.retain(|t| t.track_id != track.track_id);
+        } else if let Some(existing_track) =
+            tracks.iter_mut().find(|t| t.track_id == track.track_id)
+        {
+            *existing_track = track.clone();
+        }
+    }
+}
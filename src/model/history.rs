@@ -0,0 +1,226 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Play history.
+//!
+//! This module provides state for the history view, and tracks how much of
+//! the currently playing track has actually been listened to. This decides
+//! when the track has played for long enough to count as a play, and is
+//! recorded in the history when the track finishes or is skipped.
+
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    model::{PlayRecord, TrackInfo},
+    util::date::now_timestamp,
+};
+
+/// Player time updates further apart than this are treated as seeks rather
+/// than as time spent listening.
+const MAX_TIME_STEP_SECS: f64 = 2.0;
+
+/// A track that is currently being listened to.
+#[derive(Debug)]
+pub(crate) struct PlaySession {
+    pub(crate) track: TrackInfo,
+    pub(crate) started_at: i64,
+    played_secs: f64,
+    last_time: Option<f64>,
+    /// Seconds of listening needed for this to count as a play.
+    threshold_secs: f64,
+    counted: bool,
+}
+
+impl PlaySession {
+    /// The number of whole seconds actually listened to so far.
+    pub(crate) fn played_secs(&self) -> u64 {
+        self.played_secs as u64
+    }
+
+    /// Returns `true` if the track has played for long enough to count as a
+    /// play.
+    pub(crate) fn is_counted(&self) -> bool {
+        self.counted
+    }
+}
+
+pub(crate) struct History {
+    records: Arc<Mutex<Vec<PlayRecord>>>,
+    session: Option<PlaySession>,
+}
+
+impl History {
+    pub(crate) fn new() -> Self {
+        Self {
+            records: Arc::new(Mutex::new(vec![])),
+            session: None,
+        }
+    }
+
+    pub(crate) fn set_records(&mut self, records: Vec<PlayRecord>) {
+        let mut lock = self.records.lock().unwrap();
+        *lock = records;
+    }
+
+    pub(crate) fn records(&self) -> Arc<Mutex<Vec<PlayRecord>>> {
+        Arc::clone(&self.records)
+    }
+
+    /// Starts a new play session for a track.
+    ///
+    /// # Arguments
+    ///
+    /// * `track` - The track being played.
+    /// * `percent` - The percentage of the track that must be listened to for
+    ///   it to count as a play.
+    /// * `max_secs` - The number of seconds of listening that counts as a play
+    ///   regardless of the percentage, and when the duration of the track is
+    ///   not known.
+    pub(crate) fn start(&mut self, track: &TrackInfo, percent: u64, max_secs: u64) {
+        let threshold_secs = match u64::try_from(track.duration) {
+            Ok(duration) if duration > 0 => duration.saturating_mul(percent) / 100,
+            _ => max_secs,
+        }
+        .min(max_secs);
+
+        self.session = Some(PlaySession {
+            track: track.clone(),
+            started_at: now_timestamp(),
+            played_secs: 0.0,
+            last_time: None,
+            threshold_secs: threshold_secs as f64,
+            counted: false,
+        });
+    }
+
+    /// Accumulates listening time from a player time update.
+    ///
+    /// Only small forward steps count, so seeking through a track does not
+    /// add to the time played.
+    ///
+    /// Returns `true` if this update took the track past the point where it
+    /// counts as a play.
+    pub(crate) fn time_changed(&mut self, seconds: f64) -> bool {
+        let Some(session) = &mut self.session else {
+            return false;
+        };
+
+        if let Some(last_time) = session.last_time {
+            let step = seconds - last_time;
+            if step > 0.0 && step < MAX_TIME_STEP_SECS {
+                session.played_secs += step;
+            }
+        }
+        session.last_time = Some(seconds);
+
+        if session.counted || session.played_secs < session.threshold_secs {
+            return false;
+        }
+
+        session.counted = true;
+        true
+    }
+
+    /// Returns `true` if a track is playing that has not yet played for long
+    /// enough to count as a play.
+    pub(crate) fn is_uncounted(&self) -> bool {
+        self.session
+            .as_ref()
+            .is_some_and(|session| !session.counted)
+    }
+
+    /// Ends the current play session, if there is one.
+    pub(crate) fn finish(&mut self) -> Option<PlaySession> {
+        self.session.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::RangeInclusive;
+
+    use super::*;
+    use crate::model::Rating;
+
+    fn track(duration: i64) -> TrackInfo {
+        TrackInfo {
+            track_id: 1,
+            durable_id: 1,
+            track_title: "Track".to_string(),
+            track_number: 1,
+            duration,
+            genre: None,
+            year: None,
+            album_title: "Album".to_string(),
+            artist_name: "Artist".to_string(),
+            filename: "track.mp3".to_string(),
+            play_count: 0,
+            rating: Rating::Neutral,
+            stars: None,
+            created_at: 0,
+            is_podcast: false,
+            cover_hash: None,
+            disc_number: None,
+            album_artist: None,
+            composer: None,
+        }
+    }
+
+    /// Plays the current track in one second steps over `seconds`, returning
+    /// the second at which it counted as a play.
+    fn counted_at(history: &mut History, seconds: RangeInclusive<u32>) -> Option<u32> {
+        seconds.into_iter().find(|&second| history.time_changed(f64::from(second)))
+    }
+
+    #[test]
+    fn counts_after_percentage_of_track() {
+        let mut history = History::new();
+        history.start(&track(100), 50, 240);
+
+        assert_eq!(counted_at(&mut history, 0..=100), Some(50));
+        assert!(!history.is_uncounted());
+    }
+
+    #[test]
+    fn counts_after_max_secs_of_long_track() {
+        let mut history = History::new();
+        history.start(&track(3600), 50, 240);
+
+        assert_eq!(counted_at(&mut history, 0..=3600), Some(240));
+    }
+
+    #[test]
+    fn counts_after_max_secs_when_duration_unknown() {
+        for duration in [-1, 0] {
+            let mut history = History::new();
+            history.start(&track(duration), 50, 240);
+
+            assert_eq!(counted_at(&mut history, 0..=239), None);
+            assert!(history.is_uncounted());
+            assert_eq!(counted_at(&mut history, 240..=300), Some(240));
+        }
+    }
+
+    #[test]
+    fn seeking_does_not_count() {
+        let mut history = History::new();
+        history.start(&track(100), 50, 240);
+
+        assert!(!history.time_changed(0.0));
+        assert!(!history.time_changed(90.0));
+        assert!(history.is_uncounted());
+        assert_eq!(history.finish().unwrap().played_secs(), 0);
+    }
+}
@@ -0,0 +1,255 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//! Song lyrics.
+//!
+//! Lyrics are either plain text, or synchronised, with every line stamped with
+//! the time it is sung at. Synchronised lyrics are usually in the LRC format,
+//! where lines look like `[01:23.45]Some words`.
+
+use std::time::Duration;
+
+/// A line of lyrics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LyricLine {
+    /// When the line is sung, for synchronised lyrics.
+    pub(crate) time: Option<Duration>,
+    pub(crate) text: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Lyrics {
+    lines: Vec<LyricLine>,
+}
+
+impl Lyrics {
+    /// Parses lyrics that may be in the LRC format.
+    ///
+    /// If any line has a timestamp the lyrics are synchronised, and lines
+    /// without one, such as the `[ar:Artist]` ID tags, are dropped. Repeated
+    /// lines may have several timestamps, and an `[offset:N]` tag brings
+    /// every line forward by N milliseconds. Otherwise the lyrics are plain
+    /// text.
+    pub(crate) fn parse(text: &str) -> Self {
+        let mut offset = 0;
+        let mut timed = Vec::new();
+
+        for line in text.lines() {
+            let (times, words) = parse_timestamps(line);
+            if times.is_empty() {
+                if let Some(value) = line
+                    .trim()
+                    .strip_prefix("[offset:")
+                    .and_then(|tag| tag.strip_suffix(']'))
+                {
+                    offset = value.trim().parse().unwrap_or(0);
+                }
+                continue;
+            }
+            for time in times {
+                timed.push((time, words.trim()));
+            }
+        }
+
+        if timed.is_empty() {
+            return Self::plain(text);
+        }
+
+        let mut lines: Vec<LyricLine> = timed
+            .into_iter()
+            .map(|(time, words)| {
+                // Only a negative offset, delaying the lines, can overflow
+                let time = time.checked_sub(offset).unwrap_or(i64::MAX);
+                LyricLine {
+                    time: Some(Duration::from_millis(time.max(0) as u64)),
+                    text: words.to_string(),
+                }
+            })
+            .collect();
+        lines.sort_by_key(|line| line.time);
+
+        Self { lines }
+    }
+
+    /// Creates plain lyrics, without timestamps.
+    pub(crate) fn plain(text: &str) -> Self {
+        let lines = text
+            .trim()
+            .lines()
+            .map(|line| LyricLine {
+                time: None,
+                text: line.trim_end().to_string(),
+            })
+            .collect();
+
+        Self { lines }
+    }
+
+    /// Creates synchronised lyrics from lines and the times, in milliseconds,
+    /// they are sung at.
+    pub(crate) fn synced(lines: impl IntoIterator<Item = (u32, String)>) -> Self {
+        let mut lines: Vec<LyricLine> = lines
+            .into_iter()
+            .map(|(time, text)| LyricLine {
+                time: Some(Duration::from_millis(time.into())),
+                text,
+            })
+            .collect();
+        lines.sort_by_key(|line| line.time);
+
+        Self { lines }
+    }
+
+    pub(crate) fn lines(&self) -> &[LyricLine] {
+        &self.lines
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.lines.iter().all(|line| line.text.trim().is_empty())
+    }
+
+    pub(crate) fn is_synced(&self) -> bool {
+        self.lines.first().is_some_and(|line| line.time.is_some())
+    }
+
+    /// Finds the line being sung at the given time, if the lyrics are
+    /// synchronised and the first line has been reached.
+    pub(crate) fn line_at(&self, time: Duration) -> Option<usize> {
+        if !self.is_synced() {
+            return None;
+        }
+
+        let next = self
+            .lines
+            .partition_point(|line| line.time.is_some_and(|start| start <= time));
+        next.checked_sub(1)
+    }
+
+    /// The words alone, one line to a line, as they are stored for searching.
+    pub(crate) fn text(&self) -> String {
+        self.lines
+            .iter()
+            .map(|line| line.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Splits the leading timestamps, in milliseconds, from a line of LRC.
+fn parse_timestamps(line: &str) -> (Vec<i64>, &str) {
+    let mut times = Vec::new();
+    let mut rest = line.trim_start();
+
+    while let Some(tail) = rest.strip_prefix('[') {
+        let Some(end) = tail.find(']') else {
+            break;
+        };
+        let Some(time) = parse_timestamp(&tail[..end]) else {
+            break;
+        };
+        times.push(time);
+        rest = &tail[end + 1..];
+    }
+
+    (times, rest)
+}
+
+/// Parses a timestamp such as `01:23`, `01:23.45` or `01:23.456` into
+/// milliseconds, or `None` if it is not a timestamp or is too large to
+/// represent.
+fn parse_timestamp(timestamp: &str) -> Option<i64> {
+    let (minutes, seconds) = timestamp.split_once(':')?;
+    let (seconds, fraction) = seconds.split_once(['.', ':']).unwrap_or((seconds, ""));
+
+    let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let is_fraction = fraction.is_empty() || is_number(fraction);
+    if !is_number(minutes) || !is_number(seconds) || !is_fraction {
+        return None;
+    }
+
+    let minutes: i64 = minutes.parse().ok()?;
+    let seconds: i64 = seconds.parse().ok()?;
+
+    // Hundredths are usual, but any precision is allowed
+    let digits = &fraction[..fraction.len().min(3)];
+    let millis = if digits.is_empty() {
+        0
+    } else {
+        digits.parse::<i64>().ok()? * 10_i64.pow(3 - digits.len() as u32)
+    };
+
+    minutes
+        .checked_mul(60)?
+        .checked_add(seconds)?
+        .checked_mul(1000)?
+        .checked_add(millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn times(lyrics: &Lyrics) -> Vec<Option<Duration>> {
+        lyrics.lines.iter().map(|line| line.time).collect()
+    }
+
+    #[test]
+    fn parses_timestamps() {
+        assert_eq!(parse_timestamp("01:23"), Some(83_000));
+        assert_eq!(parse_timestamp("01:23.45"), Some(83_450));
+        assert_eq!(parse_timestamp("01:23.456"), Some(83_456));
+        assert_eq!(parse_timestamp("01:23:4"), Some(83_400));
+        assert_eq!(parse_timestamp("ar:Artist"), None);
+        assert_eq!(parse_timestamp("01:2x"), None);
+    }
+
+    #[test]
+    fn overflowing_timestamp_is_not_a_timestamp() {
+        assert_eq!(parse_timestamp(&format!("{}:00", i64::MAX)), None);
+        assert_eq!(parse_timestamp(&format!("{}:00", i64::MAX / 60)), None);
+        assert_eq!(parse_timestamp(&format!("00:{}", i64::MAX / 1000 + 1)), None);
+        assert!(parse_timestamps(&format!("[{}:00]Words", i64::MAX / 60)).0.is_empty());
+    }
+
+    #[test]
+    fn parses_synchronised_lyrics() {
+        let lyrics = Lyrics::parse("[ar:Artist]\n[00:02.00]Second\n[00:01.00][00:03.00]Chorus");
+
+        assert_eq!(
+            times(&lyrics),
+            [1000, 2000, 3000].map(|millis| Some(Duration::from_millis(millis)))
+        );
+        assert_eq!(lyrics.lines[0].text, "Chorus");
+        assert_eq!(lyrics.lines[1].text, "Second");
+    }
+
+    #[test]
+    fn applies_offset() {
+        let lyrics = Lyrics::parse("[offset:500]\n[00:00.20]First\n[00:01.00]Second");
+        assert_eq!(
+            times(&lyrics),
+            [0, 500].map(|millis| Some(Duration::from_millis(millis)))
+        );
+
+        let lyrics = Lyrics::parse(&format!("[offset:{}]\n[00:01.00]Line", i64::MIN));
+        assert_eq!(times(&lyrics), [Some(Duration::from_millis(i64::MAX as u64))]);
+    }
+
+    #[test]
+    fn lyrics_without_timestamps_are_plain() {
+        let lyrics = Lyrics::parse("First\n[99999999999999999999:00]Second");
+
+        assert_eq!(times(&lyrics), [None, None]);
+    }
+}
@@ -19,10 +19,21 @@
 //! Artists, Albums, and Tracks—representing the underlying data schema used
 //! for metadata management and playback.
 
+pub(crate) mod auto_dj;
+pub(crate) mod bookmarks;
 pub(crate) mod catalog;
 pub(crate) mod equalizer;
+pub(crate) mod favourites;
+pub(crate) mod history;
+pub(crate) mod lyrics;
+pub(crate) mod organise;
+pub(crate) mod podcasts;
 pub(crate) mod queue;
 pub(crate) mod search;
+pub(crate) mod sleep;
+pub(crate) mod stations;
+pub(crate) mod stats;
+pub(crate) mod tags;
 
 #[derive(Debug, Clone)]
 pub struct Artist {
@@ -30,17 +41,26 @@ pub struct Artist {
     pub name: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct Genre {
+    pub id: i32,
+    pub name: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct Album {
     pub id: i32,
     pub title: String,
     pub artist_id: i32,
+    pub cover_hash: Option<String>,
+    pub artist_name: String,
 }
 
 #[derive(Debug, Clone)]
 pub struct Track {
     pub id: i32,
     pub title: String,
+    pub disc_number: Option<i32>,
     pub track_number: i32,
     pub album_id: i32,
     pub filename: String,
@@ -60,16 +80,180 @@ pub struct TrackInfo {
     pub filename: String,
     pub play_count: u32,
     pub rating: Rating,
+    pub stars: Option<StarRating>,
     pub created_at: i64,
+    pub is_podcast: bool,
+    /// Identifies the cached cover thumbnail of the track's album.
+    pub cover_hash: Option<String>,
+    pub disc_number: Option<i32>,
+    /// The album artist as tagged, or else the artist the album is catalogued
+    /// under, which may differ from the artist of the track.
+    pub album_artist: Option<String>,
+    pub composer: Option<String>,
+}
+
+impl TrackInfo {
+    /// Returns `true` if playback of this track should resume from where it
+    /// was last left off, rather than from the start.
+    ///
+    /// Podcast episodes are always resumable.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold_secs` - Tracks at least this long are always resumable.
+    /// * `genres` - Tracks in any of these genres are always resumable.
+    pub(crate) fn is_resumable(&self, threshold_secs: u64, genres: &[String]) -> bool {
+        let long_track = u64::try_from(self.duration).is_ok_and(|d| d >= threshold_secs);
+
+        let resumable_genre = self
+            .genre
+            .as_deref()
+            .is_some_and(|genre| genres.iter().any(|g| g.eq_ignore_ascii_case(genre)));
+
+        self.is_podcast || long_track || resumable_genre
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Bookmark {
+    pub id: i32,
+    pub name: String,
+    pub position: u64,
+    pub track: TrackInfo,
+}
+
+/// A single play of a track, as recorded in the play history.
+#[derive(Debug, Clone)]
+pub struct PlayRecord {
+    pub started_at: i64,
+    pub played_secs: u64,
+    pub skipped: bool,
+    pub track: TrackInfo,
+}
+
+/// A listen waiting to be submitted to ListenBrainz.
+///
+/// The track details are copied, so the listen can still be submitted if the
+/// track is later removed from the catalog.
+#[derive(Debug, Clone)]
+pub struct PendingListen {
+    pub id: i64,
+    pub listened_at: i64,
+    pub artist_name: String,
+    pub track_title: String,
+    pub album_title: String,
+    pub track_number: i32,
+    pub duration: i64,
+}
+
+/// An internet radio station, played from an HTTP audio stream.
+#[derive(Debug, Clone)]
+pub struct Station {
+    pub id: i32,
+    pub name: String,
+    pub url: String,
+}
+
+/// A podcast subscription.
+#[derive(Debug, Clone)]
+pub struct Podcast {
+    pub title: String,
+    pub feed_url: String,
+}
+
+/// A podcast episode, which may or may not have been downloaded.
+#[derive(Debug, Clone)]
+pub struct Episode {
+    pub id: i32,
+    pub podcast_id: i32,
+    pub podcast_title: String,
+    pub feed_url: String,
+    pub guid: String,
+    pub title: String,
+    pub url: String,
+    pub published_at: Option<i64>,
+    /// Set once the episode has been downloaded.
+    pub filename: Option<String>,
+    /// Set once the episode has been added to the catalog.
+    pub durable_id: Option<i64>,
+    pub played: bool,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub(crate) enum Rating {
     Like = 1,
     Neutral = 0,
     Dislike = -1,
 }
 
+/// A rating on a scale of half a star to five stars, held in half stars.
+///
+/// Star ratings are optional, and kept alongside a [`Rating`] that is derived
+/// from them, so that liked tracks are simply those with enough stars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct StarRating(u8);
+
+impl StarRating {
+    pub(crate) const MAX_HALF_STARS: u8 = 10;
+
+    /// Tracks with at least this many half stars are liked.
+    const LIKE_HALF_STARS: u8 = 8;
+
+    /// Tracks with no more than this many half stars are disliked.
+    const DISLIKE_HALF_STARS: u8 = 3;
+
+    /// Returns `None` for zero stars, which means unrated, or if there are
+    /// too many stars.
+    pub(crate) fn from_half_stars(half_stars: u8) -> Option<Self> {
+        (1..=Self::MAX_HALF_STARS)
+            .contains(&half_stars)
+            .then_some(Self(half_stars))
+    }
+
+    /// Converts a rating between 0.0 and 1.0, rounding to the nearest half
+    /// star.
+    pub(crate) fn from_fraction(fraction: f64) -> Option<Self> {
+        if !(0.0..=1.0).contains(&fraction) {
+            return None;
+        }
+        Self::from_half_stars((fraction * f64::from(Self::MAX_HALF_STARS)).round() as u8)
+    }
+
+    pub(crate) fn half_stars(self) -> u8 {
+        self.0
+    }
+
+    /// The rating between 0.0 and 1.0.
+    pub(crate) fn fraction(self) -> f64 {
+        f64::from(self.0) / f64::from(Self::MAX_HALF_STARS)
+    }
+
+    /// The like or dislike implied by the number of stars.
+    pub(crate) fn rating(self) -> Rating {
+        if self.0 >= Self::LIKE_HALF_STARS {
+            Rating::Like
+        } else if self.0 <= Self::DISLIKE_HALF_STARS {
+            Rating::Dislike
+        } else {
+            Rating::Neutral
+        }
+    }
+
+    /// The stars to give a track that is liked or disliked, keeping its
+    /// current stars if they already imply that rating.
+    pub(crate) fn for_rating(rating: Rating, current: Option<StarRating>) -> Option<Self> {
+        if let Some(stars) = current.filter(|stars| stars.rating() == rating) {
+            return Some(stars);
+        }
+
+        match rating {
+            Rating::Like => Some(Self(Self::LIKE_HALF_STARS)),
+            Rating::Neutral => None,
+            Rating::Dislike => Some(Self(2)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum Recency {
     LastDay,
@@ -77,13 +261,64 @@ pub(crate) enum Recency {
     LastMonth,
 }
 
+/// A condition on how often, or how recently, a track has been played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PlayFilter {
+    MorePlaysThan(u32),
+    FewerPlaysThan(u32),
+    MoreSkipsThan(u32),
+    FewerSkipsThan(u32),
+    /// Played within the given number of seconds.
+    PlayedWithin(u64),
+    /// Not played within the given number of seconds, including never played.
+    NotPlayedWithin(u64),
+}
+
+/// The order of search results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum SearchOrder {
+    /// By artist, album and track number.
+    #[default]
+    Library,
+    MostPlayed,
+    MostSkipped,
+    RecentlyPlayed,
+    LeastRecentlyPlayed,
+}
+
+/// The order of the tracks in the favourites view.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum FavouritesOrder {
+    /// Most recently rated first.
+    #[default]
+    RatedAt,
+    /// By artist, album and track number.
+    Artist,
+    /// By album, artist and track number.
+    Album,
+}
+
+impl FavouritesOrder {
+    pub(crate) fn next(self) -> Self {
+        match self {
+            FavouritesOrder::RatedAt => FavouritesOrder::Artist,
+            FavouritesOrder::Artist => FavouritesOrder::Album,
+            FavouritesOrder::Album => FavouritesOrder::RatedAt,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
 pub(crate) struct SearchQuery {
     pub(crate) search: String,
     pub(crate) artist: String,
     pub(crate) album: String,
     pub(crate) track: String,
+    /// Words that must all appear in the lyrics.
+    pub(crate) lyrics: Vec<String>,
     pub(crate) recency: Option<Recency>,
+    pub(crate) play_filters: Vec<PlayFilter>,
+    pub(crate) order: SearchOrder,
 }
 
 impl SearchQuery {
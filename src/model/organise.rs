@@ -0,0 +1,114 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Organising media files.
+//!
+//! This module provides state for renaming and moving catalogued files to the
+//! paths given by a template, listing each planned move and what became of
+//! it.
+
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum OrganiseStatus {
+    Idle,
+    Planned,
+    Moving,
+    Finished,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum MoveStatus {
+    Pending,
+    Moved,
+    /// The move was not attempted, with the reason why.
+    Skipped(String),
+    Failed(String),
+}
+
+/// A catalogued file, and where the template puts it.
+#[derive(Debug, Clone)]
+pub(crate) struct FileMove {
+    pub(crate) track_id: i32,
+    /// The media directory the file is in, which it stays under.
+    pub(crate) root: PathBuf,
+    pub(crate) from: PathBuf,
+    pub(crate) to: PathBuf,
+    pub(crate) status: MoveStatus,
+}
+
+pub(crate) struct Organise {
+    pub(crate) status: OrganiseStatus,
+    moves: Arc<Mutex<Vec<FileMove>>>,
+    moved: usize,
+}
+
+impl Organise {
+    pub(crate) fn new() -> Self {
+        Self {
+            status: OrganiseStatus::Idle,
+            moves: Arc::new(Mutex::new(vec![])),
+            moved: 0,
+        }
+    }
+
+    pub(crate) fn moves(&self) -> Arc<Mutex<Vec<FileMove>>> {
+        Arc::clone(&self.moves)
+    }
+
+    pub(crate) fn moved(&self) -> usize {
+        self.moved
+    }
+
+    pub(crate) fn set_planned(&mut self, moves: Vec<FileMove>) {
+        *self.moves.lock().unwrap() = moves;
+        self.status = OrganiseStatus::Planned;
+        self.moved = 0;
+    }
+
+    /// Returns the planned moves, if they have been previewed and not yet made.
+    pub(crate) fn planned_moves(&self) -> Option<Vec<FileMove>> {
+        (self.status == OrganiseStatus::Planned).then(|| self.moves.lock().unwrap().clone())
+    }
+
+    pub(crate) fn start_moving(&mut self) {
+        self.status = OrganiseStatus::Moving;
+    }
+
+    /// Marks the pending moves before `count` as done, the moves are made in
+    /// order so any of those that failed will already have been marked.
+    pub(crate) fn update_progress(&mut self, count: usize) {
+        let mut moves = self.moves.lock().unwrap();
+        for file_move in moves.iter_mut().take(count) {
+            if file_move.status == MoveStatus::Pending {
+                file_move.status = MoveStatus::Moved;
+            }
+        }
+    }
+
+    pub(crate) fn move_failed(&mut self, index: usize, error: String) {
+        if let Some(file_move) = self.moves.lock().unwrap().get_mut(index) {
+            file_move.status = MoveStatus::Failed(error);
+        }
+    }
+
+    pub(crate) fn finish(&mut self, moved: usize) {
+        self.status = OrganiseStatus::Finished;
+        self.moved = moved;
+    }
+}
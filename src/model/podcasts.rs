@@ -0,0 +1,44 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Podcast episode management.
+//!
+//! This module provides state for the podcasts view, managing the list of
+//! episodes from every subscribed podcast.
+
+use std::sync::{Arc, Mutex};
+
+use crate::model::Episode;
+
+pub(crate) struct Podcasts {
+    episodes: Arc<Mutex<Vec<Episode>>>,
+}
+
+impl Podcasts {
+    pub(crate) fn new() -> Self {
+        Self {
+            episodes: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    pub(crate) fn set_episodes(&mut self, episodes: Vec<Episode>) {
+        let mut lock = self.episodes.lock().unwrap();
+        *lock = episodes;
+    }
+
+    pub(crate) fn episodes(&self) -> Arc<Mutex<Vec<Episode>>> {
+        Arc::clone(&self.episodes)
+    }
+}
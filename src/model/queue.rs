@@ -15,97 +15,388 @@
 
 //! Media browser queue management.
 //!
-//! This module provides state for the media player queue, managing a list of
-//! tracks queued for playback.
+//! This module provides the queue engine for the media player. The queue
+//! keeps its tracks in the order they were added (or arranged by the user),
+//! together with a separate play order and a cursor marking the current
+//! entry. Shuffling only changes the play order, so it can always be turned
+//! off again to play the queue as it stands.
+//!
+//! The queue also keeps a bounded history of edits that can be undone and
+//! redone.
 
 use std::{
-    collections::{HashSet, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
     sync::{Arc, Mutex},
 };
 
-use rand::{rng, seq::SliceRandom};
+use rand::{RngExt, rng, seq::SliceRandom};
+
+use crate::{
+    RepeatMode,
+    model::{Rating, TrackInfo},
+};
+
+/// Maximum number of queue edits that can be undone.
+const HISTORY_LIMIT: usize = 50;
+
+/// How the play order of the queue is arranged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum ShuffleMode {
+    /// Play the queue in order.
+    #[default]
+    Off,
+
+    /// Play the tracks in a random order.
+    Track,
+
+    /// Play the albums in a random order, keeping the tracks of each album
+    /// together.
+    Album,
+
+    /// Play the tracks in a random order that favours liked and less played
+    /// tracks, leaving disliked tracks until last.
+    Weighted,
+}
+
+impl ShuffleMode {
+    pub(crate) fn describe(&self) -> Option<&'static str> {
+        match self {
+            ShuffleMode::Off => None,
+            ShuffleMode::Track => Some("Shuffle"),
+            ShuffleMode::Album => Some("Album Shuffle"),
+            ShuffleMode::Weighted => Some("Weighted Shuffle"),
+        }
+    }
+}
+
+/// An edit to the queue that can be undone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum QueueOperation {
+    Add(usize),
+    Remove(usize),
+    Move,
+    Shuffle,
+    Clear,
+    Reset,
+}
+
+impl fmt::Display for QueueOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = |count: &usize| if *count == 1 { "" } else { "s" };
+
+        match self {
+            QueueOperation::Add(count) => write!(f, "add {} track{}", count, plural(count)),
+            QueueOperation::Remove(count) => {
+                write!(f, "remove {} track{}", count, plural(count))
+            }
+            QueueOperation::Move => write!(f, "move"),
+            QueueOperation::Shuffle => write!(f, "shuffle"),
+            QueueOperation::Clear => write!(f, "clear"),
+            QueueOperation::Reset => write!(f, "reset"),
+        }
+    }
+}
 
-use crate::model::TrackInfo;
+/// The contents of the queue before (or after) an edit.
+struct QueueSnapshot {
+    operation: QueueOperation,
+    entries: Vec<TrackInfo>,
+    order: Vec<usize>,
+    shuffle_mode: ShuffleMode,
+    cursor: Option<usize>,
+}
 
 pub(crate) struct Queue {
     tracks: Arc<Mutex<Vec<TrackInfo>>>,
-    queued: VecDeque<TrackInfo>,
-    played: Vec<TrackInfo>,
+    entries: Vec<TrackInfo>,
+    order: Vec<usize>,
+    shuffle_mode: ShuffleMode,
+    cursor: Option<usize>,
     total_duration: i64,
-    queued_duration: i64,
-    played_duration: i64,
+    undo_stack: VecDeque<QueueSnapshot>,
+    redo_stack: Vec<QueueSnapshot>,
 }
 
 impl Queue {
     pub(crate) fn new() -> Self {
         Self {
             tracks: Arc::new(Mutex::new(vec![])),
-            queued: VecDeque::new(),
-            played: Vec::new(),
+            entries: Vec::new(),
+            order: Vec::new(),
+            shuffle_mode: ShuffleMode::Off,
+            cursor: None,
             total_duration: 0,
-            queued_duration: 0,
-            played_duration: 0,
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
         }
     }
 
     pub(crate) fn add_tracks(&mut self, tracks: Vec<TrackInfo>) {
-        self.queued.extend(tracks);
+        if tracks.is_empty() {
+            return;
+        }
+
+        self.checkpoint(QueueOperation::Add(tracks.len()));
+
+        let start = self.entries.len();
+        self.entries.extend(tracks);
+        self.order.extend(start..self.entries.len());
 
         self.sync_tracks();
     }
 
-    pub(crate) fn remove_tracks(&mut self, track_ids: Vec<i32>) {
-        let ids_to_remove: HashSet<i32> = track_ids.into_iter().collect();
-        self.played
-            .retain(|track| !ids_to_remove.contains(&track.track_id));
-        self.queued
-            .retain(|track| !ids_to_remove.contains(&track.track_id));
+    /// Moves the entries at the given positions one place up or down the
+    /// queue, keeping contiguous runs of entries together.
+    ///
+    /// Returns the new position of each original entry.
+    pub(crate) fn move_entries(&mut self, positions: &[usize], direction: i8) -> Vec<Option<usize>> {
+        self.rearrange(QueueOperation::Move, |entries| {
+            let selected = |entry: &(usize, TrackInfo)| positions.contains(&entry.0);
 
-        self.sync_tracks();
+            if direction < 0 {
+                for i in 1..entries.len() {
+                    if selected(&entries[i]) && !selected(&entries[i - 1]) {
+                        entries.swap(i - 1, i);
+                    }
+                }
+            } else {
+                for i in (1..entries.len()).rev() {
+                    if selected(&entries[i - 1]) && !selected(&entries[i]) {
+                        entries.swap(i - 1, i);
+                    }
+                }
+            }
+        })
+    }
+
+    /// Moves the entries at the given positions so that they play straight
+    /// after the current entry, or first if nothing is playing.
+    ///
+    /// Returns the new position of each original entry.
+    pub(crate) fn play_next(&mut self, positions: &[usize]) -> Vec<Option<usize>> {
+        let after = self.cursor;
+        let moved: Vec<usize> = positions
+            .iter()
+            .copied()
+            .filter(|&pos| pos < self.entries.len() && Some(pos) != after)
+            .collect();
+
+        let new_positions = self.rearrange(QueueOperation::Move, |entries| {
+            let (moved, mut remaining): (Vec<_>, Vec<_>) = entries
+                .drain(..)
+                .partition(|entry| moved.contains(&entry.0));
+
+            let insert_at = after
+                .and_then(|after| remaining.iter().position(|entry| entry.0 == after))
+                .map_or(0, |idx| idx + 1);
+
+            remaining.splice(insert_at..insert_at, moved);
+            *entries = remaining;
+        });
+
+        // The play order follows the queue unless it is shuffled, in which
+        // case the moved entries need to be brought forward separately
+        if self.is_shuffled() {
+            let moved: Vec<usize> = moved.iter().filter_map(|&pos| new_positions[pos]).collect();
+            self.order.retain(|idx| !moved.contains(idx));
+
+            let insert_at = self
+                .cursor
+                .and_then(|cursor| self.order.iter().position(|&idx| idx == cursor))
+                .map_or(0, |pos| pos + 1);
+            self.order.splice(insert_at..insert_at, moved);
+        }
+
+        new_positions
     }
 
+    /// Removes the entries at the given positions.
+    ///
+    /// Returns the new position of each original entry, or `None` for the
+    /// entries that were removed.
+    pub(crate) fn remove_entries(&mut self, positions: &[usize]) -> Vec<Option<usize>> {
+        let removed = positions
+            .iter()
+            .filter(|&&pos| pos < self.entries.len())
+            .count();
+
+        self.rearrange(QueueOperation::Remove(removed), |entries| {
+            entries.retain(|entry| !positions.contains(&entry.0))
+        })
+    }
+
+    pub(crate) fn shuffle_mode(&self) -> ShuffleMode {
+        self.shuffle_mode
+    }
+
+    /// Changes how the play order is arranged, leaving the order of the queue
+    /// itself alone.
+    ///
+    /// Setting a shuffle mode always shuffles the play order again, even if
+    /// the mode has not changed. Turning shuffle off restores the play order
+    /// to the order of the queue.
+    pub(crate) fn set_shuffle_mode(&mut self, shuffle_mode: ShuffleMode) {
+        if shuffle_mode == ShuffleMode::Off && !self.is_shuffled() {
+            return;
+        }
+
+        let operation = match shuffle_mode {
+            ShuffleMode::Off => QueueOperation::Reset,
+            _ => QueueOperation::Shuffle,
+        };
+        self.checkpoint(operation);
+
+        self.shuffle_mode = shuffle_mode;
+        self.order = self.play_order();
+    }
+
+    /// Shuffles the play order again, using track shuffle if shuffle was off.
     pub(crate) fn shuffle(&mut self) {
-        let mut rng = rng();
-        let slice = self.queued.make_contiguous();
-        slice.shuffle(&mut rng);
+        let shuffle_mode = match self.shuffle_mode {
+            ShuffleMode::Off => ShuffleMode::Track,
+            shuffle_mode => shuffle_mode,
+        };
 
-        self.sync_tracks();
+        self.set_shuffle_mode(shuffle_mode);
     }
 
     pub(crate) fn clear(&mut self) {
-        self.queued.clear();
-        self.played.clear();
+        if self.entries.is_empty() {
+            return;
+        }
+
+        self.checkpoint(QueueOperation::Clear);
+
+        self.entries.clear();
+        self.order.clear();
+        self.cursor = None;
 
         self.sync_tracks();
     }
 
-    // hmm...
-    // i think it should re-add from tracks, not this popping played stuff - i don't even know why i did it this way
-    // this doesn't work how i think it works - we don't have a master copy of tracks we can restore in reset
-    pub(crate) fn reset(&mut self) {
-        while let Some(track) = self.played.pop() {
-            self.queued.push_front(track);
-        }
+    /// Reverts the most recent edit to the queue, returning the edit that was
+    /// undone.
+    pub(crate) fn undo(&mut self) -> Option<QueueOperation> {
+        let snapshot = self.undo_stack.pop_back()?;
+        let operation = snapshot.operation;
+
+        let current = self.restore(snapshot);
+        self.redo_stack.push(current);
+
+        Some(operation)
     }
 
-    pub(crate) fn current(&self) -> Option<&TrackInfo> {
-        self.played.last()
+    /// Re-applies the most recently undone edit to the queue, returning the
+    /// edit that was redone.
+    pub(crate) fn redo(&mut self) -> Option<QueueOperation> {
+        let snapshot = self.redo_stack.pop()?;
+        let operation = snapshot.operation;
+
+        let current = self.restore(snapshot);
+        self.undo_stack.push_back(current);
+
+        Some(operation)
     }
 
-    pub(crate) fn next(&mut self) -> Option<&TrackInfo> {
-        if let Some(track) = self.queued.pop_front() {
-            self.played.push(track);
-        }
+    /// Position of the current entry in the queue.
+    pub(crate) fn current_index(&self) -> Option<usize> {
+        self.cursor
+    }
 
-        self.played.last()
+    /// Moves the cursor to the first entry in the play order.
+    pub(crate) fn play_first(&mut self) -> Option<TrackInfo> {
+        self.cursor = self.order.first().copied();
+        self.current()
     }
 
-    pub(crate) fn previous(&mut self) -> Option<&TrackInfo> {
-        if let Some(track) = self.played.pop() {
-            self.queued.push_front(track);
+    /// Moves the cursor to the entry at the given position in the queue.
+    pub(crate) fn play_entry(&mut self, position: usize) -> Option<TrackInfo> {
+        if position >= self.entries.len() {
+            return None;
         }
 
-        self.played.last()
+        self.cursor = Some(position);
+        self.current()
+    }
+
+    /// Advances the cursor to the next entry in the play order.
+    ///
+    /// Returns `None`, clearing the cursor, when the end of the play order is
+    /// reached and the queue does not repeat.
+    pub(crate) fn next(&mut self, repeat_mode: RepeatMode) -> Option<TrackInfo> {
+        let pos = self.cursor_position()?;
+
+        self.cursor = match repeat_mode {
+            RepeatMode::RepeatOne => self.cursor,
+            _ if pos + 1 < self.order.len() => Some(self.order[pos + 1]),
+            RepeatMode::RepeatAll => self.order.first().copied(),
+            RepeatMode::NoRepeat => None,
+        };
+
+        self.current()
+    }
+
+    /// Moves the cursor back to the previous entry in the play order.
+    ///
+    /// At the start of the play order the first entry is played again, unless
+    /// the queue repeats, in which case it wraps around to the last entry.
+    pub(crate) fn previous(&mut self, repeat_mode: RepeatMode) -> Option<TrackInfo> {
+        let pos = self.cursor_position()?;
+
+        self.cursor = match repeat_mode {
+            RepeatMode::RepeatOne => self.cursor,
+            _ if pos > 0 => Some(self.order[pos - 1]),
+            RepeatMode::RepeatAll => self.order.last().copied(),
+            RepeatMode::NoRepeat => self.cursor,
+        };
+
+        self.current()
+    }
+
+    /// Whether there is nothing left to play after the current entry, not
+    /// counting any repeats.
+    pub(crate) fn is_last(&self) -> bool {
+        self.cursor_position()
+            .is_none_or(|pos| pos + 1 >= self.order.len())
+    }
+
+    /// Number of entries that will play after the current one.
+    pub(crate) fn remaining_count(&self) -> usize {
+        self.cursor_position()
+            .map_or(0, |pos| self.order.len() - pos - 1)
+    }
+
+    /// The most recently played entries, oldest first, up to and including
+    /// the current one. If nothing is playing, these are the last entries in
+    /// the play order.
+    pub(crate) fn recent(&self, count: usize) -> Vec<TrackInfo> {
+        let end = self
+            .cursor_position()
+            .map_or(self.order.len(), |pos| pos + 1);
+
+        self.order[end.saturating_sub(count)..end]
+            .iter()
+            .map(|&idx| self.entries[idx].clone())
+            .collect()
+    }
+
+    /// Durable ids of every track in the queue.
+    pub(crate) fn durable_ids(&self) -> HashSet<i64> {
+        self.entries.iter().map(|track| track.durable_id).collect()
+    }
+
+    /// Total duration, in seconds, of the entries that will play after the
+    /// current one.
+    pub(crate) fn remaining_duration(&self) -> i64 {
+        let Some(pos) = self.cursor_position() else {
+            return 0;
+        };
+
+        self.order[pos + 1..]
+            .iter()
+            .map(|&idx| self.entries[idx].duration.max(0))
+            .sum()
     }
 
     pub(crate) fn tracks(&self) -> Arc<Mutex<Vec<TrackInfo>>> {
@@ -116,31 +407,600 @@ impl Queue {
         self.total_duration
     }
 
-    pub(crate) fn queued_duration(&self) -> i64 {
-        self.queued_duration
+    pub(crate) fn track_updated(&mut self, track: &TrackInfo) {
+        for entry in self.entries.iter_mut().filter(|t| t.track_id == track.track_id) {
+            *entry = track.clone();
+        }
+
+        let mut tracks = self.tracks.lock().unwrap();
+
+        for existing_track in tracks.iter_mut().filter(|t| t.track_id == track.track_id) {
+            *existing_track = track.clone();
+        }
     }
 
-    pub(crate) fn played_duration(&self) -> i64 {
-        self.played_duration
+    fn is_shuffled(&self) -> bool {
+        self.shuffle_mode != ShuffleMode::Off
     }
 
-    pub(crate) fn track_updated(&self, track: &TrackInfo) {
-        let mut tracks = self.tracks.lock().unwrap();
+    /// Builds a new play order for the current shuffle mode.
+    ///
+    /// The current entry, if there is one, stays at the head of the play order
+    /// so that every other entry still gets played.
+    fn play_order(&self) -> Vec<usize> {
+        let mut rng = rng();
 
-        if let Some(existing_track) = tracks.iter_mut().find(|t| t.track_id == track.track_id) {
-            *existing_track = track.clone();
+        let mut rest: Vec<usize> = (0..self.entries.len())
+            .filter(|&idx| Some(idx) != self.cursor)
+            .collect();
+
+        let mut order = match self.shuffle_mode {
+            ShuffleMode::Off => return (0..self.entries.len()).collect(),
+
+            ShuffleMode::Track => {
+                rest.shuffle(&mut rng);
+                rest
+            }
+
+            ShuffleMode::Album => {
+                // Keyed on the album artist so that compilations, where the
+                // track artists differ, stay together
+                let album_key = |idx: usize| {
+                    let track = &self.entries[idx];
+                    let artist = track.album_artist.as_deref().unwrap_or(&track.artist_name);
+                    (artist, track.album_title.as_str())
+                };
+
+                let mut albums: Vec<Vec<usize>> = Vec::new();
+                let mut album_idx = HashMap::new();
+                for idx in rest {
+                    let i = *album_idx.entry(album_key(idx)).or_insert_with(|| {
+                        albums.push(Vec::new());
+                        albums.len() - 1
+                    });
+                    albums[i].push(idx);
+                }
+
+                albums.shuffle(&mut rng);
+
+                // The rest of the current album carries on before the others
+                if let Some(cursor) = self.cursor
+                    && let Some(pos) = albums
+                        .iter()
+                        .position(|album| album_key(album[0]) == album_key(cursor))
+                {
+                    let mut album = albums.remove(pos);
+                    album.sort_by_key(|&idx| idx < cursor);
+                    albums.insert(0, album);
+                }
+
+                albums.concat()
+            }
+
+            // Weighted random sampling without replacement, each entry gets a
+            // random key skewed by its weight and the highest keys go first
+            ShuffleMode::Weighted => {
+                let mut keyed: Vec<(f64, usize)> = rest
+                    .into_iter()
+                    .map(|idx| {
+                        let weight = shuffle_weight(&self.entries[idx]);
+                        (rng.random::<f64>().powf(1.0 / weight), idx)
+                    })
+                    .collect();
+                keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+                keyed.into_iter().map(|(_, idx)| idx).collect()
+            }
+        };
+
+        if let Some(cursor) = self.cursor {
+            order.insert(0, cursor);
+        }
+
+        order
+    }
+
+    fn current(&self) -> Option<TrackInfo> {
+        self.cursor.and_then(|idx| self.entries.get(idx)).cloned()
+    }
+
+    /// Position of the current entry in the play order.
+    fn cursor_position(&self) -> Option<usize> {
+        let cursor = self.cursor?;
+        self.order.iter().position(|&idx| idx == cursor)
+    }
+
+    /// Applies an edit to the entries of the queue, each tagged with its
+    /// original position, and maps the original positions to the new ones.
+    ///
+    /// The play order and cursor are carried over to the new positions. If the
+    /// current entry was removed, the cursor falls back to the nearest earlier
+    /// entry in the play order so that playback carries on with the entry that
+    /// followed it.
+    ///
+    /// The edit is only recorded in the history if it changed the queue.
+    fn rearrange<F>(&mut self, operation: QueueOperation, edit: F) -> Vec<Option<usize>>
+    where
+        F: FnOnce(&mut Vec<(usize, TrackInfo)>),
+    {
+        let snapshot = self.snapshot(operation);
+        let original_len = self.entries.len();
+
+        let mut entries: Vec<(usize, TrackInfo)> = self.entries.drain(..).enumerate().collect();
+        edit(&mut entries);
+
+        let mut positions = vec![None; original_len];
+        for (new_idx, (original_idx, _)) in entries.iter().enumerate() {
+            positions[*original_idx] = Some(new_idx);
+        }
+
+        let changed = entries.len() != original_len
+            || entries
+                .iter()
+                .enumerate()
+                .any(|(new_idx, (original_idx, _))| new_idx != *original_idx);
+        if changed {
+            self.push_undo(snapshot);
+        }
+
+        self.entries = entries.into_iter().map(|(_, track)| track).collect();
+
+        if let Some(pos) = self.cursor_position() {
+            self.cursor = self.order[..=pos]
+                .iter()
+                .rev()
+                .find_map(|&idx| positions[idx]);
+        }
+
+        self.order = if self.is_shuffled() {
+            self.order.iter().filter_map(|&idx| positions[idx]).collect()
+        } else {
+            (0..self.entries.len()).collect()
+        };
+
+        self.sync_tracks();
+
+        positions
+    }
+
+    /// Records the current contents of the queue before an edit.
+    fn checkpoint(&mut self, operation: QueueOperation) {
+        let snapshot = self.snapshot(operation);
+        self.push_undo(snapshot);
+    }
+
+    fn snapshot(&self, operation: QueueOperation) -> QueueSnapshot {
+        QueueSnapshot {
+            operation,
+            entries: self.entries.clone(),
+            order: self.order.clone(),
+            shuffle_mode: self.shuffle_mode,
+            cursor: self.cursor,
+        }
+    }
+
+    fn push_undo(&mut self, snapshot: QueueSnapshot) {
+        if self.undo_stack.len() == HISTORY_LIMIT {
+            self.undo_stack.pop_front();
         }
+        self.undo_stack.push_back(snapshot);
+        self.redo_stack.clear();
+    }
+
+    /// Replaces the contents of the queue with a snapshot, returning a
+    /// snapshot of the replaced contents for the same edit.
+    ///
+    /// The cursor moves to the entry for the current track in the restored
+    /// queue, choosing the nearest one if the track appears more than once.
+    /// If there is no current track, the cursor from the snapshot is used.
+    fn restore(&mut self, snapshot: QueueSnapshot) -> QueueSnapshot {
+        let replaced = self.snapshot(snapshot.operation);
+        let current = self
+            .cursor
+            .and_then(|idx| Some((idx, self.entries.get(idx)?.track_id)));
+
+        self.entries = snapshot.entries;
+        self.order = snapshot.order;
+        self.shuffle_mode = snapshot.shuffle_mode;
+
+        self.cursor = match current {
+            Some((previous_idx, track_id)) => self
+                .entries
+                .iter()
+                .enumerate()
+                .filter(|(_, track)| track.track_id == track_id)
+                .map(|(idx, _)| idx)
+                .min_by_key(|idx| idx.abs_diff(previous_idx)),
+            None => snapshot.cursor,
+        };
+
+        self.sync_tracks();
+
+        replaced
     }
 
     fn sync_tracks(&mut self) {
         let mut locked_tracks = self.tracks.lock().unwrap();
         locked_tracks.clear();
+        locked_tracks.extend(self.entries.iter().cloned());
+
+        self.total_duration = self.entries.iter().map(|t| t.duration).sum();
+    }
+}
+
+/// Relative weight of a track in a weighted shuffle.
+///
+/// Liked tracks are favoured and disliked ones all but excluded, and the more
+/// often a track has been played the less it is favoured.
+fn shuffle_weight(track: &TrackInfo) -> f64 {
+    let rating_weight = match track.rating {
+        Rating::Like => 4.0,
+        Rating::Neutral => 1.0,
+        Rating::Dislike => 0.05,
+    };
+
+    rating_weight / (1.0 + f64::from(track.play_count)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(id: i32, artist: &str, album: &str) -> TrackInfo {
+        TrackInfo {
+            track_id: id,
+            durable_id: i64::from(id),
+            track_title: format!("Track {}", id),
+            track_number: id,
+            duration: 100,
+            genre: None,
+            year: None,
+            album_title: album.to_string(),
+            artist_name: artist.to_string(),
+            filename: format!("{}.mp3", id),
+            play_count: 0,
+            rating: Rating::Neutral,
+            stars: None,
+            created_at: 0,
+            is_podcast: false,
+            cover_hash: None,
+            disc_number: None,
+            album_artist: Some(artist.to_string()),
+            composer: None,
+        }
+    }
+
+    fn compilation_track(id: i32, artist: &str, album: &str) -> TrackInfo {
+        TrackInfo {
+            album_artist: Some("Various Artists".to_string()),
+            ..track(id, artist, album)
+        }
+    }
+
+    fn track_ids(tracks: &[TrackInfo]) -> Vec<i32> {
+        tracks.iter().map(|track| track.track_id).collect()
+    }
+
+    /// Track ids in the order they will play, starting from the cursor.
+    fn play_order_ids(queue: &Queue) -> Vec<i32> {
+        queue.order.iter().map(|&idx| queue.entries[idx].track_id).collect()
+    }
+
+    /// A queue of tracks by different artists, with ids counting up from 1.
+    fn queue_of(count: i32) -> Queue {
+        let mut queue = Queue::new();
+        queue.add_tracks((1..=count).map(|id| track(id, &format!("Artist {}", id), "Album")).collect());
+        queue
+    }
+
+    fn current_id(queue: &Queue) -> Option<i32> {
+        queue.current().map(|track| track.track_id)
+    }
+
+    #[test]
+    fn next_without_repeat_stops_at_end() {
+        let mut queue = queue_of(3);
+        queue.play_first();
+
+        assert_eq!(queue.next(RepeatMode::NoRepeat).map(|t| t.track_id), Some(2));
+        assert_eq!(queue.next(RepeatMode::NoRepeat).map(|t| t.track_id), Some(3));
+        assert!(queue.is_last());
+        assert!(queue.next(RepeatMode::NoRepeat).is_none());
+        assert_eq!(queue.current_index(), None);
+    }
+
+    #[test]
+    fn next_with_repeat_all_wraps_around() {
+        let mut queue = queue_of(2);
+        queue.play_entry(1);
+
+        assert_eq!(queue.next(RepeatMode::RepeatAll).map(|t| t.track_id), Some(1));
+        assert_eq!(queue.next(RepeatMode::RepeatAll).map(|t| t.track_id), Some(2));
+    }
+
+    #[test]
+    fn next_with_repeat_one_stays_put() {
+        let mut queue = queue_of(3);
+        queue.play_entry(1);
+
+        assert_eq!(queue.next(RepeatMode::RepeatOne).map(|t| t.track_id), Some(2));
+        assert_eq!(queue.next(RepeatMode::RepeatOne).map(|t| t.track_id), Some(2));
+    }
+
+    #[test]
+    fn next_without_current_entry_plays_nothing() {
+        let mut queue = queue_of(3);
+
+        assert!(queue.next(RepeatMode::RepeatAll).is_none());
+    }
+
+    #[test]
+    fn previous_without_repeat_stays_at_start() {
+        let mut queue = queue_of(3);
+        queue.play_entry(1);
+
+        assert_eq!(queue.previous(RepeatMode::NoRepeat).map(|t| t.track_id), Some(1));
+        assert_eq!(queue.previous(RepeatMode::NoRepeat).map(|t| t.track_id), Some(1));
+    }
+
+    #[test]
+    fn previous_with_repeat_all_wraps_around() {
+        let mut queue = queue_of(3);
+        queue.play_first();
+
+        assert_eq!(queue.previous(RepeatMode::RepeatAll).map(|t| t.track_id), Some(3));
+        assert_eq!(queue.previous(RepeatMode::RepeatAll).map(|t| t.track_id), Some(2));
+    }
+
+    #[test]
+    fn previous_with_repeat_one_stays_put() {
+        let mut queue = queue_of(3);
+        queue.play_entry(2);
+
+        assert_eq!(queue.previous(RepeatMode::RepeatOne).map(|t| t.track_id), Some(3));
+    }
+
+    #[test]
+    fn next_follows_shuffled_play_order() {
+        let mut queue = queue_of(5);
+        queue.play_first();
+        queue.set_shuffle_mode(ShuffleMode::Track);
+
+        let mut played = vec![current_id(&queue).unwrap()];
+        while let Some(track) = queue.next(RepeatMode::NoRepeat) {
+            played.push(track.track_id);
+        }
+
+        assert_eq!(played, play_order_ids(&queue));
+        played.sort();
+        assert_eq!(played, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn removing_current_entry_falls_back_to_previous_entry() {
+        let mut queue = queue_of(4);
+        queue.play_entry(2);
+
+        let positions = queue.remove_entries(&[2]);
+
+        assert_eq!(positions, [Some(0), Some(1), None, Some(2)]);
+        assert_eq!(current_id(&queue), Some(2));
+        assert_eq!(queue.next(RepeatMode::NoRepeat).map(|t| t.track_id), Some(4));
+    }
+
+    #[test]
+    fn removing_entries_before_current_entry_moves_cursor() {
+        let mut queue = queue_of(4);
+        queue.play_entry(2);
+
+        queue.remove_entries(&[0, 1]);
+
+        assert_eq!(queue.current_index(), Some(0));
+        assert_eq!(current_id(&queue), Some(3));
+    }
+
+    #[test]
+    fn removing_first_entry_while_current_clears_cursor() {
+        let mut queue = queue_of(3);
+        queue.play_first();
+
+        queue.remove_entries(&[0]);
+
+        assert_eq!(queue.current_index(), None);
+        assert_eq!(track_ids(&queue.tracks().lock().unwrap()), [2, 3]);
+    }
+
+    #[test]
+    fn removing_current_entry_in_shuffle_falls_back_in_play_order() {
+        let mut queue = queue_of(5);
+        queue.play_first();
+        queue.set_shuffle_mode(ShuffleMode::Track);
+        queue.next(RepeatMode::NoRepeat);
+        queue.next(RepeatMode::NoRepeat);
+
+        let before = play_order_ids(&queue);
+        let current = queue.current_index().unwrap();
+        queue.remove_entries(&[current]);
+
+        assert_eq!(current_id(&queue), Some(before[1]));
+        assert_eq!(queue.next(RepeatMode::NoRepeat).map(|t| t.track_id), Some(before[3]));
+    }
+
+    #[test]
+    fn moving_entries_keeps_current_entry() {
+        let mut queue = queue_of(4);
+        queue.play_entry(1);
+
+        let positions = queue.move_entries(&[1, 2], 1);
+
+        assert_eq!(positions, [Some(0), Some(2), Some(3), Some(1)]);
+        assert_eq!(track_ids(&queue.tracks().lock().unwrap()), [1, 4, 2, 3]);
+        assert_eq!(current_id(&queue), Some(2));
+    }
+
+    #[test]
+    fn play_next_moves_entries_after_current_entry() {
+        let mut queue = queue_of(5);
+        queue.play_entry(1);
+
+        let positions = queue.play_next(&[3, 4]);
+
+        assert_eq!(positions, [Some(0), Some(1), Some(4), Some(2), Some(3)]);
+        assert_eq!(track_ids(&queue.tracks().lock().unwrap()), [1, 2, 4, 5, 3]);
+        assert_eq!(current_id(&queue), Some(2));
+        assert_eq!(queue.next(RepeatMode::NoRepeat).map(|t| t.track_id), Some(4));
+    }
+
+    #[test]
+    fn play_next_without_current_entry_moves_entries_first() {
+        let mut queue = queue_of(3);
+
+        queue.play_next(&[2]);
+
+        assert_eq!(track_ids(&queue.tracks().lock().unwrap()), [3, 1, 2]);
+    }
+
+    #[test]
+    fn play_next_in_shuffle_plays_entries_next() {
+        let mut queue = queue_of(6);
+        queue.play_first();
+        queue.set_shuffle_mode(ShuffleMode::Track);
+
+        // Entries later in the play order, in queue order
+        let mut later: Vec<usize> = queue.order[3..].to_vec();
+        later.sort();
+        let later_ids: Vec<i32> = later.iter().map(|&idx| queue.entries[idx].track_id).collect();
+
+        queue.play_next(&later);
+
+        let order = play_order_ids(&queue);
+        assert_eq!(order[0], 1);
+        let mut next_ids = order[1..1 + later_ids.len()].to_vec();
+        next_ids.sort();
+        let mut expected = later_ids.clone();
+        expected.sort();
+        assert_eq!(next_ids, expected);
+    }
+
+    #[test]
+    fn undo_and_redo_edits() {
+        let mut queue = queue_of(3);
+        queue.remove_entries(&[1]);
+
+        assert_eq!(queue.undo(), Some(QueueOperation::Remove(1)));
+        assert_eq!(track_ids(&queue.tracks().lock().unwrap()), [1, 2, 3]);
+
+        assert_eq!(queue.redo(), Some(QueueOperation::Remove(1)));
+        assert_eq!(track_ids(&queue.tracks().lock().unwrap()), [1, 3]);
+
+        assert_eq!(queue.undo(), Some(QueueOperation::Remove(1)));
+        assert_eq!(queue.undo(), Some(QueueOperation::Add(3)));
+        assert!(queue.tracks().lock().unwrap().is_empty());
+        assert_eq!(queue.undo(), None);
+    }
+
+    #[test]
+    fn new_edit_clears_redo() {
+        let mut queue = queue_of(3);
+        queue.clear();
+        queue.undo();
+
+        queue.remove_entries(&[0]);
+
+        assert_eq!(queue.redo(), None);
+    }
+
+    #[test]
+    fn unchanged_queue_is_not_recorded() {
+        let mut queue = queue_of(3);
+
+        queue.move_entries(&[0], -1);
+        queue.remove_entries(&[7]);
+
+        assert_eq!(queue.undo(), Some(QueueOperation::Add(3)));
+        assert_eq!(queue.undo(), None);
+    }
+
+    #[test]
+    fn undo_follows_current_track() {
+        let mut queue = queue_of(4);
+        queue.play_entry(3);
+        queue.move_entries(&[3], -1);
+        queue.move_entries(&[2], -1);
+        assert_eq!(queue.current_index(), Some(1));
+
+        queue.undo();
+        queue.undo();
+
+        assert_eq!(queue.current_index(), Some(3));
+        assert_eq!(current_id(&queue), Some(4));
+    }
+
+    #[test]
+    fn undo_history_is_limited() {
+        let mut queue = Queue::new();
+        for id in 0..HISTORY_LIMIT as i32 + 5 {
+            queue.add_tracks(vec![track(id, "Artist", "Album")]);
+        }
+
+        let mut undone = 0;
+        while queue.undo().is_some() {
+            undone += 1;
+        }
+
+        assert_eq!(undone, HISTORY_LIMIT);
+        assert_eq!(queue.tracks().lock().unwrap().len(), 5);
+
+        let mut redone = 0;
+        while queue.redo().is_some() {
+            redone += 1;
+        }
+
+        assert_eq!(redone, HISTORY_LIMIT);
+        assert_eq!(queue.tracks().lock().unwrap().len(), HISTORY_LIMIT + 5);
+    }
+
+    #[test]
+    fn album_shuffle_keeps_albums_together() {
+        let mut queue = Queue::new();
+        queue.add_tracks(vec![
+            compilation_track(1, "Artist A", "Hits"),
+            compilation_track(2, "Artist B", "Hits"),
+            track(3, "Artist C", "Album"),
+            track(4, "Artist C", "Album"),
+            compilation_track(5, "Artist C", "Hits"),
+            track(6, "Artist D", "Other"),
+        ]);
+
+        queue.set_shuffle_mode(ShuffleMode::Album);
+
+        let order = play_order_ids(&queue);
+        let mut albums: Vec<&str> = order
+            .iter()
+            .map(|&id| queue.entries[id as usize - 1].album_title.as_str())
+            .collect();
+        albums.dedup();
+        assert_eq!(albums.len(), 3, "albums split up in {:?}", order);
+
+        // Within an album the tracks keep their queue order
+        let hits: Vec<i32> = order.iter().copied().filter(|id| [1, 2, 5].contains(id)).collect();
+        assert_eq!(hits, [1, 2, 5]);
+    }
+
+    #[test]
+    fn album_shuffle_plays_rest_of_current_album_first() {
+        let mut queue = Queue::new();
+        queue.add_tracks(vec![
+            compilation_track(1, "Artist A", "Hits"),
+            compilation_track(2, "Artist B", "Hits"),
+            compilation_track(3, "Artist C", "Hits"),
+            track(4, "Artist D", "Other"),
+            track(5, "Artist D", "Other"),
+        ]);
+        queue.play_entry(1);
 
-        self.played_duration = self.played.iter().map(|t| t.duration).sum();
-        self.queued_duration = self.queued.iter().map(|t| t.duration).sum();
-        self.total_duration = self.played_duration + self.queued_duration;
+        queue.set_shuffle_mode(ShuffleMode::Album);
 
-        locked_tracks.extend(self.played.iter().cloned());
-        locked_tracks.extend(self.queued.iter().cloned());
+        assert_eq!(play_order_ids(&queue)[..3], [2, 3, 1]);
+        assert_eq!(track_ids(&queue.recent(1)), [2]);
     }
 }
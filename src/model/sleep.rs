@@ -0,0 +1,187 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Sleep timer management.
+//!
+//! This module provides state for the sleep timer, tracking when playback
+//! should end and how much the volume should be faded as that point
+//! approaches.
+
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+
+/// When the sleep timer should expire.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum SleepTarget {
+    /// Expire after a fixed amount of time.
+    After(Duration),
+
+    /// Expire when the currently playing track finishes.
+    EndOfTrack,
+
+    /// Expire when the play queue runs out.
+    EndOfQueue,
+}
+
+/// What to do when the sleep timer expires.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum SleepAction {
+    Stop,
+    Quit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SleepMode {
+    Deadline(Instant),
+    EndOfTrack,
+    EndOfQueue,
+}
+
+pub(crate) struct SleepTimer {
+    mode: Option<SleepMode>,
+    action: SleepAction,
+    fade: Duration,
+    fade_gain: f32,
+    remaining: Option<Duration>,
+}
+
+impl SleepTimer {
+    pub(crate) fn new(fade: Duration) -> Self {
+        Self {
+            mode: None,
+            action: SleepAction::Stop,
+            fade,
+            fade_gain: 1.0,
+            remaining: None,
+        }
+    }
+
+    /// Start (or restart) the timer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, leaving the timer as it was, if the duration is too
+    /// long to represent a deadline.
+    pub(crate) fn start(&mut self, target: SleepTarget, action: SleepAction) -> Result<()> {
+        self.mode = Some(match target {
+            SleepTarget::After(duration) => SleepMode::Deadline(
+                Instant::now()
+                    .checked_add(duration)
+                    .ok_or_else(|| anyhow!("Sleep timer duration is too long"))?,
+            ),
+            SleepTarget::EndOfTrack => SleepMode::EndOfTrack,
+            SleepTarget::EndOfQueue => SleepMode::EndOfQueue,
+        });
+        self.action = action;
+
+        Ok(())
+    }
+
+    pub(crate) fn cancel(&mut self) {
+        self.mode = None;
+        self.remaining = None;
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.mode.is_some()
+    }
+
+    pub(crate) fn action(&self) -> SleepAction {
+        self.action
+    }
+
+    /// Returns `true` if the timer should expire when a track finishes.
+    ///
+    /// # Arguments
+    ///
+    /// * `last_in_queue` - Whether the track that finished was the last track
+    ///   that would be played from the queue.
+    pub(crate) fn expires_on_track_finished(&self, last_in_queue: bool) -> bool {
+        match self.mode {
+            Some(SleepMode::EndOfTrack) => true,
+            Some(SleepMode::EndOfQueue) => last_in_queue,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if a fixed-time timer has run out.
+    pub(crate) fn deadline_passed(&self) -> bool {
+        matches!(self.mode, Some(SleepMode::Deadline(deadline)) if Instant::now() >= deadline)
+    }
+
+    /// Recalculates the time remaining before the timer expires, and works
+    /// out the volume multiplier for it, ramping linearly down to zero over
+    /// the fade period.
+    ///
+    /// Returns `Some` only if the gain has changed since the last update, so
+    /// the audio player is not flooded with redundant updates.
+    ///
+    /// # Arguments
+    ///
+    /// * `track_remaining` - Time left in the currently playing track.
+    /// * `queue_remaining` - Time left in the current track plus every track
+    ///   still to be played from the queue.
+    pub(crate) fn update(
+        &mut self,
+        track_remaining: Option<Duration>,
+        queue_remaining: Option<Duration>,
+    ) -> Option<f32> {
+        self.remaining = match self.mode {
+            Some(SleepMode::Deadline(deadline)) => {
+                Some(deadline.saturating_duration_since(Instant::now()))
+            }
+            Some(SleepMode::EndOfTrack) => track_remaining,
+            Some(SleepMode::EndOfQueue) => queue_remaining,
+            None => None,
+        };
+
+        let gain = match self.remaining {
+            Some(remaining) if !self.fade.is_zero() && remaining < self.fade => {
+                remaining.as_secs_f32() / self.fade.as_secs_f32()
+            }
+            _ => 1.0,
+        };
+
+        if (gain - self.fade_gain).abs() > f32::EPSILON {
+            self.fade_gain = gain;
+            Some(gain)
+        } else {
+            None
+        }
+    }
+
+    /// The time remaining as of the last update, if known.
+    pub(crate) fn remaining(&self) -> Option<Duration> {
+        self.remaining
+    }
+
+    /// Resets the fade, returning `true` if the volume had been faded and
+    /// therefore needs restoring.
+    pub(crate) fn reset_fade(&mut self) -> bool {
+        let faded = self.fade_gain < 1.0;
+        self.fade_gain = 1.0;
+        faded
+    }
+
+    /// Describes the timer target for display purposes.
+    pub(crate) fn describe(&self) -> Option<&'static str> {
+        match self.mode? {
+            SleepMode::Deadline(_) => Some("Sleep"),
+            SleepMode::EndOfTrack => Some("Sleep (end of track)"),
+            SleepMode::EndOfQueue => Some("Sleep (end of queue)"),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Internet radio station management.
+//!
+//! This module provides state for the stations view, managing the list of
+//! known internet radio streams.
+
+use std::sync::{Arc, Mutex};
+
+use crate::model::Station;
+
+pub(crate) struct Stations {
+    stations: Arc<Mutex<Vec<Station>>>,
+}
+
+impl Stations {
+    pub(crate) fn new() -> Self {
+        Self {
+            stations: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    pub(crate) fn set_stations(&mut self, stations: Vec<Station>) {
+        let mut lock = self.stations.lock().unwrap();
+        *lock = stations;
+    }
+
+    pub(crate) fn stations(&self) -> Arc<Mutex<Vec<Station>>> {
+        Arc::clone(&self.stations)
+    }
+}
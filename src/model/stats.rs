@@ -0,0 +1,55 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Listening statistics.
+//!
+//! This module defines the aggregate statistics shown in the stats view. They
+//! are computed from the catalog and play counts by the task worker, and
+//! replaced wholesale each time the view is refreshed.
+
+/// A label and the count associated with it, such as an artist and the number
+/// of times it has been played.
+pub(crate) type Tally = (String, u64);
+
+/// Overall size and usage of the music library.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LibrarySummary {
+    pub(crate) artists: u64,
+    pub(crate) albums: u64,
+    pub(crate) tracks: u64,
+    /// Total duration of all tracks, in seconds.
+    pub(crate) duration: u64,
+    pub(crate) plays: u64,
+    /// Estimated total listening time, in seconds, from each track's play
+    /// count and duration.
+    pub(crate) listening_time: u64,
+    pub(crate) never_played: u64,
+    pub(crate) liked: u64,
+    pub(crate) disliked: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LibraryStats {
+    pub(crate) summary: LibrarySummary,
+    pub(crate) top_artists: Vec<Tally>,
+    pub(crate) top_albums: Vec<Tally>,
+    pub(crate) top_tracks: Vec<Tally>,
+    pub(crate) genre_plays: Vec<Tally>,
+    /// Number of tracks by file format, most common first.
+    pub(crate) formats: Vec<Tally>,
+    /// Number of tracks added to the catalog in each recent month, oldest
+    /// first, labelled `YYYY-MM`.
+    pub(crate) added_per_month: Vec<Tally>,
+}
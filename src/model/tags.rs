@@ -0,0 +1,86 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Editable file tags.
+//!
+//! This module defines the tags that can be changed with the tag editor. Tags
+//! are edited as text, and changes are only made to the tags that are edited,
+//! so tracks edited together keep any tags they do not share.
+
+/// A tag that can be edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TagField {
+    Title,
+    Artist,
+    AlbumArtist,
+    Album,
+    TrackNumber,
+    DiscNumber,
+    Year,
+    Genre,
+}
+
+impl TagField {
+    pub(crate) const ALL: [TagField; 8] = [
+        TagField::Title,
+        TagField::Artist,
+        TagField::AlbumArtist,
+        TagField::Album,
+        TagField::TrackNumber,
+        TagField::DiscNumber,
+        TagField::Year,
+        TagField::Genre,
+    ];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            TagField::Title => "Title",
+            TagField::Artist => "Artist",
+            TagField::AlbumArtist => "Album Artist",
+            TagField::Album => "Album",
+            TagField::TrackNumber => "Track",
+            TagField::DiscNumber => "Disc",
+            TagField::Year => "Year",
+            TagField::Genre => "Genre",
+        }
+    }
+
+    /// Numeric tags must be empty or a whole number.
+    pub(crate) fn is_numeric(self) -> bool {
+        matches!(
+            self,
+            TagField::TrackNumber | TagField::DiscNumber | TagField::Year
+        )
+    }
+}
+
+/// The editable tags of a track, as text, empty where a track has no tag.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct TrackTags {
+    values: [String; TagField::ALL.len()],
+}
+
+impl TrackTags {
+    pub(crate) fn get(&self, field: TagField) -> &str {
+        &self.values[field as usize]
+    }
+
+    pub(crate) fn set(&mut self, field: TagField, value: String) {
+        self.values[field as usize] = value;
+    }
+}
+
+/// A new value for a tag. An empty value removes the tag.
+pub(crate) type TagChange = (TagField, String);
@@ -26,15 +26,20 @@ use souvlaki::{MediaControlEvent, MediaControls, MediaMetadata, PlatformConfig,
 use std::{
     fs::File,
     io::BufReader,
-    sync::{Arc, Mutex, mpsc::{Receiver, Sender}},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, Receiver, Sender},
+    },
     thread,
     time::Duration,
 };
 
 use crate::{
+    covers,
     events::AppEvent,
-    player::{AudioPlayer, PlayerState},
-    model::TrackInfo,
+    player::{AudioPlayer, PlayerState, stream::StreamReader},
+    model::{Station, TrackInfo, equalizer::ChannelMix},
 };
 
 const BANDS: usize = 18;
@@ -44,7 +49,8 @@ const FREQUENCIES: [f32; BANDS] = [
 
 #[derive(Debug, Clone)]
 pub(crate) enum AudioPlayerCommand {
-    PlayTrack(TrackInfo),
+    PlayTrack(Box<TrackInfo>, Duration),
+    PlayStation(Station),
     Play,
     Pause,
     TogglePause,
@@ -53,8 +59,33 @@ pub(crate) enum AudioPlayerCommand {
     Stop,
     AdjustVolume(i32),
     ToggleMute,
+    SetFade(f32),
     ResetEqualizer,
     UpdateEqualizerAmp(usize, f64),
+    SetChannelMix(ChannelMix),
+}
+
+/// Channel mix settings shared with the audio thread.
+///
+/// The generation changes whenever the settings do, so the audio thread only
+/// needs to take the lock when there is something new to pick up.
+struct MixSettings {
+    generation: AtomicU64,
+    channel_mix: Mutex<ChannelMix>,
+}
+
+impl MixSettings {
+    fn new(channel_mix: ChannelMix) -> Self {
+        Self {
+            generation: AtomicU64::new(0),
+            channel_mix: Mutex::new(channel_mix),
+        }
+    }
+
+    fn set(&self, channel_mix: ChannelMix) {
+        *self.channel_mix.lock().unwrap() = channel_mix;
+        self.generation.fetch_add(1, Ordering::Release);
+    }
 }
 
 struct EqSettings {
@@ -77,11 +108,12 @@ impl EqSettings {
 pub(crate) fn spawn_player_worker(
     command_rx: Receiver<AudioPlayerCommand>,
     event_tx: Sender<AppEvent>,
+    channel_mix: ChannelMix,
 ) {
     let error_tx = event_tx.clone();
 
     thread::spawn(move || {
-        if let Err(e) = audio_player_worker(command_rx, event_tx) {
+        if let Err(e) = audio_player_worker(command_rx, event_tx, channel_mix) {
             let _ = error_tx.send(AppEvent::FatalError(format!("Audio worker failure: {:?}", e)));
         }
     });
@@ -90,17 +122,26 @@ pub(crate) fn spawn_player_worker(
 fn audio_player_worker(
     command_rx: Receiver<AudioPlayerCommand>,
     event_tx: Sender<AppEvent>,
+    channel_mix: ChannelMix,
 ) -> Result<()> {
     let (_stream, stream_handle) = OutputStream::try_default().context("Failed to open audio output stream")?;
     let sink = Sink::try_new(&stream_handle).context("Failed to create audio sink")?;
 
     let eq_settings = Arc::new(Mutex::new(EqSettings::new()));
+    let mix_settings = Arc::new(MixSettings::new(channel_mix));
 
     let mut current_volume = sink.volume();
     let mut is_muted = false;
+    let mut fade_gain = 1.0f32;
     let mut player_state = PlayerState::Stopped;
     let mut last_pos = Duration::ZERO;
     let mut current_track_duration = Duration::ZERO;
+    let mut is_stream = false;
+
+    // Stations connect in the background, a connection is only used if no
+    // other playback was requested in the meantime
+    let (station_tx, station_rx) = mpsc::channel::<StationConnection>();
+    let mut station_generation = 0u64;
 
     // Media Controls setup - unique name per instance like VLC
     let pid = std::process::id();
@@ -146,7 +187,9 @@ fn audio_player_worker(
     loop {
         while let Ok(command) = command_rx.try_recv() {
             match command {
-                AudioPlayerCommand::PlayTrack(track) => {
+                AudioPlayerCommand::PlayTrack(track, start) => {
+                    station_generation += 1;
+
                     let file = File::open(&track.filename).context(format!("Failed to open file: {}", &track.filename))?;
                     let source = Decoder::new(BufReader::new(file)).context("Failed to decode audio file")?;
 
@@ -155,25 +198,42 @@ fn audio_player_worker(
                     event_tx.send(AppEvent::DurationChanged(duration_secs))?;
 
                     let eq_source = EqualizerSourceInner::new(source, Arc::clone(&eq_settings));
+                    let mix_source = ChannelMixSource::new(eq_source, Arc::clone(&mix_settings));
 
                     sink.stop();
-                    sink.append(eq_source);
+                    sink.append(mix_source);
                     sink.play();
+                    is_stream = false;
+
+                    if !start.is_zero() {
+                        let _ = sink.try_seek(start);
+                    }
 
                     event_tx.send(AppEvent::TitleChanged(track.track_title.clone()))?;
 
                     // Update Media Controls Metadata
                     if let Some(ref mut c) = controls {
+                        let cover_url = track
+                            .cover_hash
+                            .as_deref()
+                            .map(|hash| format!("file://{}", covers::thumbnail_path(hash).display()));
                         let metadata = MediaMetadata {
                             title: Some(&track.track_title),
                             artist: Some(&track.artist_name),
                             album: Some(&track.album_title),
                             duration: Some(current_track_duration),
-                            ..Default::default()
+                            cover_url: cover_url.as_deref(),
                         };
                         let _ = c.set_metadata(metadata);
                     }
                 }
+                AudioPlayerCommand::PlayStation(station) => {
+                    // Connecting and probing the stream format can take many
+                    // seconds, so it is done off the worker and the stream is
+                    // picked up below once ready
+                    station_generation += 1;
+                    spawn_station_connect(station, station_generation, station_tx.clone(), event_tx.clone());
+                }
                 AudioPlayerCommand::Play => {
                     sink.play();
                 }
@@ -200,13 +260,14 @@ fn audio_player_worker(
                     let _ = sink.try_seek(pos);
                 }
                 AudioPlayerCommand::Stop => {
+                    station_generation += 1;
                     sink.stop();
                     last_pos = Duration::ZERO;
                 }
                 AudioPlayerCommand::AdjustVolume(delta) => {
                     current_volume = (current_volume + (delta as f32 / 100.0)).clamp(0.0, 1.0);
                     if !is_muted {
-                        sink.set_volume(current_volume);
+                        sink.set_volume(current_volume * fade_gain);
                     }
                     event_tx.send(AppEvent::VolumeChanged((current_volume * 100.0) as u32))?;
                 }
@@ -215,7 +276,15 @@ fn audio_player_worker(
                     if is_muted {
                         sink.set_volume(0.0);
                     } else {
-                        sink.set_volume(current_volume);
+                        sink.set_volume(current_volume * fade_gain);
+                    }
+                }
+                AudioPlayerCommand::SetFade(gain) => {
+                    // The fade is applied on top of the user volume, so that
+                    // restoring it returns to the level the user chose
+                    fade_gain = gain.clamp(0.0, 1.0);
+                    if !is_muted {
+                        sink.set_volume(current_volume * fade_gain);
                     }
                 }
 
@@ -235,6 +304,42 @@ fn audio_player_worker(
                     }
                     settings.dirty = true;
                 }
+                AudioPlayerCommand::SetChannelMix(channel_mix) => {
+                    mix_settings.set(channel_mix);
+                }
+            }
+        }
+
+        while let Ok(connection) = station_rx.try_recv() {
+            // A stale connection is dropped here, closing its stream
+            if connection.generation != station_generation {
+                continue;
+            }
+
+            let source = match connection.source {
+                Ok(source) => source,
+                Err(e) => {
+                    event_tx.send(AppEvent::Error(format!("{:#}", e)))?;
+                    continue;
+                }
+            };
+
+            event_tx.send(AppEvent::DurationChanged(0))?;
+
+            let eq_source = EqualizerSourceInner::new(source, Arc::clone(&eq_settings));
+            let mix_source = ChannelMixSource::new(eq_source, Arc::clone(&mix_settings));
+
+            sink.stop();
+            sink.append(mix_source);
+            sink.play();
+            is_stream = true;
+
+            if let Some(ref mut c) = controls {
+                let metadata = MediaMetadata {
+                    title: Some(&connection.station.name),
+                    ..Default::default()
+                };
+                let _ = c.set_metadata(metadata);
             }
         }
 
@@ -246,7 +351,8 @@ fn audio_player_worker(
             player_state = new_player_state;
             event_tx.send(AppEvent::PlayerStateChanged(player_state))?;
 
-            if is_idle && last_pos.as_secs() > 0 {
+            // A stream has no end, if it stops the connection was lost
+            if is_idle && last_pos.as_secs() > 0 && !is_stream {
                  event_tx.send(AppEvent::TrackFinished)?;
             }
 
@@ -280,6 +386,37 @@ fn audio_player_worker(
     }
 }
 
+/// The outcome of connecting to a station in the background.
+struct StationConnection {
+    station: Station,
+
+    /// The `PlayStation` request this connection was made for.
+    generation: u64,
+
+    source: Result<Decoder<StreamReader>>,
+}
+
+/// Connects to a station and probes its stream format on a separate thread,
+/// sending the result back to the audio worker.
+fn spawn_station_connect(
+    station: Station,
+    generation: u64,
+    station_tx: Sender<StationConnection>,
+    event_tx: Sender<AppEvent>,
+) {
+    thread::spawn(move || {
+        // Stations come and go, so failing to play one is not fatal
+        let source = StreamReader::open(&station, event_tx)
+            .and_then(|reader| Decoder::new(reader).context("Failed to decode audio stream"));
+
+        let _ = station_tx.send(StationConnection {
+            station,
+            generation,
+            source,
+        });
+    });
+}
+
 struct EqualizerSourceInner<S: Source>
 where
     S::Item: Sample,
@@ -401,3 +538,163 @@ where
         Ok(())
     }
 }
+
+/// Channel mixing stage, applying channel swap, mono downmix and stereo
+/// balance (in that order) to the left and right channels.
+///
+/// Samples are processed a whole frame at a time since every output channel
+/// may depend on every input channel. Sources with fewer than two channels
+/// pass through untouched.
+struct ChannelMixSource<S: Source<Item = f32>> {
+    input: S,
+    settings: Arc<MixSettings>,
+    channel_mix: ChannelMix,
+    generation: u64,
+    channels: u16,
+    frame: Vec<f32>,
+    frame_pos: usize,
+}
+
+impl<S: Source<Item = f32>> ChannelMixSource<S> {
+    fn new(input: S, settings: Arc<MixSettings>) -> Self {
+        let channels = input.channels();
+        let generation = settings.generation.load(Ordering::Acquire);
+        let channel_mix = *settings.channel_mix.lock().unwrap();
+        Self {
+            input,
+            settings,
+            channel_mix,
+            generation,
+            channels,
+            frame: Vec::with_capacity(channels as usize),
+            frame_pos: 0,
+        }
+    }
+
+    /// Picks up changed settings, without ever waiting on the lock so that the
+    /// audio thread can not be held up. If the lock is busy the change is
+    /// picked up on a later frame.
+    fn refresh_settings(&mut self) {
+        let generation = self.settings.generation.load(Ordering::Acquire);
+        if generation == self.generation {
+            return;
+        }
+
+        if let Ok(channel_mix) = self.settings.channel_mix.try_lock() {
+            self.channel_mix = *channel_mix;
+            self.generation = generation;
+        }
+    }
+
+    fn fill_frame(&mut self) -> bool {
+        self.frame.clear();
+        self.frame_pos = 0;
+        self.frame
+            .extend(self.input.by_ref().take(self.channels as usize));
+
+        if self.frame.len() >= 2 {
+            self.refresh_settings();
+            let mix = self.channel_mix;
+
+            if mix.swap {
+                self.frame.swap(0, 1);
+            }
+
+            if mix.mono {
+                let mid = (self.frame[0] + self.frame[1]) * 0.5;
+                self.frame[0] = mid;
+                self.frame[1] = mid;
+            }
+
+            let (left_gain, right_gain) = mix.balance_gains();
+            self.frame[0] *= left_gain;
+            self.frame[1] *= right_gain;
+        }
+
+        !self.frame.is_empty()
+    }
+}
+
+impl<S: Source<Item = f32>> Iterator for ChannelMixSource<S> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.frame_pos >= self.frame.len() && !self.fill_frame() {
+            return None;
+        }
+
+        let sample = self.frame[self.frame_pos];
+        self.frame_pos += 1;
+        Some(sample)
+    }
+}
+
+impl<S: Source<Item = f32>> Source for ChannelMixSource<S> {
+    fn current_frame_len(&self) -> Option<usize> {
+        // Account for samples already pulled from the input but not yet returned
+        let buffered = self.frame.len() - self.frame_pos;
+        self.input.current_frame_len().map(|len| len + buffered)
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    fn try_seek(&mut self, pos: Duration) -> Result<(), rodio::source::SeekError> {
+        self.input.try_seek(pos)?;
+        self.frame.clear();
+        self.frame_pos = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rodio::buffer::SamplesBuffer;
+
+    use super::*;
+
+    #[test]
+    fn channel_mix_picks_up_changed_settings() {
+        let settings = Arc::new(MixSettings::new(ChannelMix::default()));
+        let input = SamplesBuffer::new(2, 44100, vec![0.25f32, 1.0, 0.25, 1.0]);
+        let mut source = ChannelMixSource::new(input, Arc::clone(&settings));
+
+        assert_eq!((source.next(), source.next()), (Some(0.25), Some(1.0)));
+
+        settings.set(ChannelMix {
+            swap: true,
+            ..Default::default()
+        });
+
+        assert_eq!((source.next(), source.next()), (Some(1.0), Some(0.25)));
+        assert_eq!(source.next(), None);
+    }
+
+    #[test]
+    fn channel_mix_keeps_settings_while_locked() {
+        let settings = Arc::new(MixSettings::new(ChannelMix::default()));
+        let input = SamplesBuffer::new(2, 44100, vec![0.25f32, 1.0, 0.25, 1.0]);
+        let mut source = ChannelMixSource::new(input, Arc::clone(&settings));
+
+        settings.set(ChannelMix {
+            mono: true,
+            ..Default::default()
+        });
+
+        {
+            let _lock = settings.channel_mix.lock().unwrap();
+            assert_eq!((source.next(), source.next()), (Some(0.25), Some(1.0)));
+        }
+
+        assert_eq!((source.next(), source.next()), (Some(0.625), Some(0.625)));
+    }
+}
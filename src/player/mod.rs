@@ -21,12 +21,17 @@
 //! audio operations do not block the main application thread.
 
 mod commands;
+mod stream;
 
-use std::sync::mpsc;
+use std::{sync::mpsc, time::Duration};
 
 use anyhow::Result;
 
-use crate::{events::AppEvent, model::TrackInfo, player::commands::AudioPlayerCommand};
+use crate::{
+    events::AppEvent,
+    model::{Station, TrackInfo, equalizer::ChannelMix},
+    player::commands::AudioPlayerCommand,
+};
 
 /// Represents the current playback status of the audio engine.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -52,10 +57,11 @@ impl AudioPlayer {
     ///
     /// * `event_tx` - A channel to send application-level events (like progress
     ///   updates or errors) back to the main event loop.
-    pub(crate) fn new(event_tx: mpsc::Sender<AppEvent>) -> Result<Self> {
+    /// * `channel_mix` - The initial channel mixing settings.
+    pub(crate) fn new(event_tx: mpsc::Sender<AppEvent>, channel_mix: ChannelMix) -> Result<Self> {
         let (command_tx, command_rx) = mpsc::channel::<AudioPlayerCommand>();
 
-        commands::spawn_player_worker(command_rx, event_tx);
+        commands::spawn_player_worker(command_rx, event_tx, channel_mix);
 
         Ok(Self { command_tx })
     }
@@ -76,10 +82,22 @@ impl AudioPlayer {
     /// # Arguments
     ///
     /// * `track` - The track metadata and filename.
+    /// * `start` - The position to start playback from.
     ///
-    pub(crate) fn play_track(&self, track: TrackInfo) -> Result<()> {
+    pub(crate) fn play_track(&self, track: TrackInfo, start: Duration) -> Result<()> {
         self.command_tx
-            .send(AudioPlayerCommand::PlayTrack(track))?;
+            .send(AudioPlayerCommand::PlayTrack(Box::new(track), start))?;
+        Ok(())
+    }
+
+    /// Instructs the worker to connect to and play an internet radio station.
+    ///
+    /// # Arguments
+    ///
+    /// * `station` - The station to play.
+    pub(crate) fn play_station(&self, station: Station) -> Result<()> {
+        self.command_tx
+            .send(AudioPlayerCommand::PlayStation(station))?;
         Ok(())
     }
 
@@ -124,6 +142,16 @@ impl AudioPlayer {
         Ok(())
     }
 
+    /// Sets a fade multiplier applied on top of the current volume.
+    ///
+    /// # Arguments
+    ///
+    /// * `gain` - The multiplier, from 0.0 (silent) to 1.0 (no fade).
+    pub(crate) fn set_fade(&self, gain: f32) -> Result<()> {
+        self.command_tx.send(AudioPlayerCommand::SetFade(gain))?;
+        Ok(())
+    }
+
     /// Adjusts the playback position forward or backwards relative to the
     /// current position.
     ///
@@ -136,7 +164,7 @@ impl AudioPlayer {
     }
 
     /// Seeks to an absolute position.
-    pub(crate) fn seek_absolute(&self, pos: std::time::Duration) -> Result<()> {
+    pub(crate) fn seek_absolute(&self, pos: Duration) -> Result<()> {
         self.command_tx.send(AudioPlayerCommand::SeekAbsolute(pos))?;
         Ok(())
     }
@@ -158,4 +186,11 @@ impl AudioPlayer {
         self.command_tx.send(AudioPlayerCommand::ResetEqualizer)?;
         Ok(())
     }
+
+    /// Sets the stereo balance, mono downmix and channel swap.
+    pub(crate) fn set_channel_mix(&self, channel_mix: ChannelMix) -> Result<()> {
+        self.command_tx
+            .send(AudioPlayerCommand::SetChannelMix(channel_mix))?;
+        Ok(())
+    }
 }
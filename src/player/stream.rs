@@ -0,0 +1,681 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! HTTP audio stream reading for internet radio.
+//!
+//! Streams are fetched on a dedicated thread into a bounded buffer, so that
+//! network stalls never block the audio output directly. The fetch thread
+//! strips out any Shoutcast/Icecast (ICY) in-band metadata, reporting station
+//! and stream titles as they arrive, and transparently reconnects if the
+//! connection drops.
+
+use std::{
+    collections::VecDeque,
+    io::{self, Read, Seek, SeekFrom},
+    sync::{Arc, Condvar, Mutex, mpsc::Sender},
+    thread,
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use ureq::{Agent, Body, http::Response};
+
+use crate::{events::AppEvent, model::Station};
+
+const CONNECT_TIMEOUT_SECS: u64 = 10;
+
+/// Consecutive failed connection attempts before giving up on a stream.
+const MAX_RECONNECT_ATTEMPTS: u32 = 5;
+
+const RECONNECT_DELAY_SECS: u64 = 2;
+
+/// Upper bound on audio data buffered ahead of playback.
+const MAX_BUFFERED_BYTES: usize = 512 * 1024;
+
+const READ_CHUNK_SIZE: usize = 8192;
+
+/// Size of each unit of the ICY metadata length byte.
+const ICY_METADATA_BLOCK: usize = 16;
+
+/// Amount of data from the start of a stream kept so that the decoder can
+/// rewind while probing the audio format.
+///
+/// The Ogg probe may scan up to 150KiB looking for a page header before giving
+/// up, so this must comfortably exceed that.
+const REWIND_LIMIT: usize = 256 * 1024;
+
+#[derive(Default)]
+struct StreamBuffer {
+    data: VecDeque<u8>,
+
+    /// The fetch thread has given up and no more data will arrive.
+    finished: bool,
+
+    /// The reader has been dropped so the fetch thread should stop.
+    closed: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    buffer: Mutex<StreamBuffer>,
+    changed: Condvar,
+}
+
+/// A reader over an internet radio stream, suitable for passing to the audio
+/// decoder.
+///
+/// Streams can not be seeked in general, but the start of the stream is
+/// retained until [`REWIND_LIMIT`] bytes have been read so that format
+/// detection can seek back within it.
+pub(super) struct StreamReader {
+    shared: Arc<Shared>,
+    position: u64,
+
+    /// Data read from the start of the stream, while still rewindable.
+    head: Vec<u8>,
+
+    /// The start of the stream is still being retained in `head`.
+    rewindable: bool,
+}
+
+impl StreamReader {
+    /// Connects to a station and starts fetching its stream in the background.
+    ///
+    /// The initial connection is made before returning so that an unreachable
+    /// station is reported immediately.
+    ///
+    /// # Arguments
+    ///
+    /// * `station` - The station to connect to.
+    /// * `event_tx` - Channel used to report title changes and errors.
+    pub(super) fn open(station: &Station, event_tx: Sender<AppEvent>) -> Result<Self> {
+        let agent: Agent = Agent::config_builder()
+            .timeout_connect(Some(Duration::from_secs(CONNECT_TIMEOUT_SECS)))
+            .timeout_recv_response(Some(Duration::from_secs(CONNECT_TIMEOUT_SECS)))
+            .build()
+            .into();
+
+        let response = connect(&agent, &station.url)
+            .with_context(|| format!("Failed to connect to station: {}", station.url))?;
+
+        let shared = Arc::new(Shared::default());
+
+        let fetcher = StreamFetcher {
+            agent,
+            station: station.clone(),
+            shared: Arc::clone(&shared),
+            event_tx,
+        };
+        thread::spawn(move || fetcher.run(response));
+
+        Ok(Self {
+            shared,
+            position: 0,
+            head: Vec::new(),
+            rewindable: true,
+        })
+    }
+
+    fn read_live(&mut self, buf: &mut [u8]) -> usize {
+        let mut buffer = self.shared.buffer.lock().unwrap();
+        while buffer.data.is_empty() && !buffer.finished {
+            buffer = self.shared.changed.wait(buffer).unwrap();
+        }
+
+        let len = buf.len().min(buffer.data.len());
+        for (dst, src) in buf.iter_mut().zip(buffer.data.drain(..len)) {
+            *dst = src;
+        }
+        self.shared.changed.notify_all();
+
+        len
+    }
+}
+
+impl Read for StreamReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let position = self.position as usize;
+
+        // Replay previously read data after a rewind
+        let len = if self.rewindable && position < self.head.len() {
+            let len = buf.len().min(self.head.len() - position);
+            buf[..len].copy_from_slice(&self.head[position..position + len]);
+            len
+        } else {
+            let len = self.read_live(buf);
+            if self.rewindable {
+                self.head.extend_from_slice(&buf[..len]);
+                if self.head.len() > REWIND_LIMIT {
+                    self.rewindable = false;
+                    self.head = Vec::new();
+                }
+            }
+            len
+        };
+
+        self.position += len as u64;
+        Ok(len)
+    }
+}
+
+impl Seek for StreamReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Current(0) => return Ok(self.position),
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(delta) => self.position.checked_add_signed(delta),
+            SeekFrom::End(_) => None,
+        };
+
+        match target {
+            Some(target) if self.rewindable && target <= self.head.len() as u64 => {
+                self.position = target;
+                Ok(target)
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "Can not seek within a live stream",
+            )),
+        }
+    }
+}
+
+impl Drop for StreamReader {
+    fn drop(&mut self) {
+        self.shared.buffer.lock().unwrap().closed = true;
+        self.shared.changed.notify_all();
+    }
+}
+
+struct StreamFetcher {
+    agent: Agent,
+    station: Station,
+    shared: Arc<Shared>,
+    event_tx: Sender<AppEvent>,
+}
+
+impl StreamFetcher {
+    fn run(self, response: Response<Body>) {
+        let mut response = Some(response);
+        let mut failures = 0;
+
+        loop {
+            if let Some(response) = response.take()
+                && self.pump(response)
+            {
+                failures = 0;
+            }
+
+            if self.is_closed() {
+                break;
+            }
+
+            failures += 1;
+            if failures > MAX_RECONNECT_ATTEMPTS {
+                let _ = self.event_tx.send(AppEvent::Error(format!(
+                    "Lost connection to station: {}",
+                    self.station.name
+                )));
+                break;
+            }
+
+            thread::sleep(Duration::from_secs(RECONNECT_DELAY_SECS));
+            if self.is_closed() {
+                break;
+            }
+
+            response = connect(&self.agent, &self.station.url).ok();
+        }
+
+        self.shared.buffer.lock().unwrap().finished = true;
+        self.shared.changed.notify_all();
+    }
+
+    /// Copies audio data from a connected stream into the shared buffer until
+    /// the connection drops or the reader is closed.
+    ///
+    /// Returns `true` if any audio data was received.
+    fn pump(&self, response: Response<Body>) -> bool {
+        let headers = response.headers();
+
+        let station_title = headers
+            .get("icy-name")
+            .and_then(|name| name.to_str().ok())
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .unwrap_or(&self.station.name)
+            .to_string();
+        let _ = self.event_tx.send(AppEvent::TitleChanged(station_title));
+
+        let metaint = headers
+            .get("icy-metaint")
+            .and_then(|metaint| metaint.to_str().ok())
+            .and_then(|metaint| metaint.trim().parse().ok());
+
+        let mut reader = IcyReader::new(response.into_body().into_reader(), metaint);
+        let mut chunk = [0u8; READ_CHUNK_SIZE];
+        let mut received = false;
+
+        loop {
+            let len = match reader.read(&mut chunk) {
+                Ok(0) | Err(_) => break,
+                Ok(len) => len,
+            };
+            received = true;
+
+            if let Some(title) = reader.take_stream_title() {
+                let _ = self.event_tx.send(AppEvent::TitleChanged(title));
+            }
+
+            let mut buffer = self.shared.buffer.lock().unwrap();
+            while buffer.data.len() >= MAX_BUFFERED_BYTES && !buffer.closed {
+                buffer = self.shared.changed.wait(buffer).unwrap();
+            }
+            if buffer.closed {
+                break;
+            }
+            buffer.data.extend(&chunk[..len]);
+            self.shared.changed.notify_all();
+        }
+
+        received
+    }
+
+    fn is_closed(&self) -> bool {
+        self.shared.buffer.lock().unwrap().closed
+    }
+}
+
+fn connect(agent: &Agent, url: &str) -> Result<Response<Body>> {
+    let response = agent.get(url).header("Icy-MetaData", "1").call()?;
+    Ok(response)
+}
+
+/// Separates ICY metadata blocks from the audio data they are interleaved
+/// with.
+///
+/// When a server honours the `Icy-MetaData` request header, a metadata block
+/// follows every `metaint` bytes of audio. Each block is a single length byte
+/// (in units of 16 bytes) followed by text such as
+/// `StreamTitle='Artist - Title';`.
+struct IcyReader<R: Read> {
+    inner: R,
+    metaint: Option<usize>,
+    until_metadata: usize,
+    stream_title: Option<String>,
+    title_changed: bool,
+}
+
+impl<R: Read> IcyReader<R> {
+    fn new(inner: R, metaint: Option<usize>) -> Self {
+        Self {
+            inner,
+            metaint: metaint.filter(|&metaint| metaint > 0),
+            until_metadata: metaint.unwrap_or(0),
+            stream_title: None,
+            title_changed: false,
+        }
+    }
+
+    /// Returns the stream title if it has changed since last asked.
+    fn take_stream_title(&mut self) -> Option<String> {
+        if !self.title_changed {
+            return None;
+        }
+        self.title_changed = false;
+        self.stream_title.clone()
+    }
+
+    fn read_metadata(&mut self) -> io::Result<()> {
+        let mut len = [0u8; 1];
+        self.inner.read_exact(&mut len)?;
+
+        let mut metadata = vec![0u8; len[0] as usize * ICY_METADATA_BLOCK];
+        self.inner.read_exact(&mut metadata)?;
+
+        // Most stations repeat the same title in every block
+        if let Some(title) = parse_stream_title(&metadata)
+            && self.stream_title.as_ref() != Some(&title)
+        {
+            self.stream_title = Some(title);
+            self.title_changed = true;
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for IcyReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some(metaint) = self.metaint else {
+            return self.inner.read(buf);
+        };
+
+        if self.until_metadata == 0 {
+            self.read_metadata()?;
+            self.until_metadata = metaint;
+        }
+
+        let max = buf.len().min(self.until_metadata);
+        let len = self.inner.read(&mut buf[..max])?;
+        self.until_metadata -= len;
+
+        Ok(len)
+    }
+}
+
+/// Extracts the `StreamTitle` value from an ICY metadata block.
+///
+/// An empty title is ignored, since some stations clear the title between
+/// tracks.
+fn parse_stream_title(metadata: &[u8]) -> Option<String> {
+    const START: &str = "StreamTitle='";
+
+    let text = String::from_utf8_lossy(metadata);
+    let text = text.trim_end_matches('\0');
+
+    let start = text.find(START)? + START.len();
+    let rest = &text[start..];
+    let end = rest.find("';").unwrap_or(rest.len());
+
+    let title = rest[..end].trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Cursor, Write},
+        net::TcpListener,
+        sync::mpsc,
+    };
+
+    use rodio::{Decoder, Source};
+
+    use super::*;
+
+    /// Builds an ICY metadata block, including its length byte.
+    fn metadata_block(text: &str) -> Vec<u8> {
+        let blocks = text.len().div_ceil(ICY_METADATA_BLOCK);
+        let mut block = vec![blocks as u8];
+        block.extend(text.as_bytes());
+        block.resize(1 + blocks * ICY_METADATA_BLOCK, 0);
+        block
+    }
+
+    /// Serves a single HTTP response with the given headers and body, then
+    /// closes the connection.
+    ///
+    /// Returns the URL to request and a receiver for the request headers.
+    fn serve_once(headers: &str, body: Vec<u8>) -> (String, mpsc::Receiver<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/stream", listener.local_addr().unwrap());
+        let headers = headers.to_string();
+        let (request_tx, request_rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (mut socket, _) = listener.accept().unwrap();
+
+            let request: Vec<String> = BufReader::new(&socket)
+                .lines()
+                .map_while(|line| line.ok())
+                .take_while(|line| !line.is_empty())
+                .collect();
+            let _ = request_tx.send(request);
+
+            let _ = socket.write_all(format!("HTTP/1.0 200 OK\r\n{headers}\r\n").as_bytes());
+            let _ = socket.write_all(&body);
+        });
+
+        (url, request_rx)
+    }
+
+    fn station(url: String) -> Station {
+        Station {
+            id: 1,
+            name: "Station".to_string(),
+            url,
+        }
+    }
+
+    /// Creates a reader over data that has already been fully fetched.
+    fn fetched_reader(data: &[u8]) -> StreamReader {
+        let shared = Arc::new(Shared::default());
+        {
+            let mut buffer = shared.buffer.lock().unwrap();
+            buffer.data.extend(data);
+            buffer.finished = true;
+        }
+        StreamReader {
+            shared,
+            position: 0,
+            head: Vec::new(),
+            rewindable: true,
+        }
+    }
+
+    #[test]
+    fn parses_stream_title() {
+        assert_eq!(
+            parse_stream_title(b"StreamTitle='Artist - Title';StreamUrl='';\0\0\0"),
+            Some("Artist - Title".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_stream_title_containing_quotes() {
+        assert_eq!(
+            parse_stream_title(b"StreamTitle='Don't Stop';"),
+            Some("Don't Stop".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_unterminated_stream_title() {
+        assert_eq!(parse_stream_title(b"StreamTitle='Title\0\0"), Some("Title".to_string()));
+    }
+
+    #[test]
+    fn ignores_empty_or_missing_stream_title() {
+        assert_eq!(parse_stream_title(b"StreamTitle='';\0\0"), None);
+        assert_eq!(parse_stream_title(b"StreamTitle='  ';"), None);
+        assert_eq!(parse_stream_title(b"StreamUrl='http://example.com';"), None);
+        assert_eq!(parse_stream_title(b""), None);
+    }
+
+    #[test]
+    fn icy_reader_strips_metadata() {
+        let mut data = b"abcd".to_vec();
+        data.extend(metadata_block("StreamTitle='One';"));
+        data.extend(b"efgh");
+        data.push(0);
+        data.extend(b"ij");
+
+        let mut reader = IcyReader::new(Cursor::new(data), Some(4));
+        let mut audio = Vec::new();
+        reader.read_to_end(&mut audio).unwrap();
+
+        assert_eq!(audio, b"abcdefghij");
+        assert_eq!(reader.take_stream_title(), Some("One".to_string()));
+    }
+
+    #[test]
+    fn icy_reader_reports_only_title_changes() {
+        let mut data = b"ab".to_vec();
+        data.extend(metadata_block("StreamTitle='One';"));
+        data.extend(b"cd");
+        data.extend(metadata_block("StreamTitle='One';"));
+        data.extend(b"ef");
+        data.extend(metadata_block("StreamTitle='Two';"));
+        data.extend(b"gh");
+
+        let mut reader = IcyReader::new(Cursor::new(data), Some(2));
+        let mut buf = [0u8; 2];
+        let mut titles = Vec::new();
+        for _ in 0..4 {
+            reader.read_exact(&mut buf).unwrap();
+            titles.extend(reader.take_stream_title());
+        }
+
+        assert_eq!(titles, ["One", "Two"]);
+    }
+
+    #[test]
+    fn icy_reader_passes_through_without_metaint() {
+        let data = b"StreamTitle='One';".to_vec();
+
+        let mut reader = IcyReader::new(Cursor::new(data.clone()), None);
+        let mut audio = Vec::new();
+        reader.read_to_end(&mut audio).unwrap();
+
+        assert_eq!(audio, data);
+        assert_eq!(reader.take_stream_title(), None);
+    }
+
+    #[test]
+    fn stream_reader_rewinds_within_head() {
+        let data: Vec<u8> = (0..=255).collect();
+        let mut reader = fetched_reader(&data);
+
+        let mut buf = [0u8; 16];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(reader.stream_position().unwrap(), 16);
+
+        assert_eq!(reader.seek(SeekFrom::Start(4)).unwrap(), 4);
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf[..], data[4..20]);
+
+        assert_eq!(reader.seek(SeekFrom::Current(-20)).unwrap(), 0);
+        let mut all = Vec::new();
+        reader.read_to_end(&mut all).unwrap();
+        assert_eq!(all, data);
+    }
+
+    #[test]
+    fn stream_reader_can_not_seek_ahead_or_from_end() {
+        let mut reader = fetched_reader(&[0u8; 64]);
+
+        let mut buf = [0u8; 16];
+        reader.read_exact(&mut buf).unwrap();
+
+        assert!(reader.seek(SeekFrom::Start(32)).is_err());
+        assert!(reader.seek(SeekFrom::Current(-32)).is_err());
+        assert!(reader.seek(SeekFrom::End(0)).is_err());
+        assert_eq!(reader.stream_position().unwrap(), 16);
+    }
+
+    #[test]
+    fn stream_reader_stops_rewinding_past_limit() {
+        let mut reader = fetched_reader(&vec![0u8; REWIND_LIMIT + 1]);
+
+        std::io::copy(&mut reader, &mut std::io::sink()).unwrap();
+
+        assert!(reader.seek(SeekFrom::Start(0)).is_err());
+    }
+
+    #[test]
+    fn stream_reader_reads_icy_stream() {
+        let audio: Vec<u8> = (0..3000).map(|i| i as u8).collect();
+        let mut body = audio[..1000].to_vec();
+        body.extend(metadata_block("StreamTitle='Artist - Title';"));
+        body.extend(&audio[1000..2000]);
+        body.push(0);
+        body.extend(&audio[2000..]);
+
+        let (url, request_rx) = serve_once("icy-name: Test FM\r\nicy-metaint: 1000\r\n", body);
+        let (event_tx, event_rx) = mpsc::channel();
+
+        let mut reader = StreamReader::open(&station(url), event_tx).unwrap();
+        let mut received = vec![0u8; audio.len()];
+        reader.read_exact(&mut received).unwrap();
+        assert_eq!(received, audio);
+
+        let request = request_rx.recv().unwrap();
+        assert!(request.iter().any(|line| line.eq_ignore_ascii_case("icy-metadata: 1")));
+
+        let mut titles = Vec::new();
+        while let Ok(AppEvent::TitleChanged(title)) = event_rx.recv_timeout(Duration::from_secs(1)) {
+            titles.push(title);
+        }
+        assert_eq!(titles, ["Test FM", "Artist - Title"]);
+    }
+
+    #[test]
+    fn stream_reader_falls_back_to_station_name() {
+        let (url, _request_rx) = serve_once("", vec![1, 2, 3, 4]);
+        let (event_tx, event_rx) = mpsc::channel();
+
+        let mut reader = StreamReader::open(&station(url), event_tx).unwrap();
+        let mut received = [0u8; 4];
+        reader.read_exact(&mut received).unwrap();
+
+        assert!(matches!(
+            event_rx.recv_timeout(Duration::from_secs(1)),
+            Ok(AppEvent::TitleChanged(title)) if title == "Station"
+        ));
+    }
+
+    #[test]
+    fn stream_reader_reports_unreachable_station() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/stream", listener.local_addr().unwrap());
+        drop(listener);
+
+        let (event_tx, _event_rx) = mpsc::channel();
+        assert!(StreamReader::open(&station(url), event_tx).is_err());
+    }
+
+    #[test]
+    fn stream_can_be_decoded() {
+        let samples: Vec<i16> = (0..800).map(|i| (i * 40) as i16).collect();
+
+        // Minimal 8kHz mono 16-bit PCM WAV file
+        let data_len = (samples.len() * 2) as u32;
+        let mut wav = Vec::new();
+        wav.extend(b"RIFF");
+        wav.extend((36 + data_len).to_le_bytes());
+        wav.extend(b"WAVEfmt ");
+        wav.extend(16u32.to_le_bytes());
+        wav.extend(1u16.to_le_bytes());
+        wav.extend(1u16.to_le_bytes());
+        wav.extend(8000u32.to_le_bytes());
+        wav.extend(16000u32.to_le_bytes());
+        wav.extend(2u16.to_le_bytes());
+        wav.extend(16u16.to_le_bytes());
+        wav.extend(b"data");
+        wav.extend(data_len.to_le_bytes());
+        for sample in &samples {
+            wav.extend(sample.to_le_bytes());
+        }
+
+        let (url, _request_rx) = serve_once("icy-metaint: 100\r\n", {
+            let mut body = Vec::new();
+            for chunk in wav.chunks(100) {
+                body.extend(chunk);
+                body.push(0);
+            }
+            body
+        });
+        let (event_tx, _event_rx) = mpsc::channel();
+
+        let reader = StreamReader::open(&station(url), event_tx).unwrap();
+        let decoder = Decoder::new(reader).unwrap();
+
+        assert_eq!(decoder.channels(), 1);
+        assert_eq!(decoder.sample_rate(), 8000);
+        let decoded: Vec<i16> = decoder.take(samples.len()).collect();
+        assert_eq!(decoded, samples);
+    }
+}
@@ -0,0 +1,267 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Podcast feed parsing.
+//!
+//! Both RSS 2.0 and Atom feeds are supported. Only entries with an audio
+//! enclosure are of interest, anything else in the feed is ignored.
+
+use anyhow::{Result, anyhow};
+use roxmltree::{Document, Node};
+
+use crate::util::date::{parse_rfc2822, parse_rfc3339};
+
+/// A parsed podcast feed.
+#[derive(Debug)]
+pub(crate) struct Feed {
+    pub(crate) title: String,
+    pub(crate) items: Vec<FeedItem>,
+}
+
+/// A single episode listed in a podcast feed.
+#[derive(Debug)]
+pub(crate) struct FeedItem {
+    /// Identifies the episode within the feed, falling back to the enclosure
+    /// URL for feeds that do not provide one.
+    pub(crate) guid: String,
+    pub(crate) title: String,
+    pub(crate) url: String,
+    pub(crate) published_at: Option<i64>,
+}
+
+/// Parses the XML text of an RSS or Atom feed.
+///
+/// # Errors
+///
+/// Returns an error if the text is not well-formed XML, or is neither an RSS
+/// nor an Atom feed.
+pub(crate) fn parse_feed(xml: &str) -> Result<Feed> {
+    let doc = Document::parse(xml)?;
+    let root = doc.root_element();
+
+    match root.tag_name().name() {
+        "rss" => {
+            let channel = child(root, "channel").ok_or_else(|| anyhow!("RSS feed has no channel"))?;
+            Ok(parse_rss(channel))
+        }
+        "feed" => Ok(parse_atom(root)),
+        name => Err(anyhow!("Unrecognised feed format: {}", name)),
+    }
+}
+
+fn parse_rss(channel: Node) -> Feed {
+    let items = children(channel, "item")
+        .filter_map(|item| {
+            let url = child(item, "enclosure")?.attribute("url")?.trim().to_string();
+            let guid = child_text(item, "guid").unwrap_or_else(|| url.clone());
+            let title = child_text(item, "title").unwrap_or_else(|| url.clone());
+            let published_at = child_text(item, "pubDate").and_then(|date| parse_rfc2822(&date));
+
+            Some(FeedItem {
+                guid,
+                title,
+                url,
+                published_at,
+            })
+        })
+        .collect();
+
+    Feed {
+        title: child_text(channel, "title").unwrap_or_default(),
+        items,
+    }
+}
+
+fn parse_atom(feed: Node) -> Feed {
+    let items = children(feed, "entry")
+        .filter_map(|entry| {
+            let url = children(entry, "link")
+                .find(|link| link.attribute("rel") == Some("enclosure"))?
+                .attribute("href")?
+                .trim()
+                .to_string();
+            let guid = child_text(entry, "id").unwrap_or_else(|| url.clone());
+            let title = child_text(entry, "title").unwrap_or_else(|| url.clone());
+            let published_at = child_text(entry, "published")
+                .or_else(|| child_text(entry, "updated"))
+                .and_then(|date| parse_rfc3339(&date));
+
+            Some(FeedItem {
+                guid,
+                title,
+                url,
+                published_at,
+            })
+        })
+        .collect();
+
+    Feed {
+        title: child_text(feed, "title").unwrap_or_default(),
+        items,
+    }
+}
+
+/// Finds the child elements with the given name.
+///
+/// Only children in the same namespace as the parent match, so that extension
+/// elements such as `itunes:title` are not mistaken for the standard ones.
+fn children<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    let namespace = node.tag_name().namespace();
+    node.children().filter(move |child| {
+        child.is_element()
+            && child.tag_name().name() == name
+            && child.tag_name().namespace() == namespace
+    })
+}
+
+/// Finds the first child element with the given name.
+fn child<'a, 'input: 'a>(node: Node<'a, 'input>, name: &'a str) -> Option<Node<'a, 'input>> {
+    children(node, name).next()
+}
+
+/// Gets the trimmed text of the first child element with the given local
+/// name, if it is not empty.
+fn child_text(node: Node, name: &str) -> Option<String> {
+    let text: String = child(node, name)?
+        .descendants()
+        .filter(|node| node.is_text())
+        .filter_map(|node| node.text())
+        .collect();
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+  <channel>
+    <title> A Podcast </title>
+    <itunes:title>Not This</itunes:title>
+    <item>
+      <guid>episode-2</guid>
+      <title>Second <![CDATA[Episode]]></title>
+      <itunes:title>Not This</itunes:title>
+      <pubDate>Fri, 01 Mar 2024 12:30:15 +0000</pubDate>
+      <enclosure url=" http://example.com/2.mp3 " type="audio/mpeg"/>
+    </item>
+    <item>
+      <title>No Enclosure</title>
+    </item>
+    <item>
+      <title>No Enclosure URL</title>
+      <enclosure type="audio/mpeg"/>
+    </item>
+    <item>
+      <pubDate>sometime</pubDate>
+      <enclosure url="http://example.com/1.mp3"/>
+    </item>
+  </channel>
+</rss>"#;
+
+    const ATOM: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>An Atom Podcast</title>
+  <entry>
+    <id>urn:episode:2</id>
+    <title>Second Episode</title>
+    <published>2024-03-01T12:30:15Z</published>
+    <updated>2024-03-02T00:00:00Z</updated>
+    <link rel="alternate" href="http://example.com/2.html"/>
+    <link rel="enclosure" href="http://example.com/2.mp3"/>
+  </entry>
+  <entry>
+    <title>No Enclosure</title>
+    <link rel="alternate" href="http://example.com/3.html"/>
+  </entry>
+  <entry>
+    <title>No Enclosure Link</title>
+    <link rel="enclosure"/>
+  </entry>
+  <entry>
+    <updated>2024-02-29</updated>
+    <link rel="enclosure" href="http://example.com/1.mp3"/>
+  </entry>
+  <entry>
+    <title>Bad Date</title>
+    <published>last week</published>
+    <link rel="enclosure" href="http://example.com/0.mp3"/>
+  </entry>
+</feed>"#;
+
+    #[test]
+    fn parses_rss() {
+        let feed = parse_feed(RSS).unwrap();
+
+        assert_eq!(feed.title, "A Podcast");
+        assert_eq!(feed.items.len(), 2);
+
+        let item = &feed.items[0];
+        assert_eq!(item.guid, "episode-2");
+        assert_eq!(item.title, "Second Episode");
+        assert_eq!(item.url, "http://example.com/2.mp3");
+        assert_eq!(item.published_at, Some(1_709_296_215));
+    }
+
+    #[test]
+    fn rss_item_falls_back_to_enclosure_url() {
+        let feed = parse_feed(RSS).unwrap();
+
+        let item = &feed.items[1];
+        assert_eq!(item.guid, "http://example.com/1.mp3");
+        assert_eq!(item.title, "http://example.com/1.mp3");
+        assert_eq!(item.published_at, None);
+    }
+
+    #[test]
+    fn parses_atom() {
+        let feed = parse_feed(ATOM).unwrap();
+
+        assert_eq!(feed.title, "An Atom Podcast");
+        assert_eq!(feed.items.len(), 3);
+
+        let item = &feed.items[0];
+        assert_eq!(item.guid, "urn:episode:2");
+        assert_eq!(item.title, "Second Episode");
+        assert_eq!(item.url, "http://example.com/2.mp3");
+        assert_eq!(item.published_at, Some(1_709_296_215));
+    }
+
+    #[test]
+    fn atom_entry_falls_back_to_enclosure_link_and_updated() {
+        let feed = parse_feed(ATOM).unwrap();
+
+        let item = &feed.items[1];
+        assert_eq!(item.guid, "http://example.com/1.mp3");
+        assert_eq!(item.title, "http://example.com/1.mp3");
+        assert_eq!(item.published_at, Some(1_709_164_800));
+
+        let item = &feed.items[2];
+        assert_eq!(item.title, "Bad Date");
+        assert_eq!(item.published_at, None);
+    }
+
+    #[test]
+    fn rejects_other_documents() {
+        assert!(parse_feed("not xml").is_err());
+        assert!(parse_feed("<rss version=\"2.0\"/>").is_err());
+        assert!(parse_feed("<html><body/></html>").is_err());
+    }
+}
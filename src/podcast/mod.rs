@@ -0,0 +1,192 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Podcast feed fetching and episode downloads.
+//!
+//! These operations block on the network, so are only ever invoked from the
+//! background task worker.
+
+mod feed;
+
+pub(crate) use feed::Feed;
+
+use std::{
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+
+use crate::{model::Episode, util::format::format_date};
+
+/// Upper bound on the size of a feed document.
+const MAX_FEED_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Longest file or directory name used for downloads, before the extension.
+const MAX_FILENAME_LEN: usize = 100;
+
+const DEFAULT_EXTENSION: &str = "mp3";
+
+/// Fetches and parses a podcast feed.
+///
+/// # Errors
+///
+/// Returns an error if the feed can not be fetched, or can not be parsed.
+pub(crate) fn fetch_feed(url: &str) -> Result<Feed> {
+    let xml = ureq::get(url)
+        .call()
+        .with_context(|| format!("Failed to fetch feed: {}", url))?
+        .body_mut()
+        .with_config()
+        .limit(MAX_FEED_BYTES)
+        .read_to_string()?;
+
+    feed::parse_feed(&xml).with_context(|| format!("Failed to parse feed: {}", url))
+}
+
+/// Downloads an episode into a sub-directory of the podcast directory named
+/// for its podcast.
+///
+/// The episode is first downloaded to a temporary file, so that an interrupted
+/// download never leaves a truncated episode behind.
+///
+/// # Arguments
+///
+/// * `episode` - The episode to download.
+/// * `podcast_dir` - The directory podcasts are downloaded to.
+///
+/// # Returns
+///
+/// Returns the path to the downloaded file.
+pub(crate) fn download_episode(episode: &Episode, podcast_dir: &str) -> Result<PathBuf> {
+    let dir = Path::new(podcast_dir).join(sanitise_filename(&episode.podcast_title));
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+
+    let name = match episode.published_at {
+        Some(published_at) => format!("{} {}", format_date(published_at), episode.title),
+        None => episode.title.clone(),
+    };
+    let path = dir.join(format!(
+        "{}.{}",
+        sanitise_filename(&name),
+        url_extension(&episode.url)
+    ));
+    let part_path = path.with_extension("part");
+
+    let response = ureq::get(&episode.url)
+        .call()
+        .with_context(|| format!("Failed to download episode: {}", episode.url))?;
+
+    let mut reader = response.into_body().into_reader();
+    let mut file = File::create(&part_path)
+        .with_context(|| format!("Failed to create file: {}", part_path.display()))?;
+
+    if let Err(e) = io::copy(&mut reader, &mut file) {
+        let _ = fs::remove_file(&part_path);
+        return Err(e).context("Episode download failed");
+    }
+
+    fs::rename(&part_path, &path)?;
+
+    Ok(path)
+}
+
+/// Replaces characters that are not valid in file names on common platforms.
+fn sanitise_filename(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .take(MAX_FILENAME_LEN)
+        .collect();
+
+    let name = name.trim_matches(|c: char| c == '.' || c.is_whitespace());
+    if name.is_empty() {
+        "Untitled".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Gets the file extension from the path of a URL, ignoring any query string.
+fn url_extension(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let file = path.rsplit('/').next().unwrap_or(path);
+
+    file.rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .filter(|ext| (1..=4).contains(&ext.len()) && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+        .unwrap_or_else(|| DEFAULT_EXTENSION.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_server;
+
+    fn episode(url: String) -> Episode {
+        Episode {
+            id: 1,
+            podcast_id: 1,
+            podcast_title: "Test: Podcast".to_string(),
+            feed_url: "http://example.com/feed.xml".to_string(),
+            guid: "episode-1".to_string(),
+            title: "First Episode".to_string(),
+            url,
+            published_at: None,
+            filename: None,
+            durable_id: None,
+            played: false,
+        }
+    }
+
+    #[test]
+    fn sanitises_filenames() {
+        assert_eq!(sanitise_filename("A/B: C?"), "A_B_ C_");
+        assert_eq!(sanitise_filename(" ..Name.. "), "Name");
+        assert_eq!(sanitise_filename("..."), "Untitled");
+        assert_eq!(sanitise_filename(&"x".repeat(200)).len(), MAX_FILENAME_LEN);
+    }
+
+    #[test]
+    fn gets_url_extension() {
+        assert_eq!(url_extension("http://example.com/a/episode.M4A?token=1.2"), "m4a");
+        assert_eq!(url_extension("http://example.com/episode"), "mp3");
+        assert_eq!(url_extension("http://example.com/episode.download-now"), "mp3");
+        assert_eq!(url_extension("http://example.com/v1.2/episode#t=10"), "mp3");
+    }
+
+    #[test]
+    fn failed_download_leaves_no_file() {
+        let (url, _request_rx) = test_server::serve(|_| (404, Vec::new()));
+        let podcast_dir =
+            std::env::temp_dir().join(format!("chooui-failed-download-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&podcast_dir);
+
+        let episode = episode(format!("{}/episode.mp3", url));
+        let result = download_episode(&episode, podcast_dir.to_str().unwrap());
+
+        assert!(result.is_err());
+        let files = fs::read_dir(podcast_dir.join("Test_ Podcast")).unwrap().count();
+        assert_eq!(files, 0);
+
+        fs::remove_dir_all(&podcast_dir).unwrap();
+    }
+}
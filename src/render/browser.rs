@@ -16,7 +16,8 @@
 //! Render the media browser interface.
 //!
 //! This module provides renders the visual representation of the music
-//! catalog, organised via artist, artist albums, and album tracks.
+//! catalog, organised via artist, artist albums, and album tracks, or via one
+//! of the other browse hierarchies.
 
 use ratatui::{
     Frame,
@@ -26,11 +27,18 @@ use ratatui::{
 };
 
 use crate::{
-    browser::{MediaBrowser, MediaBrowserPane},
+    browser::{BrowseHierarchy, MediaBrowser, MediaBrowserPane},
+    render::cover::CoverArt,
     theme::Theme,
 };
 
-pub(crate) fn draw(f: &mut Frame, area: Rect, browser: &mut MediaBrowser, theme: &Theme) {
+pub(crate) fn draw(
+    f: &mut Frame,
+    area: Rect,
+    browser: &mut MediaBrowser,
+    cover_art: &mut CoverArt,
+    theme: &Theme,
+) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(2), Constraint::Min(0)])
@@ -40,70 +48,198 @@ pub(crate) fn draw(f: &mut Frame, area: Rect, browser: &mut MediaBrowser, theme:
         .borders(Borders::BOTTOM)
         .padding(Padding::horizontal(1));
 
-    let header_text = "Media Browser";
+    let header_text = format!("Media Browser | {}", browser.hierarchy.title());
 
     let header = Paragraph::new(header_text).block(header_block);
 
-    draw_browser(f, chunks[1], browser);
+    draw_browser(f, chunks[1], browser, cover_art);
 
     f.render_widget(header, chunks[0]);
 }
 
-/// Renders the media browser widget including artist, album, and track info.
-fn draw_browser(f: &mut Frame, area: Rect, browser: &mut MediaBrowser) {
-    let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
+/// Renders the media browser widget, with a pane for each level of the
+/// current hierarchy.
+fn draw_browser(f: &mut Frame, area: Rect, browser: &mut MediaBrowser, cover_art: &mut CoverArt) {
+    let widths = match browser.hierarchy {
+        BrowseHierarchy::Artist | BrowseHierarchy::Genre => vec![
             Constraint::Percentage(25),
             Constraint::Percentage(30),
             Constraint::Percentage(45),
-        ])
-        .split(area);
+        ],
+        BrowseHierarchy::Decade => vec![
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
+            Constraint::Percentage(60),
+        ],
+        BrowseHierarchy::RecentlyAdded => {
+            vec![Constraint::Percentage(55), Constraint::Percentage(45)]
+        }
+    };
 
-    let artist_items: Vec<ListItem> = browser
-        .artists
-        .iter()
-        .map(|a| ListItem::new(a.name.as_str()))
-        .collect();
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(widths)
+        .split(area);
 
-    render_list(
-        f,
-        chunks[0],
-        " Artists ",
-        artist_items,
-        &mut browser.artists_state,
-        browser.active_pane == MediaBrowserPane::Artist,
-    );
+    for (&pane, &chunk) in browser.hierarchy.panes().iter().zip(chunks.iter()) {
+        let is_active = browser.active_pane == pane;
+        match pane {
+            MediaBrowserPane::Genre => {
+                let items = browser
+                    .genres
+                    .iter()
+                    .map(|g| ListItem::new(g.name.as_str()))
+                    .collect();
+                render_list(
+                    f,
+                    chunk,
+                    " Genres ",
+                    items,
+                    &mut browser.genres_state,
+                    is_active,
+                );
+            }
+            MediaBrowserPane::Decade => {
+                let items = browser
+                    .decades
+                    .iter()
+                    .map(|d| ListItem::new(format!("{}s", d)))
+                    .collect();
+                render_list(
+                    f,
+                    chunk,
+                    " Decades ",
+                    items,
+                    &mut browser.decades_state,
+                    is_active,
+                );
+            }
+            MediaBrowserPane::Year => {
+                let items = browser
+                    .years
+                    .iter()
+                    .map(|y| ListItem::new(y.to_string()))
+                    .collect();
+                render_list(
+                    f,
+                    chunk,
+                    " Years ",
+                    items,
+                    &mut browser.years_state,
+                    is_active,
+                );
+            }
+            MediaBrowserPane::Artist => {
+                let items = browser
+                    .artists
+                    .iter()
+                    .map(|a| ListItem::new(a.name.as_str()))
+                    .collect();
+                render_list(
+                    f,
+                    chunk,
+                    " Artists ",
+                    items,
+                    &mut browser.artists_state,
+                    is_active,
+                );
+            }
+            MediaBrowserPane::Album => draw_albums(f, chunk, browser, cover_art, is_active),
+            MediaBrowserPane::Track => draw_tracks(f, chunk, browser, is_active),
+        }
+    }
+}
 
+/// Renders the album pane, with the selected album's cover underneath.
+fn draw_albums(
+    f: &mut Frame,
+    area: Rect,
+    browser: &mut MediaBrowser,
+    cover_art: &mut CoverArt,
+    is_active: bool,
+) {
+    // Albums are listed with their artist unless there is a pane for artists
+    let with_artist = !browser.shows_pane(MediaBrowserPane::Artist);
     let album_items: Vec<ListItem> = browser
         .albums
         .iter()
-        .map(|a| ListItem::new(a.title.as_str()))
+        .map(|a| match with_artist {
+            true => ListItem::new(format!("{} - {}", a.artist_name, a.title)),
+            false => ListItem::new(a.title.as_str()),
+        })
         .collect();
 
+    // The selected album's cover goes underneath the albums, taking at most
+    // half the height of the pane
+    let cover_hash = browser
+        .albums_state
+        .selected()
+        .and_then(|index| browser.albums.get(index))
+        .and_then(|album| album.cover_hash.clone())
+        .filter(|_| cover_art.is_enabled());
+
+    let album_chunks = if cover_hash.is_some() {
+        let cover_height = (area.width.saturating_sub(2) / 2 + 2).min(area.height / 2);
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(cover_height)])
+            .split(area)
+    } else {
+        Layout::default()
+            .constraints([Constraint::Min(0)])
+            .split(area)
+    };
+
+    let title = match browser.hierarchy {
+        BrowseHierarchy::RecentlyAdded => " Recently Added ",
+        _ => " Albums ",
+    };
+
     render_list(
         f,
-        chunks[1],
-        " Albums ",
+        album_chunks[0],
+        title,
         album_items,
         &mut browser.albums_state,
-        browser.active_pane == MediaBrowserPane::Album,
+        is_active,
     );
 
+    if let Some(hash) = cover_hash {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let cover_area = block.inner(album_chunks[1]);
+        f.render_widget(block, album_chunks[1]);
+        cover_art.draw(f, cover_area, &hash);
+    }
+}
+
+/// Renders the track pane.
+fn draw_tracks(f: &mut Frame, area: Rect, browser: &mut MediaBrowser, is_active: bool) {
+    // Tracks are only numbered with their disc on albums of more than one disc
     let width = browser.tracks.len().to_string().len().max(2);
+    let multi_disc = browser
+        .tracks
+        .iter()
+        .any(|t| t.disc_number.unwrap_or(1) > 1);
     let track_items: Vec<ListItem> = browser
         .tracks
         .iter()
-        .map(|t| ListItem::new(format!("{:0width$} {}", t.track_number, t.title)))
+        .map(|t| match t.disc_number {
+            Some(disc) if multi_disc => {
+                ListItem::new(format!("{}-{:0width$} {}", disc, t.track_number, t.title))
+            }
+            _ => ListItem::new(format!("{:0width$} {}", t.track_number, t.title)),
+        })
         .collect();
 
     render_list(
         f,
-        chunks[2],
+        area,
         " Tracks ",
         track_items,
         &mut browser.tracks_state,
-        browser.active_pane == MediaBrowserPane::Track,
+        is_active,
     );
 }
 
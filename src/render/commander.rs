@@ -17,6 +17,9 @@
 //!
 //! This module provides renders the visual representation of the
 //! command-line, the current text, the cursor and so on.
+//!
+//! When the command-line is not in use, it shows the latest status message
+//! instead.
 
 use ratatui::{
     Frame,
@@ -36,14 +39,16 @@ pub(crate) fn draw_commander(f: &mut Frame, area: Rect, app: &App) {
         .horizontal_margin(1)
         .split(area);
 
-    f.render_widget(
-        Paragraph::new(commander.input.value()).style(
-            Style::default()
-                .fg(app.theme.commander_colour)
-                .bg(app.theme.gauge_track_colour),
-        ),
-        container[0],
-    );
+    let style = Style::default().bg(app.theme.gauge_track_colour);
+
+    let paragraph = match app.status.message() {
+        Some(message) if !commander.active() => {
+            Paragraph::new(message).style(style.fg(app.theme.accent_colour))
+        }
+        _ => Paragraph::new(commander.input.value()).style(style.fg(app.theme.commander_colour)),
+    };
+
+    f.render_widget(paragraph, container[0]);
 
     if commander.active() {
         let cursor_x = container[0].x + commander.input.cursor() as u16;
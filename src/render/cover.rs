@@ -0,0 +1,435 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//! Render album art.
+//!
+//! Covers are drawn with whichever [`GraphicsProtocol`] the terminal speaks.
+//! Half blocks are ordinary cells, written into the frame like any other
+//! widget. The other protocols draw images over the top of the cells, so while
+//! rendering the cells are only reserved, and the images themselves are
+//! written out once the frame has been drawn, by [`CoverArt::flush`].
+
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use crossterm::{
+    cursor::{MoveTo, RestorePosition, SavePosition},
+    queue, terminal,
+};
+use image::{
+    DynamicImage, ExtendedColorType, ImageEncoder, RgbImage, codecs::png::PngEncoder,
+    imageops::FilterType,
+};
+use ratatui::{Frame, buffer::Buffer, layout::Rect, style::Color};
+
+use crate::{covers, util::term::GraphicsProtocol};
+
+/// Size of a cell in pixels, assumed when the terminal does not report it.
+const DEFAULT_CELL_SIZE: (u16, u16) = (8, 16);
+
+/// Cached images are all dropped once there are this many.
+const CACHE_LIMIT: usize = 16;
+
+/// Kitty image data is sent in chunks of at most this many bytes.
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+/// Sixel images are quantised to a 6x6x6 colour cube.
+const SIXEL_LEVELS: u32 = 6;
+
+/// A cover drawn at a position on the screen.
+#[derive(Debug, Clone, PartialEq)]
+struct Placement {
+    hash: String,
+    area: Rect,
+}
+
+/// Draws album covers, caching the thumbnails and their resized pixels.
+pub(crate) struct CoverArt {
+    protocol: Option<GraphicsProtocol>,
+
+    /// Size of a cell in pixels, if the terminal reports it.
+    cell_size: Option<(u16, u16)>,
+
+    /// Decoded thumbnails by hash, `None` if the thumbnail could not be read.
+    images: HashMap<String, Option<DynamicImage>>,
+
+    /// Thumbnails resized to the pixel size they were last drawn at.
+    resized: HashMap<(String, u32, u32), RgbImage>,
+
+    /// Images reserved in the frame being drawn.
+    pending: Vec<Placement>,
+
+    /// Images currently on the screen.
+    shown: Vec<Placement>,
+}
+
+impl CoverArt {
+    /// Creates the renderer, drawing with the given protocol, or not drawing at
+    /// all if there is none.
+    pub(crate) fn new(protocol: Option<GraphicsProtocol>) -> Self {
+        Self {
+            protocol,
+            cell_size: query_cell_size(),
+            images: HashMap::new(),
+            resized: HashMap::new(),
+            pending: Vec::new(),
+            shown: Vec::new(),
+        }
+    }
+
+    /// Whether covers are drawn at all.
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.protocol.is_some()
+    }
+
+    /// Forgets what is on the screen, so every image is written again with
+    /// the next frame.
+    ///
+    /// This must be called when the terminal is resized, since the terminal
+    /// is cleared and the size of a cell may have changed.
+    pub(crate) fn invalidate(&mut self) {
+        self.cell_size = query_cell_size();
+        self.shown.clear();
+
+        // Give thumbnails that could not be read another chance, they may
+        // have been cached since
+        self.images.retain(|_, image| image.is_some());
+    }
+
+    /// Draws the cover with the given thumbnail hash, as large as it fits
+    /// within the area and centred horizontally.
+    ///
+    /// Nothing is drawn if the thumbnail can not be read.
+    pub(crate) fn draw(&mut self, f: &mut Frame, area: Rect, hash: &str) {
+        let Some(protocol) = self.protocol() else {
+            return;
+        };
+        let Some((width, height)) = self.load(hash) else {
+            return;
+        };
+        if area.is_empty() {
+            return;
+        }
+
+        let area = self.fit(width, height, area);
+        let buffer = f.buffer_mut();
+
+        if protocol == GraphicsProtocol::HalfBlocks {
+            let Some(pixels) = self.resize(hash, area.width as u32, area.height as u32 * 2) else {
+                return;
+            };
+            for y in 0..area.height {
+                for x in 0..area.width {
+                    let top = pixels.get_pixel(x as u32, y as u32 * 2);
+                    let bottom = pixels.get_pixel(x as u32, y as u32 * 2 + 1);
+                    buffer[(area.x + x, area.y + y)]
+                        .set_symbol("▀")
+                        .set_fg(Color::Rgb(top[0], top[1], top[2]))
+                        .set_bg(Color::Rgb(bottom[0], bottom[1], bottom[2]));
+                }
+            }
+        } else {
+            // Leave the cells for the image to be drawn over once the rest of
+            // the frame has been written
+            for position in area.positions() {
+                buffer[position].reset();
+                buffer[position].set_skip(true);
+            }
+            self.pending.push(Placement {
+                hash: hash.to_string(),
+                area,
+            });
+        }
+    }
+
+    /// Writes the images reserved in the frame just drawn to the terminal.
+    ///
+    /// Images that were already on the screen in the same place are not
+    /// written again. Removed sixel and iTerm2 images are simply overwritten
+    /// by the cells drawn in their place, but kitty images sit on top of the
+    /// text, so with kitty every image is deleted and the remaining ones
+    /// written again whenever anything changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the terminal fails.
+    pub(crate) fn flush(&mut self, buffer: &Buffer) -> io::Result<()> {
+        let mut placements = std::mem::take(&mut self.pending);
+
+        // Anything drawn over an image afterwards, such as a popup, hides it
+        placements.retain(|placement| {
+            placement
+                .area
+                .positions()
+                .all(|position| buffer.cell(position).is_some_and(|cell| cell.skip))
+        });
+
+        if placements == self.shown {
+            return Ok(());
+        }
+
+        let protocol = self.protocol();
+        let mut out = io::stdout().lock();
+        queue!(out, SavePosition)?;
+
+        if protocol == Some(GraphicsProtocol::Kitty) {
+            write!(out, "\x1b_Ga=d,d=A,q=2\x1b\\")?;
+        }
+
+        for placement in &placements {
+            if protocol != Some(GraphicsProtocol::Kitty) && self.shown.contains(placement) {
+                continue;
+            }
+
+            let (cell_width, cell_height) = self.cell_size.unwrap_or(DEFAULT_CELL_SIZE);
+            let area = placement.area;
+            let width = area.width as u32 * cell_width as u32;
+            let height = area.height as u32 * cell_height as u32;
+            let Some(pixels) = self.resize(&placement.hash, width, height) else {
+                continue;
+            };
+
+            queue!(out, MoveTo(area.x, area.y))?;
+            match protocol {
+                Some(GraphicsProtocol::Kitty) => write_kitty(&mut out, pixels, area)?,
+                Some(GraphicsProtocol::Sixel) => write_sixel(&mut out, pixels)?,
+                Some(GraphicsProtocol::Iterm2) => write_iterm2(&mut out, pixels, area)?,
+                _ => {}
+            }
+        }
+
+        queue!(out, RestorePosition)?;
+        out.flush()?;
+
+        self.shown = placements;
+        Ok(())
+    }
+
+    /// The protocol covers are actually drawn with.
+    ///
+    /// Sixel images are sized in pixels, so if the terminal does not report
+    /// the size of its cells they fall back to half blocks rather than risk
+    /// overflowing their area.
+    fn protocol(&self) -> Option<GraphicsProtocol> {
+        match self.protocol {
+            Some(GraphicsProtocol::Sixel) if self.cell_size.is_none() => {
+                Some(GraphicsProtocol::HalfBlocks)
+            }
+            protocol => protocol,
+        }
+    }
+
+    /// Loads the thumbnail with the given hash, returning its size.
+    fn load(&mut self, hash: &str) -> Option<(u32, u32)> {
+        if !self.images.contains_key(hash) {
+            if self.images.len() >= CACHE_LIMIT {
+                self.images.clear();
+            }
+            let image = image::open(covers::thumbnail_path(hash)).ok();
+            self.images.insert(hash.to_string(), image);
+        }
+
+        let image = self.images.get(hash)?.as_ref()?;
+        Some((image.width(), image.height()))
+    }
+
+    /// Resizes the thumbnail with the given hash to exactly the given size.
+    fn resize(&mut self, hash: &str, width: u32, height: u32) -> Option<&RgbImage> {
+        let key = (hash.to_string(), width, height);
+        if !self.resized.contains_key(&key) {
+            let image = self.images.get(hash)?.as_ref()?;
+            if self.resized.len() >= CACHE_LIMIT {
+                self.resized.clear();
+            }
+            let pixels = image
+                .resize_exact(width, height, FilterType::Triangle)
+                .to_rgb8();
+            self.resized.insert(key.clone(), pixels);
+        }
+
+        self.resized.get(&key)
+    }
+
+    /// Finds the largest area, within the given one, that an image of the
+    /// given size fits without being stretched.
+    fn fit(&self, width: u32, height: u32, area: Rect) -> Rect {
+        let (cell_width, cell_height) = self.cell_size.unwrap_or(DEFAULT_CELL_SIZE);
+
+        let scale = f64::min(
+            area.width as f64 * cell_width as f64 / width as f64,
+            area.height as f64 * cell_height as f64 / height as f64,
+        );
+        let columns = (width as f64 * scale / cell_width as f64).round() as u16;
+        let rows = (height as f64 * scale / cell_height as f64).round() as u16;
+        let columns = columns.clamp(1, area.width);
+        let rows = rows.clamp(1, area.height);
+
+        Rect::new(area.x + (area.width - columns) / 2, area.y, columns, rows)
+    }
+}
+
+/// Asks the terminal for the size of a cell in pixels.
+fn query_cell_size() -> Option<(u16, u16)> {
+    let size = terminal::window_size().ok()?;
+    if size.columns == 0 || size.rows == 0 || size.width == 0 || size.height == 0 {
+        return None;
+    }
+    Some((size.width / size.columns, size.height / size.rows))
+}
+
+/// Writes an image with the kitty graphics protocol, scaled to the area.
+///
+/// The pixels are sent raw, in chunks, and the terminal asked not to reply
+/// since nothing reads the replies.
+fn write_kitty(out: &mut impl Write, pixels: &RgbImage, area: Rect) -> io::Result<()> {
+    let data = STANDARD.encode(pixels.as_raw());
+    let chunks: Vec<&[u8]> = data.as_bytes().chunks(KITTY_CHUNK_SIZE).collect();
+
+    for (index, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(index + 1 < chunks.len());
+        if index == 0 {
+            write!(
+                out,
+                "\x1b_Ga=T,f=24,s={},v={},c={},r={},C=1,q=2,m={};",
+                pixels.width(),
+                pixels.height(),
+                area.width,
+                area.height,
+                more
+            )?;
+        } else {
+            write!(out, "\x1b_Gm={};", more)?;
+        }
+        out.write_all(chunk)?;
+        write!(out, "\x1b\\")?;
+    }
+
+    Ok(())
+}
+
+/// Writes an image as an iTerm2 inline image, scaled to the area.
+fn write_iterm2(out: &mut impl Write, pixels: &RgbImage, area: Rect) -> io::Result<()> {
+    let mut data = Vec::new();
+    PngEncoder::new(&mut data)
+        .write_image(
+            pixels.as_raw(),
+            pixels.width(),
+            pixels.height(),
+            ExtendedColorType::Rgb8,
+        )
+        .map_err(io::Error::other)?;
+
+    write!(
+        out,
+        "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=0:{}\x07",
+        data.len(),
+        area.width,
+        area.height,
+        STANDARD.encode(&data)
+    )
+}
+
+/// Writes an image with sixel graphics.
+///
+/// Each band of six rows of pixels is written once per colour it uses, with
+/// runs of the same sixel compressed.
+fn write_sixel(out: &mut impl Write, pixels: &RgbImage) -> io::Result<()> {
+    let (width, height) = pixels.dimensions();
+    let max = SIXEL_LEVELS - 1;
+
+    let level = |value: u8| (value as u32 * max + 127) / 255;
+    let colours: Vec<u32> = pixels
+        .pixels()
+        .map(|p| (level(p[0]) * SIXEL_LEVELS + level(p[1])) * SIXEL_LEVELS + level(p[2]))
+        .collect();
+
+    write!(out, "\x1bPq\"1;1;{};{}", width, height)?;
+
+    let palette_size = SIXEL_LEVELS.pow(3);
+    for colour in 0..palette_size {
+        let percent = |level: u32| level * 100 / max;
+        write!(
+            out,
+            "#{};2;{};{};{}",
+            colour,
+            percent(colour / (SIXEL_LEVELS * SIXEL_LEVELS)),
+            percent(colour / SIXEL_LEVELS % SIXEL_LEVELS),
+            percent(colour % SIXEL_LEVELS)
+        )?;
+    }
+
+    for top in (0..height).step_by(6) {
+        if top > 0 {
+            write!(out, "-")?;
+        }
+
+        let rows = (height - top).min(6);
+        let mut used = vec![false; palette_size as usize];
+        for y in top..top + rows {
+            for x in 0..width {
+                used[colours[(y * width + x) as usize] as usize] = true;
+            }
+        }
+
+        let mut first = true;
+        for colour in (0..palette_size).filter(|&colour| used[colour as usize]) {
+            if !first {
+                write!(out, "$")?;
+            }
+            first = false;
+
+            write!(out, "#{}", colour)?;
+
+            let mut run: Option<(u8, u32)> = None;
+            for x in 0..width {
+                let mut bits = 0;
+                for row in 0..rows {
+                    if colours[((top + row) * width + x) as usize] == colour {
+                        bits |= 1 << row;
+                    }
+                }
+                let sixel = 63 + bits;
+
+                run = match run {
+                    Some((last, count)) if last == sixel => Some((last, count + 1)),
+                    _ => {
+                        write_sixel_run(out, run)?;
+                        Some((sixel, 1))
+                    }
+                };
+            }
+            write_sixel_run(out, run)?;
+        }
+    }
+
+    write!(out, "\x1b\\")
+}
+
+/// Writes a run of repeated sixels, using the repeat introducer when that is
+/// shorter.
+fn write_sixel_run(out: &mut impl Write, run: Option<(u8, u32)>) -> io::Result<()> {
+    match run {
+        Some((sixel, count)) if count > 3 => write!(out, "!{}{}", count, sixel as char),
+        Some((sixel, count)) => {
+            for _ in 0..count {
+                write!(out, "{}", sixel as char)?;
+            }
+            Ok(())
+        }
+        None => Ok(()),
+    }
+}
@@ -41,3 +41,5 @@ pub(crate) const ICON_MUTED: &str = "\u{1F507}";
 pub(crate) const ICON_LIKE: &str = "\u{25B2}";
 pub(crate) const ICON_DISLIKE: &str = "\u{25BC}";
 pub(crate) const ICON_FAVOURITE: &str = "\u{2764}";
+pub(crate) const ICON_STAR: &str = "\u{2605}";
+pub(crate) const ICON_HALF_STAR: &str = "\u{00BD}";
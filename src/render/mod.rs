@@ -26,6 +26,7 @@
 
 mod browser;
 mod commander;
+pub(crate) mod cover;
 pub mod icons;
 mod player;
 
@@ -82,19 +83,54 @@ pub(crate) fn draw(f: &mut Frame, app: &mut App) {
         .split(outer[0]);
 
     match app.main_view {
-        crate::MainView::Playlist => app.playlist_view.draw(f, main[1], &app.queue, &app.theme),
+        crate::MainView::Playlist => {
+            app.playlist_view
+                .draw(f, main[1], &app.queue, &app.auto_dj, &app.theme)
+        }
         crate::MainView::Search => app.search_view.draw(f, main[1], &app.search, &app.theme),
-        crate::MainView::Favourites => app.favourites_view.draw(f, main[1], &app.theme),
-        crate::MainView::Browse => browser::draw(f, main[1], &mut app.media_browser, &app.theme),
+        crate::MainView::Favourites => {
+            app.favourites_view
+                .draw(f, main[1], &app.favourites, &app.theme)
+        }
+        crate::MainView::Browse => {
+            browser::draw(f, main[1], &mut app.media_browser, &mut app.cover_art, &app.theme)
+        }
         crate::MainView::Equalizer => {
             app.equalizer_view
                 .draw(f, main[1], &mut app.equalizer, &app.theme)
         }
         crate::MainView::Catalog => app.catalog_view.draw(f, main[1], &app.catalog, &app.theme),
+        crate::MainView::Bookmarks => {
+            app.bookmarks_view
+                .draw(f, main[1], app.now_playing.as_ref(), &app.theme)
+        }
+        crate::MainView::History => app.history_view.draw(f, main[1], &app.theme),
+        crate::MainView::Stats => app.stats_view.draw(f, main[1], app.stats.as_ref(), &app.theme),
+        crate::MainView::Stations => {
+            app.stations_view
+                .draw(f, main[1], app.now_streaming.as_ref(), &app.theme)
+        }
+        crate::MainView::Podcasts => {
+            app.podcasts_view
+                .draw(f, main[1], app.now_playing.as_ref(), &app.theme)
+        }
+        crate::MainView::Organise => {
+            app.organise_view
+                .draw(f, main[1], &app.organise, &app.theme)
+        }
+        crate::MainView::Lyrics => app.lyrics_view.draw(
+            f,
+            main[1],
+            app.lyrics.as_ref(),
+            app.now_playing.as_ref(),
+            &app.theme,
+        ),
         crate::MainView::Help => app.help_view.draw(f, main[1], &app.theme),
     };
 
     draw_player(f, outer[1], app);
 
     draw_commander(f, outer[2], app);
+
+    app.tag_editor.draw(f, area, &app.theme);
 }
@@ -18,6 +18,8 @@
 //! This module provides renders the visual representation of the current
 //! track, playback controls, progress barsm and so on.
 
+use std::fmt::Write;
+
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -34,15 +36,34 @@ use crate::{
 };
 
 /// Renders the main player widget including track info and controls.
-pub(crate) fn draw_player(f: &mut Frame, area: Rect, app: &App) {
+pub(crate) fn draw_player(f: &mut Frame, area: Rect, app: &mut App) {
     let block = Block::default()
         .borders(Borders::TOP | Borders::BOTTOM)
         .border_style(Style::default().fg(app.theme.border_colour))
         .padding(Padding::horizontal(1));
 
-    let inner_area = block.inner(area);
+    let mut inner_area = block.inner(area);
     f.render_widget(block, area);
 
+    // The album cover goes to the left of everything else, roughly square
+    // given cells are about twice as tall as they are wide
+    let cover_hash = app.now_playing.as_ref().and_then(|t| t.cover_hash.as_deref());
+    if let Some(hash) = cover_hash
+        && app.cover_art.is_enabled()
+    {
+        let cover_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(inner_area.height * 2),
+                Constraint::Length(2),
+                Constraint::Min(0),
+            ])
+            .split(inner_area);
+
+        app.cover_art.draw(f, cover_chunks[0], hash);
+        inner_area = cover_chunks[2];
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -119,13 +140,58 @@ pub(crate) fn draw_player(f: &mut Frame, area: Rect, app: &App) {
 
         let time_p = Paragraph::new(time_line).alignment(Alignment::Right);
 
+        f.render_widget(time_p, info_chunks[1]);
+    } else if let Some(station) = &app.now_streaming {
+        let icon = match app.player_state {
+            PlayerState::Playing => ICON_PLAY,
+            PlayerState::Paused => ICON_PAUSE,
+            PlayerState::Stopped => ICON_STOP,
+        };
+
+        // The title is whatever the station last reported, which may just be
+        // its own name
+        let mut spans = vec![
+            Span::styled(
+                format!(" {} ", icon),
+                Style::default().add_modifier(Modifier::BOLD),
+            )
+            .fg(Color::White),
+        ];
+        match app.player_track_name.as_deref() {
+            Some(title) if title != station.name => {
+                spans.push(
+                    Span::styled(title, Style::default().add_modifier(Modifier::BOLD))
+                        .fg(app.theme.accent_colour),
+                );
+                spans.push(Span::raw(" on "));
+            }
+            _ => {}
+        }
+        spans.push(
+            Span::styled(&station.name, Style::default().add_modifier(Modifier::BOLD))
+                .fg(app.theme.accent_colour),
+        );
+        f.render_widget(Paragraph::new(Line::from(spans)), info_chunks[0]);
+
+        let time = app.player_time.unwrap_or(0);
+        let time_line = Line::from(vec![
+            Span::styled("LIVE ", Style::default().add_modifier(Modifier::BOLD)).fg(Color::White),
+            Span::styled(
+                util::format::format_time(time, TimeFormat::Minutes),
+                Style::default().add_modifier(Modifier::BOLD),
+            )
+            .fg(app.theme.accent_colour),
+        ]);
+
+        let time_p = Paragraph::new(time_line).alignment(Alignment::Right);
+
         f.render_widget(time_p, info_chunks[1]);
     }
 
     let control_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Length(12),
+            Constraint::Length(40),
             Constraint::Min(0),
             Constraint::Length(26),
         ])
@@ -137,12 +203,38 @@ pub(crate) fn draw_player(f: &mut Frame, area: Rect, app: &App) {
         crate::RepeatMode::RepeatAll => "Repeat All",
     };
 
+    let mut repeat_text = repeat_text.to_string();
+    if let Some(shuffle_text) = app.queue.shuffle_mode().describe() {
+        let _ = write!(repeat_text, " | {}", shuffle_text);
+    }
+    if app.auto_dj.is_enabled() {
+        repeat_text.push_str(" | Auto-DJ");
+    }
+
     let repeat_label = Paragraph::new(repeat_text)
         .alignment(Alignment::Left)
         .fg(Color::White);
 
     f.render_widget(repeat_label, control_chunks[0]);
 
+    if let Some(sleep_text) = app.sleep_timer.describe() {
+        let remaining = app
+            .sleep_timer
+            .remaining()
+            .map(|remaining| util::format::format_time(remaining.as_secs(), TimeFormat::Minutes))
+            .unwrap_or_else(|| "--:--".to_string());
+
+        let sleep_line = Line::from(vec![
+            Span::raw(format!("{} ", sleep_text)).fg(Color::White),
+            Span::styled(remaining, Style::default().add_modifier(Modifier::BOLD))
+                .fg(app.theme.accent_colour),
+        ]);
+
+        let sleep_label = Paragraph::new(sleep_line).alignment(Alignment::Center);
+
+        f.render_widget(sleep_label, control_chunks[1]);
+    }
+
     let volume = app.volume.unwrap_or(0);
     let vol_ratio = (volume as f64 / 100.0).clamp(0.0, 1.0);
 
@@ -0,0 +1,259 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Listen submission to ListenBrainz, or a compatible server.
+//!
+//! See <https://listenbrainz.readthedocs.io/en/latest/users/api/core.html>.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+
+use crate::model::{PendingListen, TrackInfo};
+
+const SUBMIT_LISTENS_PATH: &str = "/1/submit-listens";
+
+const CLIENT_NAME: &str = "chooui";
+
+/// Requests taking longer than this are abandoned, so an unreachable server
+/// does not hold up the task worker.
+const TIMEOUT_SECS: u64 = 10;
+
+/// Tells the server which track is now playing.
+///
+/// # Arguments
+///
+/// * `base_url` - The base URL of the API.
+/// * `token` - The user token.
+/// * `track` - The track that has started playing.
+///
+/// # Errors
+///
+/// Returns an error if the server can not be reached, or rejects the request.
+pub(crate) fn submit_playing_now(base_url: &str, token: &str, track: &TrackInfo) -> Result<()> {
+    let payload = json!({
+        "listen_type": "playing_now",
+        "payload": [{
+            "track_metadata": track_metadata(
+                &track.artist_name,
+                &track.track_title,
+                &track.album_title,
+                track.track_number,
+                track.duration,
+            ),
+        }],
+    });
+
+    submit(base_url, token, &payload)
+}
+
+/// Submits a batch of listens.
+///
+/// # Arguments
+///
+/// * `base_url` - The base URL of the API.
+/// * `token` - The user token.
+/// * `listens` - The listens to submit.
+///
+/// # Errors
+///
+/// Returns an error if the server can not be reached, or rejects the request.
+pub(crate) fn submit_listens(base_url: &str, token: &str, listens: &[PendingListen]) -> Result<()> {
+    let listen_type = if listens.len() == 1 {
+        "single"
+    } else {
+        "import"
+    };
+
+    let payload: Vec<Value> = listens
+        .iter()
+        .map(|listen| {
+            json!({
+                "listened_at": listen.listened_at,
+                "track_metadata": track_metadata(
+                    &listen.artist_name,
+                    &listen.track_title,
+                    &listen.album_title,
+                    listen.track_number,
+                    listen.duration,
+                ),
+            })
+        })
+        .collect();
+
+    submit(
+        base_url,
+        token,
+        &json!({ "listen_type": listen_type, "payload": payload }),
+    )
+}
+
+/// Whether a submission failed because the server rejected the listens
+/// themselves, so that sending the same listens again can never succeed.
+///
+/// Authentication failures and rate limiting are not rejections, they clear up
+/// without any change to the listens.
+pub(crate) fn is_rejected(error: &anyhow::Error) -> bool {
+    match error.downcast_ref::<ureq::Error>() {
+        Some(ureq::Error::StatusCode(status)) => {
+            (400..500).contains(status) && !matches!(status, 401 | 408 | 429)
+        }
+        _ => false,
+    }
+}
+
+fn track_metadata(
+    artist_name: &str,
+    track_title: &str,
+    album_title: &str,
+    track_number: i32,
+    duration: i64,
+) -> Value {
+    let mut additional_info = json!({
+        "media_player": CLIENT_NAME,
+        "submission_client": CLIENT_NAME,
+        "submission_client_version": env!("CARGO_PKG_VERSION"),
+    });
+
+    if track_number > 0 {
+        additional_info["tracknumber"] = json!(track_number);
+    }
+
+    if duration > 0 {
+        additional_info["duration_ms"] = json!(duration * 1000);
+    }
+
+    json!({
+        "artist_name": artist_name,
+        "track_name": track_title,
+        "release_name": album_title,
+        "additional_info": additional_info,
+    })
+}
+
+fn submit(base_url: &str, token: &str, payload: &Value) -> Result<()> {
+    let url = format!("{}{}", base_url.trim_end_matches('/'), SUBMIT_LISTENS_PATH);
+
+    ureq::post(&url)
+        .config()
+        .timeout_global(Some(Duration::from_secs(TIMEOUT_SECS)))
+        .build()
+        .header("Authorization", format!("Token {}", token))
+        .content_type("application/json")
+        .send(payload.to_string())
+        .with_context(|| format!("Failed to submit to ListenBrainz: {}", url))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{net::TcpListener, sync::mpsc};
+
+    use super::*;
+    use crate::util::test_server::{self, Request};
+
+    /// Starts a stand-in ListenBrainz server that answers every request with
+    /// the status chosen by `respond`.
+    ///
+    /// Returns the base URL of the server and a receiver for the requests it
+    /// has answered.
+    pub(crate) fn serve(
+        respond: impl Fn(&Value) -> u16 + Send + 'static,
+    ) -> (String, mpsc::Receiver<Request>) {
+        test_server::serve(move |request| (respond(&json(request)), Vec::new()))
+    }
+
+    fn json(request: &Request) -> Value {
+        serde_json::from_slice(&request.body).unwrap()
+    }
+
+    pub(crate) fn listen(id: i64, track_title: &str) -> PendingListen {
+        PendingListen {
+            id,
+            listened_at: 1_700_000_000 + id,
+            artist_name: "Artist".to_string(),
+            track_title: track_title.to_string(),
+            album_title: "Album".to_string(),
+            track_number: 3,
+            duration: 200,
+        }
+    }
+
+    #[test]
+    fn submits_listens() {
+        let (url, request_rx) = serve(|_| 200);
+
+        submit_listens(&url, "secret", &[listen(1, "One"), listen(2, "Two")]).unwrap();
+
+        let request = request_rx.recv().unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/1/submit-listens");
+        assert!(request.headers.iter().any(|header| header == "authorization: Token secret"));
+        let body = json(&request);
+        assert_eq!(body["listen_type"], "import");
+
+        let payload = body["payload"].as_array().unwrap();
+        assert_eq!(payload.len(), 2);
+        assert_eq!(payload[0]["listened_at"], 1_700_000_001);
+        assert_eq!(payload[0]["track_metadata"]["artist_name"], "Artist");
+        assert_eq!(payload[0]["track_metadata"]["track_name"], "One");
+        assert_eq!(payload[0]["track_metadata"]["release_name"], "Album");
+        assert_eq!(payload[0]["track_metadata"]["additional_info"]["tracknumber"], 3);
+        assert_eq!(payload[0]["track_metadata"]["additional_info"]["duration_ms"], 200_000);
+        assert_eq!(payload[1]["track_metadata"]["track_name"], "Two");
+    }
+
+    #[test]
+    fn submits_single_listen() {
+        let (url, request_rx) = serve(|_| 200);
+
+        submit_listens(&url, "secret", &[listen(1, "One")]).unwrap();
+
+        assert_eq!(json(&request_rx.recv().unwrap())["listen_type"], "single");
+    }
+
+    #[test]
+    fn bad_request_is_rejected() {
+        let (url, _request_rx) = serve(|_| 400);
+
+        let error = submit_listens(&url, "secret", &[listen(1, "One")]).unwrap_err();
+
+        assert!(is_rejected(&error));
+    }
+
+    #[test]
+    fn transient_failures_are_not_rejected() {
+        for status in [401, 408, 429, 500, 503] {
+            let (url, _request_rx) = serve(move |_| status);
+
+            let error = submit_listens(&url, "secret", &[listen(1, "One")]).unwrap_err();
+
+            assert!(!is_rejected(&error), "status {status}");
+        }
+    }
+
+    #[test]
+    fn unreachable_server_is_not_rejected() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let error = submit_listens(&url, "secret", &[listen(1, "One")]).unwrap_err();
+
+        assert!(!is_rejected(&error));
+    }
+}
@@ -0,0 +1,25 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Scrobbling, recording plays with listening history services.
+//!
+//! Plays can be appended to an offline `.scrobbler.log`, to be uploaded later
+//! by other tools, and submitted to ListenBrainz. Submissions block on the
+//! network, so these are only ever invoked from the background task worker.
+
+pub(crate) mod listenbrainz;
+mod scrobbler_log;
+
+pub(crate) use scrobbler_log::append_to_log;
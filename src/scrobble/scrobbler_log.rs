@@ -0,0 +1,101 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! The Audioscrobbler/Rockbox `.scrobbler.log` format.
+//!
+//! The log is a tab-separated text file, starting with a short header, with
+//! one line per play:
+//!
+//! ```text
+//! artist  album  title  track number  duration  rating  timestamp  MusicBrainz id
+//! ```
+//!
+//! The rating is `L` if the track was listened to, or `S` if it was skipped.
+//! Timestamps are in UTC, as declared by the header.
+
+use std::{fs::OpenOptions, io::Write, path::Path};
+
+use anyhow::{Context, Result};
+
+use crate::model::TrackInfo;
+
+const HEADER: &str = concat!(
+    "#AUDIOSCROBBLER/1.1\n",
+    "#TZ/UTC\n",
+    "#CLIENT/chooui ",
+    env!("CARGO_PKG_VERSION"),
+    "\n",
+);
+
+/// Appends a play to the scrobble log, creating the log if needed.
+///
+/// # Arguments
+///
+/// * `path` - The path of the log file.
+/// * `track` - The track that was played.
+/// * `started_at` - When playback started, as a Unix timestamp.
+/// * `listened` - Whether the track played for long enough to count as
+///   listened to, rather than skipped.
+///
+/// # Errors
+///
+/// Returns an error if the log can not be opened or written to.
+pub(crate) fn append_to_log(
+    path: &Path,
+    track: &TrackInfo,
+    started_at: i64,
+    listened: bool,
+) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open scrobble log: {}", path.display()))?;
+
+    let mut entry = String::new();
+
+    // A new log needs its header before the first entry
+    if file.metadata()?.len() == 0 {
+        entry.push_str(HEADER);
+    }
+
+    let track_number = if track.track_number > 0 {
+        track.track_number.to_string()
+    } else {
+        String::new()
+    };
+
+    let fields = [
+        field(&track.artist_name),
+        field(&track.album_title),
+        field(&track.track_title),
+        track_number,
+        track.duration.max(0).to_string(),
+        if listened { "L" } else { "S" }.to_string(),
+        started_at.to_string(),
+        String::new(),
+    ];
+    entry.push_str(&fields.join("\t"));
+    entry.push('\n');
+
+    file.write_all(entry.as_bytes())
+        .with_context(|| format!("Failed to write scrobble log: {}", path.display()))
+}
+
+/// Makes a value safe to use as a field, since tabs and line breaks would
+/// corrupt the log.
+fn field(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
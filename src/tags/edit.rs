@@ -0,0 +1,140 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Editing of the catalogued tags in ID3v2 tags.
+
+use std::{borrow::Cow, fs::File, path::Path};
+
+use anyhow::{Context, Result, anyhow};
+use lofty::{
+    TextEncoding,
+    config::{ParseOptions, ParsingMode, WriteOptions},
+    id3::v2::{Frame, FrameId, Id3v2Tag, TextInformationFrame},
+    mpeg::MpegFile,
+    prelude::*,
+    tag::items::Timestamp,
+};
+
+use crate::model::tags::{TagChange, TagField, TrackTags};
+
+const ALBUM_ARTIST_ID: FrameId<'static> = FrameId::Valid(Cow::Borrowed("TPE2"));
+
+/// Reads the editable tags of an MP3 file.
+///
+/// # Errors
+///
+/// Returns an error if the file can not be read, or is not an MP3 file.
+pub(crate) fn read_track_tags(path: &Path) -> Result<TrackTags> {
+    let tag = read_id3v2(path)?;
+
+    let mut tags = TrackTags::default();
+    for field in TagField::ALL {
+        let value = match field {
+            TagField::Title => tag.title().map(Cow::into_owned),
+            TagField::Artist => tag.artist().map(Cow::into_owned),
+            TagField::AlbumArtist => tag.get_text(&ALBUM_ARTIST_ID).map(str::to_string),
+            TagField::Album => tag.album().map(Cow::into_owned),
+            TagField::TrackNumber => tag.track().map(|track| track.to_string()),
+            TagField::DiscNumber => tag.disk().map(|disk| disk.to_string()),
+            TagField::Year => tag.date().map(|date| date.year.to_string()),
+            TagField::Genre => tag.genre().map(Cow::into_owned),
+        };
+        tags.set(field, value.unwrap_or_default());
+    }
+
+    Ok(tags)
+}
+
+/// Changes the tags of an MP3 file, leaving any other tags as they are.
+///
+/// # Arguments
+///
+/// * `path` - The file to write to.
+/// * `changes` - The new tag values, where an empty value removes the tag.
+///
+/// # Errors
+///
+/// Returns an error if the file can not be read, is not an MP3 file, a
+/// numeric tag is not a number, or the file can not be written.
+pub(crate) fn write_track_tags(path: &Path, changes: &[TagChange]) -> Result<()> {
+    let mut tag = read_id3v2(path)?;
+
+    for (field, value) in changes {
+        let value = value.trim();
+        if value.is_empty() {
+            remove_tag(&mut tag, *field);
+            continue;
+        }
+
+        match field {
+            TagField::Title => tag.set_title(value.to_string()),
+            TagField::Artist => tag.set_artist(value.to_string()),
+            TagField::AlbumArtist => {
+                tag.insert(Frame::Text(TextInformationFrame::new(
+                    ALBUM_ARTIST_ID,
+                    TextEncoding::UTF8,
+                    value.to_string(),
+                )));
+            }
+            TagField::Album => tag.set_album(value.to_string()),
+            TagField::TrackNumber => tag.set_track(parse_number(*field, value)?),
+            TagField::DiscNumber => tag.set_disk(parse_number(*field, value)?),
+            TagField::Year => {
+                let year = u16::try_from(parse_number(*field, value)?)?;
+                tag.set_date(Timestamp {
+                    year,
+                    ..Timestamp::default()
+                });
+            }
+            TagField::Genre => tag.set_genre(value.to_string()),
+        }
+    }
+
+    tag.save_to_path(path, WriteOptions::default())
+        .with_context(|| format!("Failed to write tags: {}", path.display()))
+}
+
+fn read_id3v2(path: &Path) -> Result<Id3v2Tag> {
+    let options = ParseOptions::new()
+        .read_properties(false)
+        .parsing_mode(ParsingMode::Relaxed);
+
+    let mut file = File::open(path)?;
+    let mpeg_file = MpegFile::read_from(&mut file, options)
+        .with_context(|| format!("Failed to read tags: {}", path.display()))?;
+
+    Ok(mpeg_file.id3v2().cloned().unwrap_or_default())
+}
+
+fn remove_tag(tag: &mut Id3v2Tag, field: TagField) {
+    match field {
+        TagField::Title => tag.remove_title(),
+        TagField::Artist => tag.remove_artist(),
+        TagField::AlbumArtist => {
+            let _ = tag.remove(&ALBUM_ARTIST_ID);
+        }
+        TagField::Album => tag.remove_album(),
+        TagField::TrackNumber => tag.remove_track(),
+        TagField::DiscNumber => tag.remove_disk(),
+        TagField::Year => tag.remove_date(),
+        TagField::Genre => tag.remove_genre(),
+    }
+}
+
+fn parse_number(field: TagField, value: &str) -> Result<u32> {
+    value
+        .parse()
+        .map_err(|_| anyhow!("{} must be a number: {}", field.label(), value))
+}
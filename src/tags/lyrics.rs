@@ -0,0 +1,135 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//! Reading of lyrics, from LRC files alongside tracks or from their tags.
+
+use std::{fs, path::Path};
+
+use anyhow::Result;
+use lofty::{
+    config::{ParseOptions, ParsingMode},
+    file::{FileType, TaggedFile},
+    id3::v2::{Frame, Id3v2Tag, SyncTextContentType, SynchronizedTextFrame, TimestampFormat},
+    mpeg::MpegFile,
+    prelude::*,
+    probe::Probe,
+    tag::Tag,
+};
+
+use crate::model::lyrics::Lyrics;
+
+/// Reads the lyrics of a track.
+///
+/// An LRC file with the same name as the track comes first, then the
+/// synchronised lyrics of an ID3v2 `SYLT` frame, then the lyrics tag, which
+/// may itself hold LRC.
+///
+/// # Errors
+///
+/// Returns an error if the LRC file or the track can not be read.
+pub(crate) fn read_lyrics(path: &Path) -> Result<Option<Lyrics>> {
+    if let Some(lyrics) = read_lrc_file(path)? {
+        return Ok(Some(lyrics));
+    }
+
+    let options = ParseOptions::new().parsing_mode(ParsingMode::Relaxed);
+    let probe = Probe::open(path)?.options(options);
+    let tagged_file = match probe.file_type() {
+        Some(FileType::Mpeg) => {
+            let mpeg_file = MpegFile::read_from(&mut probe.into_inner(), options)?;
+            if let Some(lyrics) = mpeg_file.id3v2().and_then(read_synced_lyrics) {
+                return Ok(Some(lyrics));
+            }
+            TaggedFile::from(mpeg_file)
+        }
+        _ => probe.read()?,
+    };
+
+    let tag = tagged_file
+        .primary_tag()
+        .or_else(|| tagged_file.first_tag());
+
+    Ok(tag.and_then(read_tag_lyrics))
+}
+
+/// Reads the LRC file with the same name as a track, if there is one.
+///
+/// # Errors
+///
+/// Returns an error if the LRC file exists but can not be read.
+pub(crate) fn read_lrc_file(path: &Path) -> Result<Option<Lyrics>> {
+    let lrc_path = path.with_extension("lrc");
+    if !lrc_path.is_file() {
+        return Ok(None);
+    }
+
+    // LRC files are not always UTF-8, so the odd character may be lost
+    let data = fs::read(&lrc_path)?;
+    let text = String::from_utf8_lossy(&data);
+    let lyrics = Lyrics::parse(text.trim_start_matches('\u{feff}'));
+
+    Ok((!lyrics.is_empty()).then_some(lyrics))
+}
+
+/// Reads synchronised lyrics from an ID3v2 `SYLT` frame.
+///
+/// Only frames timed in milliseconds are understood. Karaoke frames time each
+/// syllable, with new lines starting with a line feed, these are joined back
+/// up into lines.
+pub(crate) fn read_synced_lyrics(tag: &Id3v2Tag) -> Option<Lyrics> {
+    let frame = tag.into_iter().find_map(|frame| match frame {
+        Frame::Binary(binary) if frame.id_str() == "SYLT" => {
+            SynchronizedTextFrame::parse(&binary.data, binary.flags()).ok()
+        }
+        _ => None,
+    })?;
+
+    if frame.timestamp_format != TimestampFormat::MS
+        || frame.content_type != SyncTextContentType::Lyrics
+    {
+        return None;
+    }
+
+    let is_line_start = |text: &str| text.starts_with(['\n', '\r']);
+    let is_karaoke = frame.content.iter().any(|(_, text)| is_line_start(text));
+
+    let mut lines: Vec<(u32, String)> = Vec::new();
+    for (time, text) in frame.content {
+        match lines.last_mut() {
+            Some((_, line)) if is_karaoke && !is_line_start(&text) => line.push_str(&text),
+            _ => lines.push((time, text.trim_start().to_string())),
+        }
+    }
+
+    let lyrics = Lyrics::synced(
+        lines
+            .into_iter()
+            .map(|(time, line)| (time, line.trim_end().to_string())),
+    );
+
+    (!lyrics.is_empty()).then_some(lyrics)
+}
+
+/// Reads the lyrics tag, such as an ID3v2 `USLT` frame or a Vorbis `LYRICS`
+/// comment.
+///
+/// Some formats keep LRC and plain lyrics in separate tags, in which case the
+/// LRC is preferred.
+pub(crate) fn read_tag_lyrics(tag: &Tag) -> Option<Lyrics> {
+    let text = tag
+        .get_string(ItemKey::Lyrics)
+        .or_else(|| tag.get_string(ItemKey::UnsyncLyrics))?;
+    let lyrics = Lyrics::parse(text);
+    (!lyrics.is_empty()).then_some(lyrics)
+}
@@ -0,0 +1,27 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Reading and writing of file tags.
+//!
+//! Catalog metadata is read while scanning, this module deals with tags that
+//! are read or written individually, such as ratings, lyrics and edited tags.
+
+mod edit;
+mod lyrics;
+mod rating;
+
+pub(crate) use edit::{read_track_tags, write_track_tags};
+pub(crate) use lyrics::{read_lrc_file, read_lyrics, read_synced_lyrics, read_tag_lyrics};
+pub(crate) use rating::{read_star_rating, write_star_rating};
@@ -0,0 +1,140 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Star ratings in ID3v2 tags.
+//!
+//! Players disagree on how to store ratings. Most write a `POPM`
+//! (popularimeter) frame, with a rating from 1 to 255 that each player maps
+//! to stars in its own way, and some write an `FMPS_Rating` user text frame,
+//! with a rating from 0.0 to 1.0. Both are read, preferring `FMPS_Rating` as
+//! it is unambiguous, and both are written.
+
+use std::{fs::File, path::Path};
+
+use anyhow::{Context, Result};
+use lofty::{
+    config::{ParseOptions, ParsingMode, WriteOptions},
+    id3::v2::{Frame, Id3v2Tag, PopularimeterFrame},
+    mpeg::MpegFile,
+    prelude::*,
+};
+
+use crate::model::StarRating;
+
+/// The user text frame description for Free Media Player Specs ratings.
+const FMPS_RATING: &str = "FMPS_Rating";
+
+/// The email identifying the `POPM` frame that is written. This is the one
+/// Windows Media Player uses, which most other players also read.
+const POPM_EMAIL: &str = "Windows Media Player 9 Series";
+
+/// `POPM` ratings written for each number of half stars. Whole stars use
+/// the Windows Media Player values, and half stars those of MediaMonkey.
+const POPM_RATINGS: [u8; StarRating::MAX_HALF_STARS as usize] =
+    [13, 1, 54, 64, 118, 128, 186, 196, 242, 255];
+
+/// Reads the star rating from an ID3v2 tag, if it has one.
+pub(crate) fn read_star_rating(tag: &Id3v2Tag) -> Option<StarRating> {
+    if let Some(fmps_rating) = tag.get_user_text(FMPS_RATING) {
+        return fmps_rating
+            .trim()
+            .parse()
+            .ok()
+            .and_then(StarRating::from_fraction);
+    }
+
+    let popularimeters: Vec<&PopularimeterFrame> = tag
+        .into_iter()
+        .filter_map(|frame| match frame {
+            Frame::Popularimeter(popm) if popm.rating > 0 => Some(popm),
+            _ => None,
+        })
+        .collect();
+
+    popularimeters
+        .iter()
+        .find(|popm| popm.email == POPM_EMAIL)
+        .or_else(|| popularimeters.first())
+        .and_then(|popm| StarRating::from_half_stars(popm_half_stars(popm.rating)))
+}
+
+/// Writes a star rating to the ID3v2 tag of an MP3 file, replacing any
+/// rating already there.
+///
+/// # Arguments
+///
+/// * `path` - The file to write to.
+/// * `stars` - The star rating, or `None` to remove the rating.
+///
+/// # Errors
+///
+/// Returns an error if the file can not be read, is not an MP3 file, or can
+/// not be written.
+pub(crate) fn write_star_rating(path: &Path, stars: Option<StarRating>) -> Result<()> {
+    let options = ParseOptions::new()
+        .read_properties(false)
+        .parsing_mode(ParsingMode::Relaxed);
+
+    let mut file = File::open(path)?;
+    let mpeg_file = MpegFile::read_from(&mut file, options)
+        .with_context(|| format!("Failed to read tags: {}", path.display()))?;
+
+    let mut tag = mpeg_file.id3v2().cloned().unwrap_or_default();
+
+    // The play counter shares the frame, so keep it
+    let play_counter = (&tag)
+        .into_iter()
+        .find_map(|frame| match frame {
+            Frame::Popularimeter(popm) if popm.email == POPM_EMAIL => Some(popm.counter),
+            _ => None,
+        })
+        .unwrap_or(0);
+
+    tag.retain(|frame| !matches!(frame, Frame::Popularimeter(_)));
+    tag.remove_user_text(FMPS_RATING);
+
+    if let Some(stars) = stars {
+        let popm_rating = POPM_RATINGS[usize::from(stars.half_stars()) - 1];
+        tag.insert(Frame::Popularimeter(PopularimeterFrame::new(
+            POPM_EMAIL,
+            popm_rating,
+            play_counter,
+        )));
+        tag.insert_user_text(FMPS_RATING.into(), format!("{:.2}", stars.fraction()));
+    }
+
+    tag.save_to_path(path, WriteOptions::default())
+        .with_context(|| format!("Failed to write tags: {}", path.display()))
+}
+
+/// Converts a `POPM` rating to half stars, accepting the values written by
+/// both Windows Media Player and MediaMonkey.
+fn popm_half_stars(rating: u8) -> u8 {
+    match rating {
+        0 => 0,
+        // Windows Media Player's one star
+        1 => 2,
+        2..=22 => 1,
+        23..=31 => 2,
+        32..=63 => 3,
+        64..=95 => 4,
+        96..=127 => 5,
+        128..=159 => 6,
+        160..=195 => 7,
+        196..=223 => 8,
+        224..=254 => 9,
+        255 => 10,
+    }
+}
@@ -13,18 +13,54 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use anyhow::Result;
+use std::{path::Path, slice, time::Duration};
+
+use anyhow::{Context, Result, anyhow};
 
 use crate::{
-    db::{self, scan},
-    events::AppEvent,
-    model::{Rating, SearchQuery, TrackInfo},
+    db::{self, organise, scan},
+    events::{AppEvent, OrganiseEvent},
+    model::{
+        FavouritesOrder, PendingListen, Rating, SearchOrder, SearchQuery, StarRating, TrackInfo,
+        auto_dj::{self, AutoDjRequest},
+        history::PlaySession,
+        organise::FileMove,
+        stats::LibraryStats,
+        tags::TagChange,
+    },
+    podcast,
+    scrobble::{self, listenbrainz},
+    tags,
     tasks::TaskContext,
 };
 
 const MIN_SEARCH_LEN: usize = 3;
 
+/// Saved positions this close to the end of a track are not resumed from.
+const RESUME_END_MARGIN_SECS: u64 = 10;
+
+/// Number of tracks sampled from the catalog for the Auto-DJ to choose from.
+const AUTO_DJ_CANDIDATES: u32 = 1000;
+
+/// Number of recent plays shown in the history view.
+const HISTORY_LIMIT: u32 = 500;
+
+/// Number of albums listed when browsing recently added albums.
+const RECENT_ALBUMS_LIMIT: u32 = 100;
+
+/// Number of entries in each of the "top" lists in the stats view.
+const STATS_TOP_COUNT: u32 = 10;
+
+/// Number of months charted in the stats view.
+const STATS_MONTHS: u32 = 12;
+
+/// Number of listens submitted to ListenBrainz in each request.
+const LISTEN_BATCH_SIZE: u32 = 100;
+
 pub(super) fn scan_catalog(ctx: &mut TaskContext) -> Result<()> {
+    ctx.event_tx.send(AppEvent::SetBrowserGenres(vec![]))?;
+    ctx.event_tx.send(AppEvent::SetBrowserDecades(vec![]))?;
+    ctx.event_tx.send(AppEvent::SetBrowserYears(vec![]))?;
     ctx.event_tx.send(AppEvent::SetBrowserArtists(vec![]))?;
     ctx.event_tx.send(AppEvent::SetBrowserAlbums(vec![]))?;
     ctx.event_tx.send(AppEvent::SetBrowserTracks(vec![]))?;
@@ -45,7 +81,10 @@ pub(super) fn search(ctx: &mut TaskContext, query: SearchQuery) -> Result<()> {
         || query.artist.len() >= MIN_SEARCH_LEN
         || query.album.len() >= MIN_SEARCH_LEN
         || query.track.len() >= MIN_SEARCH_LEN
-        || query.recency.is_some();
+        || query.lyrics.iter().any(|words| words.len() >= MIN_SEARCH_LEN)
+        || query.recency.is_some()
+        || !query.play_filters.is_empty()
+        || query.order != SearchOrder::Library;
 
     if can_search {
         let search_results = db::search(ctx.conn, &query)?;
@@ -77,6 +116,59 @@ pub(super) fn get_browser_tracks(ctx: &mut TaskContext, album_id: i32) -> Result
     Ok(())
 }
 
+pub(super) fn get_browser_genres(ctx: &mut TaskContext) -> Result<()> {
+    let genres = db::fetch_genres(ctx.conn)?;
+    ctx.event_tx.send(AppEvent::SetBrowserGenres(genres))?;
+
+    Ok(())
+}
+
+pub(super) fn get_browser_genre_artists(ctx: &mut TaskContext, genre_id: i32) -> Result<()> {
+    let artists = db::fetch_genre_artists(ctx.conn, genre_id)?;
+    ctx.event_tx.send(AppEvent::SetBrowserArtists(artists))?;
+
+    Ok(())
+}
+
+pub(super) fn get_browser_genre_albums(
+    ctx: &mut TaskContext,
+    genre_id: i32,
+    artist_id: i32,
+) -> Result<()> {
+    let albums = db::fetch_genre_albums(ctx.conn, genre_id, artist_id)?;
+    ctx.event_tx.send(AppEvent::SetBrowserAlbums(albums))?;
+
+    Ok(())
+}
+
+pub(super) fn get_browser_decades(ctx: &mut TaskContext) -> Result<()> {
+    let decades = db::fetch_decades(ctx.conn)?;
+    ctx.event_tx.send(AppEvent::SetBrowserDecades(decades))?;
+
+    Ok(())
+}
+
+pub(super) fn get_browser_years(ctx: &mut TaskContext, decade: i64) -> Result<()> {
+    let years = db::fetch_decade_years(ctx.conn, decade)?;
+    ctx.event_tx.send(AppEvent::SetBrowserYears(years))?;
+
+    Ok(())
+}
+
+pub(super) fn get_browser_year_albums(ctx: &mut TaskContext, year: i64) -> Result<()> {
+    let albums = db::fetch_year_albums(ctx.conn, year)?;
+    ctx.event_tx.send(AppEvent::SetBrowserAlbums(albums))?;
+
+    Ok(())
+}
+
+pub(super) fn get_browser_recent_albums(ctx: &mut TaskContext) -> Result<()> {
+    let albums = db::fetch_recent_albums(ctx.conn, RECENT_ALBUMS_LIMIT)?;
+    ctx.event_tx.send(AppEvent::SetBrowserAlbums(albums))?;
+
+    Ok(())
+}
+
 pub(super) fn add_artist_to_queue(ctx: &mut TaskContext, artist_id: i32) -> Result<()> {
     let tracks = db::fetch_artist_trackinfo(ctx.conn, artist_id)?;
     ctx.event_tx.send(AppEvent::AddTracksToQueue(tracks))?;
@@ -84,6 +176,24 @@ pub(super) fn add_artist_to_queue(ctx: &mut TaskContext, artist_id: i32) -> Resu
     Ok(())
 }
 
+pub(super) fn add_genre_to_queue(
+    ctx: &mut TaskContext,
+    genre_id: i32,
+    artist_id: Option<i32>,
+) -> Result<()> {
+    let tracks = db::fetch_genre_trackinfo(ctx.conn, genre_id, artist_id)?;
+    ctx.event_tx.send(AppEvent::AddTracksToQueue(tracks))?;
+
+    Ok(())
+}
+
+pub(super) fn add_years_to_queue(ctx: &mut TaskContext, from: i64, to: i64) -> Result<()> {
+    let tracks = db::fetch_years_trackinfo(ctx.conn, from, to)?;
+    ctx.event_tx.send(AppEvent::AddTracksToQueue(tracks))?;
+
+    Ok(())
+}
+
 pub(super) fn add_album_to_queue(ctx: &mut TaskContext, album_id: i32) -> Result<()> {
     let tracks = db::fetch_album_track_info(ctx.conn, album_id)?;
     ctx.event_tx.send(AppEvent::AddTracksToQueue(tracks))?;
@@ -122,25 +232,686 @@ pub(super) fn add_matching_track_to_queue(ctx: &mut TaskContext, track: String)
     Ok(())
 }
 
-pub(super) fn play_track(ctx: &mut TaskContext, mut track: TrackInfo) -> Result<()> {
-    let durable_id = track.durable_id;
-    ctx.event_tx.send(AppEvent::PlayTrack(track.clone()))?; // FIXME not sure this is right... but this is the only place playtrack is used currently
+pub(super) fn play_track(ctx: &mut TaskContext, track: TrackInfo) -> Result<()> {
+    let start = resume_position(ctx, &track)?;
+    start_track(ctx, track, start)
+}
+
+pub(super) fn play_track_from(
+    ctx: &mut TaskContext,
+    track: TrackInfo,
+    position: u64,
+) -> Result<()> {
+    start_track(ctx, track, Duration::from_secs(position))
+}
+
+fn resume_position(ctx: &mut TaskContext, track: &TrackInfo) -> Result<Duration> {
+    let config = ctx.config;
+    if !track.is_resumable(config.resume_threshold_secs, &config.resume_genres) {
+        return Ok(Duration::ZERO);
+    }
 
-    let new_count = db::increment_play_count(ctx.conn, durable_id)?;
+    let position = db::fetch_resume_position(ctx.conn, track.durable_id)?.unwrap_or(0);
+    let duration = u64::try_from(track.duration).unwrap_or(0);
+    if position + RESUME_END_MARGIN_SECS >= duration {
+        return Ok(Duration::ZERO);
+    }
+
+    Ok(Duration::from_secs(position))
+}
+
+fn start_track(ctx: &mut TaskContext, track: TrackInfo, start: Duration) -> Result<()> {
+    ctx.event_tx.send(AppEvent::PlayTrack(track, start))?; // FIXME not sure this is right... but this is the only place playtrack is used currently
+
+    Ok(())
+}
+
+/// Counts a play of a track, once it has played for long enough.
+pub(super) fn count_play(ctx: &mut TaskContext, mut track: TrackInfo) -> Result<()> {
+    let new_count = db::increment_play_count(ctx.conn, track.durable_id)?;
     track.play_count = u32::try_from(new_count).unwrap_or(u32::MAX);
     ctx.event_tx.send(AppEvent::TrackUpdated(track))?;
 
     Ok(())
 }
 
-pub(super) fn rate_track(
+pub(super) fn skip_track(ctx: &mut TaskContext, durable_id: i64) -> Result<()> {
+    db::increment_skip_count(ctx.conn, durable_id)
+}
+
+pub(super) fn rate_track(ctx: &mut TaskContext, track: TrackInfo, rating: Rating) -> Result<()> {
+    let stars = StarRating::for_rating(rating, track.stars);
+    update_rating(ctx, track, rating, stars)
+}
+
+pub(super) fn rate_track_stars(
+    ctx: &mut TaskContext,
+    track: TrackInfo,
+    stars: Option<StarRating>,
+) -> Result<()> {
+    let rating = stars.map_or(Rating::Neutral, StarRating::rating);
+    update_rating(ctx, track, rating, stars)
+}
+
+fn update_rating(
     ctx: &mut TaskContext,
     mut track: TrackInfo,
     rating: Rating,
+    stars: Option<StarRating>,
+) -> Result<()> {
+    (track.rating, track.stars) = db::update_rating(ctx.conn, track.durable_id, rating, stars)?;
+
+    if ctx.config.write_rating_tags && !track.is_podcast {
+        tags::write_star_rating(Path::new(&track.filename), track.stars)?;
+    }
+    ctx.event_tx.send(AppEvent::TrackUpdated(track))?;
+
+    Ok(())
+}
+
+pub(super) fn get_favourites(
+    ctx: &mut TaskContext,
+    rating: Rating,
+    order: FavouritesOrder,
+) -> Result<()> {
+    let tracks = db::fetch_rated_tracks(ctx.conn, rating, order)?;
+    ctx.event_tx.send(AppEvent::FavouritesReady(rating, tracks))?;
+
+    Ok(())
+}
+
+/// Reads the tags of tracks that are about to be edited.
+pub(super) fn read_tags(ctx: &mut TaskContext, mut tracks: Vec<TrackInfo>) -> Result<()> {
+    // Podcast episodes are catalogued from their feed rather than their tags
+    tracks.retain(|track| !track.is_podcast);
+    if tracks.is_empty() {
+        return Err(anyhow!("Podcast episode tags can not be edited"));
+    }
+
+    let tags = tracks
+        .iter()
+        .map(|track| tags::read_track_tags(Path::new(&track.filename)))
+        .collect::<Result<Vec<_>>>()?;
+
+    ctx.event_tx.send(AppEvent::TagsReady(tracks, tags))?;
+
+    Ok(())
+}
+
+/// Writes edited tags to the files of tracks, updating the catalog to match.
+///
+/// The catalog is refreshed even if a track fails, since the tracks before it
+/// will already have been changed.
+pub(super) fn edit_tags(
+    ctx: &mut TaskContext,
+    tracks: Vec<TrackInfo>,
+    changes: Vec<TagChange>,
 ) -> Result<()> {
-    let new_rating = db::update_rating(ctx.conn, track.durable_id, rating)?;
-    track.rating = new_rating;
+    let result = tracks
+        .iter()
+        .try_for_each(|track| edit_track_tags(ctx, track, &changes));
+
+    ctx.event_tx.send(AppEvent::CatalogUpdated)?;
+
+    result
+}
+
+/// Reads the lyrics of a track, from an LRC file alongside it or its tags.
+pub(super) fn get_lyrics(ctx: &mut TaskContext, track: TrackInfo) -> Result<()> {
+    let lyrics = tags::read_lyrics(Path::new(&track.filename))?;
+    ctx.event_tx.send(AppEvent::LyricsReady(track.track_id, lyrics))?;
+
+    Ok(())
+}
+
+/// Lists the moves that organising the media directories would make, without
+/// making them.
+pub(super) fn preview_organise(ctx: &mut TaskContext) -> Result<()> {
+    let config = ctx.config;
+    let moves = organise::plan_moves(ctx.conn, &config.organise_template, &config.media_dirs)?;
+    ctx.event_tx.send(AppEvent::Organise(OrganiseEvent::Planned(moves)))?;
+
+    Ok(())
+}
+
+/// Makes the moves previewed by [`preview_organise`], so that only the moves
+/// that were shown are made even if the catalog has changed since.
+pub(super) fn organise(ctx: &mut TaskContext, moves: Vec<FileMove>) -> Result<()> {
+    let moved = organise::apply_moves(ctx.conn, &moves, ctx.event_tx)?;
+
+    // Tracks already listed elsewhere need their new filenames to be played
+    let tracks = moved
+        .into_iter()
+        .map(|track_id| db::fetch_track_info(ctx.conn, track_id))
+        .collect::<Result<Vec<_>>>()?;
+    ctx.event_tx.send(AppEvent::TracksUpdated(tracks))?;
+
+    Ok(())
+}
+
+fn edit_track_tags(ctx: &mut TaskContext, track: &TrackInfo, changes: &[TagChange]) -> Result<()> {
+    tags::write_track_tags(Path::new(&track.filename), changes)?;
+    scan::reindex_track(ctx.conn, track)?;
+
+    let track = db::fetch_track_info(ctx.conn, track.track_id)?;
     ctx.event_tx.send(AppEvent::TrackUpdated(track))?;
 
     Ok(())
 }
+
+pub(super) fn save_resume_position(
+    ctx: &mut TaskContext,
+    durable_id: i64,
+    position: u64,
+) -> Result<()> {
+    db::save_resume_position(ctx.conn, durable_id, position)
+}
+
+pub(super) fn clear_resume_position(ctx: &mut TaskContext, durable_id: i64) -> Result<()> {
+    db::clear_resume_position(ctx.conn, durable_id)
+}
+
+pub(super) fn get_bookmarks(ctx: &mut TaskContext) -> Result<()> {
+    let bookmarks = db::fetch_bookmarks(ctx.conn)?;
+    ctx.event_tx.send(AppEvent::BookmarksReady(bookmarks))?;
+
+    Ok(())
+}
+
+pub(super) fn add_bookmark(
+    ctx: &mut TaskContext,
+    track: TrackInfo,
+    name: String,
+    position: u64,
+) -> Result<()> {
+    db::add_bookmark(ctx.conn, track.durable_id, &name, position)?;
+    get_bookmarks(ctx)
+}
+
+pub(super) fn delete_bookmark(ctx: &mut TaskContext, bookmark_id: i32) -> Result<()> {
+    db::delete_bookmark(ctx.conn, bookmark_id)?;
+    get_bookmarks(ctx)
+}
+
+pub(super) fn get_history(ctx: &mut TaskContext) -> Result<()> {
+    let records = db::fetch_play_history(ctx.conn, HISTORY_LIMIT)?;
+    ctx.event_tx.send(AppEvent::HistoryReady(records))?;
+
+    Ok(())
+}
+
+/// Records a finished play in the play history, and in the scrobble log if
+/// one is configured.
+pub(super) fn record_play(
+    ctx: &mut TaskContext,
+    session: PlaySession,
+    skipped: bool,
+) -> Result<()> {
+    let track = &session.track;
+    db::add_play_history(
+        ctx.conn,
+        track.durable_id,
+        session.started_at,
+        session.played_secs(),
+        skipped,
+    )?;
+    get_history(ctx)?;
+
+    if track.is_podcast {
+        return Ok(());
+    }
+
+    // Listens are queued first so they are not lost if submitting fails
+    let submit_listen = session.is_counted() && !ctx.config.listenbrainz_token.is_empty();
+    if submit_listen {
+        db::add_pending_listen(ctx.conn, track, session.started_at)?;
+    }
+
+    let log_path = &ctx.config.scrobbler_log;
+    if !log_path.is_empty() {
+        scrobble::append_to_log(
+            Path::new(log_path),
+            track,
+            session.started_at,
+            session.is_counted(),
+        )?;
+    }
+
+    if submit_listen {
+        submit_listens(ctx)?;
+    }
+
+    Ok(())
+}
+
+pub(super) fn submit_playing_now(ctx: &mut TaskContext, track: TrackInfo) -> Result<()> {
+    let config = ctx.config;
+    if config.listenbrainz_token.is_empty() || track.is_podcast {
+        return Ok(());
+    }
+
+    listenbrainz::submit_playing_now(&config.listenbrainz_url, &config.listenbrainz_token, &track)
+}
+
+/// Submits any listens waiting in the queue to ListenBrainz, in batches.
+///
+/// Listens stay in the queue until they have been accepted, so if the server
+/// can not be reached they are tried again later. Listens the server rejects
+/// outright are dropped, otherwise they would hold up every later submission.
+pub(super) fn submit_listens(ctx: &mut TaskContext) -> Result<()> {
+    let config = ctx.config;
+    if config.listenbrainz_token.is_empty() {
+        return Ok(());
+    }
+
+    loop {
+        let listens = db::fetch_pending_listens(ctx.conn, LISTEN_BATCH_SIZE)?;
+        if listens.is_empty() {
+            return Ok(());
+        }
+
+        match listenbrainz::submit_listens(
+            &config.listenbrainz_url,
+            &config.listenbrainz_token,
+            &listens,
+        ) {
+            Ok(()) => {
+                let ids: Vec<i64> = listens.iter().map(|listen| listen.id).collect();
+                db::delete_pending_listens(ctx.conn, &ids)?;
+            }
+
+            // A single bad listen rejects the whole batch, so find it by
+            // submitting them one at a time
+            Err(e) if listenbrainz::is_rejected(&e) => submit_listens_individually(ctx, &listens)?,
+
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Submits listens one at a time, dropping any that the server rejects.
+fn submit_listens_individually(ctx: &mut TaskContext, listens: &[PendingListen]) -> Result<()> {
+    let config = ctx.config;
+    let mut done = Vec::new();
+
+    for listen in listens {
+        match listenbrainz::submit_listens(
+            &config.listenbrainz_url,
+            &config.listenbrainz_token,
+            slice::from_ref(listen),
+        ) {
+            Ok(()) => done.push(listen.id),
+
+            Err(e) if listenbrainz::is_rejected(&e) => {
+                ctx.event_tx.send(AppEvent::Error(format!(
+                    "Discarded listen rejected by ListenBrainz: {} - {}: {:#}",
+                    listen.artist_name, listen.track_title, e
+                )))?;
+                done.push(listen.id);
+            }
+
+            Err(e) => {
+                // Keep the listens already accepted from being sent again
+                db::delete_pending_listens(ctx.conn, &done)?;
+                return Err(e);
+            }
+        }
+    }
+
+    db::delete_pending_listens(ctx.conn, &done)
+}
+
+pub(super) fn get_stats(ctx: &mut TaskContext) -> Result<()> {
+    let stats = LibraryStats {
+        summary: db::fetch_library_summary(ctx.conn)?,
+        top_artists: db::fetch_top_artists(ctx.conn, STATS_TOP_COUNT)?,
+        top_albums: db::fetch_top_albums(ctx.conn, STATS_TOP_COUNT)?,
+        top_tracks: db::fetch_top_tracks(ctx.conn, STATS_TOP_COUNT)?,
+        genre_plays: db::fetch_genre_plays(ctx.conn, STATS_TOP_COUNT)?,
+        formats: db::fetch_format_counts(ctx.conn)?,
+        added_per_month: db::fetch_tracks_added_per_month(ctx.conn, STATS_MONTHS)?,
+    };
+    ctx.event_tx.send(AppEvent::StatsReady(stats))?;
+
+    Ok(())
+}
+
+pub(super) fn get_stations(ctx: &mut TaskContext) -> Result<()> {
+    let stations = db::fetch_stations(ctx.conn)?;
+    ctx.event_tx.send(AppEvent::StationsReady(stations))?;
+
+    Ok(())
+}
+
+pub(super) fn add_station(ctx: &mut TaskContext, name: String, url: String) -> Result<()> {
+    db::add_station(ctx.conn, &name, &url)?;
+    get_stations(ctx)
+}
+
+pub(super) fn delete_station(ctx: &mut TaskContext, station_id: i32) -> Result<()> {
+    db::delete_station(ctx.conn, station_id)?;
+    get_stations(ctx)
+}
+
+pub(super) fn get_episodes(ctx: &mut TaskContext) -> Result<()> {
+    let episodes = db::fetch_episodes(ctx.conn)?;
+    ctx.event_tx.send(AppEvent::EpisodesReady(episodes))?;
+
+    Ok(())
+}
+
+pub(super) fn subscribe_podcast(ctx: &mut TaskContext, url: String) -> Result<()> {
+    let feed = podcast::fetch_feed(&url)?;
+    db::save_podcast(ctx.conn, &url, &feed)?;
+    get_episodes(ctx)
+}
+
+/// Fetches every subscribed feed for new episodes.
+///
+/// A feed that can not be fetched is reported but does not stop the others
+/// from being refreshed.
+pub(super) fn refresh_podcasts(ctx: &mut TaskContext) -> Result<()> {
+    for podcast in db::fetch_podcasts(ctx.conn)? {
+        let result = podcast::fetch_feed(&podcast.feed_url)
+            .and_then(|feed| db::save_podcast(ctx.conn, &podcast.feed_url, &feed))
+            .with_context(|| format!("Failed to refresh {}", podcast.title));
+
+        if let Err(e) = result {
+            ctx.event_tx.send(AppEvent::Error(format!("{:#}", e)))?;
+        }
+    }
+
+    get_episodes(ctx)
+}
+
+pub(super) fn unsubscribe_podcast(ctx: &mut TaskContext, podcast_id: i32) -> Result<()> {
+    db::delete_podcast(ctx.conn, podcast_id)?;
+    get_episodes(ctx)
+}
+
+/// Downloads an episode and adds it to the catalog.
+pub(super) fn download_episode(ctx: &mut TaskContext, episode_id: i32) -> Result<()> {
+    let episode = db::fetch_episode(ctx.conn, episode_id)?;
+
+    // The file may still be there even if the catalog entry is not
+    let path = match &episode.filename {
+        Some(filename) if Path::new(filename).exists() => filename.into(),
+        _ => podcast::download_episode(&episode, &ctx.config.podcast_dir)?,
+    };
+
+    let filename = path
+        .to_str()
+        .context("Path contains invalid UTF-8")?
+        .to_string();
+
+    let durable_id = scan::add_episode_to_catalog(ctx.conn, &episode, &path)?;
+    db::set_episode_downloaded(ctx.conn, episode_id, &filename, durable_id)?;
+
+    get_episodes(ctx)
+}
+
+pub(super) fn play_episode(ctx: &mut TaskContext, durable_id: i64) -> Result<()> {
+    let track = db::fetch_trackinfo_by_durable_id(ctx.conn, durable_id)?
+        .ok_or_else(|| anyhow!("Episode is not in the catalog, try downloading it again"))?;
+    play_track(ctx, track)
+}
+
+pub(super) fn set_episode_played(ctx: &mut TaskContext, episode_id: i32, played: bool) -> Result<()> {
+    db::set_episode_played(ctx.conn, episode_id, played)?;
+    get_episodes(ctx)
+}
+
+/// Marks the episode for a catalog track as played, if there is one.
+pub(super) fn track_episode_played(ctx: &mut TaskContext, durable_id: i64) -> Result<()> {
+    if db::set_track_episode_played(ctx.conn, durable_id)? {
+        get_episodes(ctx)?;
+    }
+
+    Ok(())
+}
+
+/// Chooses tracks to top up the queue with.
+pub(super) fn auto_dj(ctx: &mut TaskContext, request: AutoDjRequest) -> Result<()> {
+    // The Auto-DJ waits for picks before asking again, so it must always get
+    // an answer even if there are no picks to give
+    let candidates = match db::fetch_auto_dj_candidates(
+        ctx.conn,
+        AUTO_DJ_CANDIDATES,
+        request.replay_gap_secs,
+    ) {
+        Ok(candidates) => candidates,
+        Err(e) => {
+            ctx.event_tx.send(AppEvent::AutoDjPicks(Vec::new()))?;
+            return Err(e);
+        }
+    };
+    let picks = auto_dj::pick_tracks(&request, candidates);
+    ctx.event_tx.send(AppEvent::AutoDjPicks(picks))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use rusqlite::{Connection, params};
+
+    use super::*;
+    use crate::{config::AppConfig, scrobble::listenbrainz::tests::serve, util::test_server};
+
+    /// Opens a fresh database in a file unique to the test.
+    fn open_db(name: &str) -> Connection {
+        let path = std::env::temp_dir().join(format!("chooui-{}-{}.db", name, std::process::id()));
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+        db::init_db(path.to_str().unwrap()).unwrap()
+    }
+
+    fn add_listens(conn: &Connection, titles: &[&str]) {
+        for title in titles {
+            conn.execute(
+                "INSERT INTO pending_listens
+                    (listened_at, artist_name, track_title, album_title, track_number, duration)
+                VALUES (1700000000, 'Artist', ?1, 'Album', 1, 200)",
+                params![title],
+            )
+            .unwrap();
+        }
+    }
+
+    fn pending_titles(conn: &Connection) -> Vec<String> {
+        db::fetch_pending_listens(conn, 1000)
+            .unwrap()
+            .into_iter()
+            .map(|listen| listen.track_title)
+            .collect()
+    }
+
+    /// Runs the submit listens task against a server answering with `respond`.
+    ///
+    /// Returns the result and any errors reported.
+    fn run_submit_listens(
+        conn: &mut Connection,
+        respond: impl Fn(&serde_json::Value) -> u16 + Send + 'static,
+    ) -> (Result<()>, Vec<String>) {
+        let (url, _request_rx) = serve(respond);
+        let config = AppConfig {
+            listenbrainz_url: url,
+            listenbrainz_token: "secret".into(),
+            ..Default::default()
+        };
+        let (event_tx, event_rx) = mpsc::channel();
+        let mut ctx = TaskContext {
+            config: &config,
+            event_tx: &event_tx,
+            conn,
+        };
+
+        let result = submit_listens(&mut ctx);
+
+        let errors = event_rx
+            .try_iter()
+            .filter_map(|event| match event {
+                AppEvent::Error(error) => Some(error),
+                _ => None,
+            })
+            .collect();
+
+        (result, errors)
+    }
+
+    fn contains_title(body: &serde_json::Value, title: &str) -> bool {
+        body["payload"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|listen| listen["track_metadata"]["track_name"] == title)
+    }
+
+    #[test]
+    fn accepted_listens_are_removed() {
+        let mut conn = open_db("accepted-listens");
+        add_listens(&conn, &["One", "Two", "Three"]);
+
+        let (result, errors) = run_submit_listens(&mut conn, |_| 200);
+
+        assert!(result.is_ok());
+        assert!(errors.is_empty());
+        assert!(pending_titles(&conn).is_empty());
+    }
+
+    #[test]
+    fn rejected_listens_are_dropped() {
+        let mut conn = open_db("rejected-listens");
+        add_listens(&conn, &["One", "Bad", "Three"]);
+
+        let (result, errors) =
+            run_submit_listens(&mut conn, |body| if contains_title(body, "Bad") { 400 } else { 200 });
+
+        assert!(result.is_ok());
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("Artist - Bad"));
+        assert!(pending_titles(&conn).is_empty());
+    }
+
+    #[test]
+    fn listens_are_kept_when_server_fails() {
+        let mut conn = open_db("failed-listens");
+        add_listens(&conn, &["One", "Two"]);
+
+        let (result, errors) = run_submit_listens(&mut conn, |_| 503);
+
+        assert!(result.is_err());
+        assert!(errors.is_empty());
+        assert_eq!(pending_titles(&conn), ["One", "Two"]);
+    }
+
+    #[test]
+    fn handled_listens_are_removed_when_server_fails_midway() {
+        let mut conn = open_db("midway-listens");
+        add_listens(&conn, &["One", "Bad", "Three"]);
+
+        // The batch is rejected, then the server fails after the first two
+        // listens have been accepted and rejected on their own
+        let (result, _) = run_submit_listens(&mut conn, |body| {
+            if contains_title(body, "Bad") {
+                400
+            } else if contains_title(body, "Three") {
+                503
+            } else {
+                200
+            }
+        });
+
+        assert!(result.is_err());
+        assert_eq!(pending_titles(&conn), ["Three"]);
+    }
+
+    /// Builds an MP3 stream of silent 128 kbit/s, 44.1 kHz frames.
+    fn mp3(frames: usize) -> Vec<u8> {
+        let mut frame = vec![0u8; 417];
+        frame[..4].copy_from_slice(&[0xFF, 0xFB, 0x90, 0x64]);
+        frame.repeat(frames)
+    }
+
+    /// Serves a feed with one episode, whose enclosure is served by the same
+    /// server.
+    fn serve_podcast(request: &test_server::Request) -> (u16, Vec<u8>) {
+        let host = request
+            .headers
+            .iter()
+            .find_map(|header| header.strip_prefix("host: "))
+            .unwrap();
+
+        match request.path.as_str() {
+            "/feed.xml" => {
+                let feed = format!(
+                    r#"<rss version="2.0"><channel><title>Test Podcast</title>
+                        <item>
+                            <guid>episode-1</guid>
+                            <title>First Episode</title>
+                            <pubDate>Fri, 01 Mar 2024 12:30:15 +0000</pubDate>
+                            <enclosure url="http://{}/episode.mp3" type="audio/mpeg"/>
+                        </item>
+                    </channel></rss>"#,
+                    host
+                );
+                (200, feed.into_bytes())
+            }
+            "/episode.mp3" => (200, mp3(200)),
+            _ => (404, Vec::new()),
+        }
+    }
+
+    #[test]
+    fn subscribed_episode_is_downloaded_to_catalog() {
+        let (url, _request_rx) = test_server::serve(serve_podcast);
+        let podcast_dir =
+            std::env::temp_dir().join(format!("chooui-podcasts-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&podcast_dir);
+        let config = AppConfig {
+            podcast_dir: podcast_dir.to_str().unwrap().to_string(),
+            ..Default::default()
+        };
+        let mut conn = open_db("podcast-download");
+        let (event_tx, _event_rx) = mpsc::channel();
+        let mut ctx = TaskContext {
+            config: &config,
+            event_tx: &event_tx,
+            conn: &mut conn,
+        };
+
+        subscribe_podcast(&mut ctx, format!("{}/feed.xml", url)).unwrap();
+
+        let episodes = db::fetch_episodes(ctx.conn).unwrap();
+        assert_eq!(episodes.len(), 1);
+        assert_eq!(episodes[0].podcast_title, "Test Podcast");
+        assert_eq!(episodes[0].title, "First Episode");
+        assert_eq!(episodes[0].published_at, Some(1_709_296_215));
+        assert_eq!(episodes[0].filename, None);
+
+        download_episode(&mut ctx, episodes[0].id).unwrap();
+
+        let episode = db::fetch_episode(ctx.conn, episodes[0].id).unwrap();
+        let filename = episode.filename.unwrap();
+        assert!(filename.starts_with(&config.podcast_dir));
+        assert_eq!(std::fs::read(&filename).unwrap(), mp3(200));
+
+        let partial_files = std::fs::read_dir(Path::new(&filename).parent().unwrap())
+            .unwrap()
+            .filter(|entry| entry.as_ref().unwrap().path().extension().unwrap() == "part")
+            .count();
+        assert_eq!(partial_files, 0);
+
+        let track = db::fetch_trackinfo_by_durable_id(ctx.conn, episode.durable_id.unwrap())
+            .unwrap()
+            .unwrap();
+        assert!(track.is_podcast);
+        assert_eq!(track.track_title, "First Episode");
+        assert_eq!(track.filename, filename);
+
+        std::fs::remove_dir_all(&podcast_dir).unwrap();
+    }
+}
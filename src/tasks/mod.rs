@@ -39,7 +39,12 @@ use crate::{
     config::AppConfig,
     db::{self},
     events::AppEvent,
-    model::{Rating, SearchQuery, TrackInfo},
+    model::{
+        FavouritesOrder, Rating, SearchQuery, StarRating, TrackInfo, auto_dj::AutoDjRequest,
+        history::PlaySession,
+        organise::FileMove,
+        tags::TagChange,
+    },
 };
 
 const DATABASE_FILE: &str = "music.db";
@@ -53,16 +58,66 @@ pub(crate) enum AppTask {
     GetBrowserArtists,
     GetBrowserAlbums(i32),
     GetBrowserTracks(i32),
+    GetBrowserGenres,
+    GetBrowserGenreArtists(i32),
+    GetBrowserGenreAlbums(i32, i32),
+    GetBrowserDecades,
+    GetBrowserYears(i64),
+    GetBrowserYearAlbums(i64),
+    GetBrowserRecentAlbums,
 
     AddArtistToQueue(i32),
     AddAlbumToQueue(i32),
     AddTrackToQueue(i32),
+    AddGenreToQueue(i32, Option<i32>),
+    AddYearsToQueue(i64, i64),
     AddMatchingArtistToQueue(String),
     AddMatchingAlbumToQueue(String),
     AddMatchingTrackToQueue(String),
 
     PlayTrack(TrackInfo),
+    PlayTrackFrom(TrackInfo, u64),
+    CountPlay(TrackInfo),
+    SkipTrack(i64),
     RateTrack(TrackInfo, Rating),
+    RateTrackStars(TrackInfo, Option<StarRating>),
+    GetFavourites(Rating, FavouritesOrder),
+
+    ReadTags(Vec<TrackInfo>),
+    EditTags(Vec<TrackInfo>, Vec<TagChange>),
+    GetLyrics(TrackInfo),
+
+    PreviewOrganise,
+    Organise(Vec<FileMove>),
+
+    SaveResumePosition(i64, u64),
+    ClearResumePosition(i64),
+
+    GetBookmarks,
+    AddBookmark(TrackInfo, String, u64),
+    DeleteBookmark(i32),
+
+    GetHistory,
+    RecordPlay(PlaySession, bool),
+    SubmitPlayingNow(TrackInfo),
+    SubmitListens,
+
+    GetStats,
+
+    GetStations,
+    AddStation(String, String),
+    DeleteStation(i32),
+
+    GetEpisodes,
+    SubscribePodcast(String),
+    RefreshPodcasts,
+    UnsubscribePodcast(i32),
+    DownloadEpisode(i32),
+    PlayEpisode(i64),
+    SetEpisodePlayed(i32, bool),
+    TrackEpisodePlayed(i64),
+
+    AutoDj(AutoDjRequest),
 }
 
 /// Bundles shared resources required by task handlers to simplify resource
@@ -120,15 +175,69 @@ fn handle_task(task: AppTask, ctx: &mut TaskContext) -> Result<()> {
         AppTask::GetBrowserArtists => get_browser_artists(ctx),
         AppTask::GetBrowserAlbums(id) => get_browser_albums(ctx, id),
         AppTask::GetBrowserTracks(id) => get_browser_tracks(ctx, id),
+        AppTask::GetBrowserGenres => get_browser_genres(ctx),
+        AppTask::GetBrowserGenreArtists(id) => get_browser_genre_artists(ctx, id),
+        AppTask::GetBrowserGenreAlbums(genre_id, artist_id) => {
+            get_browser_genre_albums(ctx, genre_id, artist_id)
+        }
+        AppTask::GetBrowserDecades => get_browser_decades(ctx),
+        AppTask::GetBrowserYears(decade) => get_browser_years(ctx, decade),
+        AppTask::GetBrowserYearAlbums(year) => get_browser_year_albums(ctx, year),
+        AppTask::GetBrowserRecentAlbums => get_browser_recent_albums(ctx),
 
         AppTask::AddArtistToQueue(id) => add_artist_to_queue(ctx, id),
         AppTask::AddAlbumToQueue(id) => add_album_to_queue(ctx, id),
         AppTask::AddTrackToQueue(id) => add_track_to_queue(ctx, id),
+        AppTask::AddGenreToQueue(genre_id, artist_id) => {
+            add_genre_to_queue(ctx, genre_id, artist_id)
+        }
+        AppTask::AddYearsToQueue(from, to) => add_years_to_queue(ctx, from, to),
         AppTask::AddMatchingArtistToQueue(artist) => add_matching_artist_to_queue(ctx, artist),
         AppTask::AddMatchingAlbumToQueue(album) => add_matching_album_to_queue(ctx, album),
         AppTask::AddMatchingTrackToQueue(track) => add_matching_track_to_queue(ctx, track),
 
         AppTask::PlayTrack(track) => play_track(ctx, track),
+        AppTask::PlayTrackFrom(track, position) => play_track_from(ctx, track, position),
+        AppTask::CountPlay(track) => count_play(ctx, track),
+        AppTask::SkipTrack(durable_id) => skip_track(ctx, durable_id),
         AppTask::RateTrack(track, rating) => rate_track(ctx, track, rating),
+        AppTask::RateTrackStars(track, stars) => rate_track_stars(ctx, track, stars),
+        AppTask::GetFavourites(rating, order) => get_favourites(ctx, rating, order),
+
+        AppTask::ReadTags(tracks) => read_tags(ctx, tracks),
+        AppTask::EditTags(tracks, changes) => edit_tags(ctx, tracks, changes),
+        AppTask::GetLyrics(track) => get_lyrics(ctx, track),
+
+        AppTask::PreviewOrganise => preview_organise(ctx),
+        AppTask::Organise(moves) => organise(ctx, moves),
+
+        AppTask::SaveResumePosition(id, position) => save_resume_position(ctx, id, position),
+        AppTask::ClearResumePosition(id) => clear_resume_position(ctx, id),
+
+        AppTask::GetBookmarks => get_bookmarks(ctx),
+        AppTask::AddBookmark(track, name, position) => add_bookmark(ctx, track, name, position),
+        AppTask::DeleteBookmark(id) => delete_bookmark(ctx, id),
+
+        AppTask::GetHistory => get_history(ctx),
+        AppTask::RecordPlay(session, skipped) => record_play(ctx, session, skipped),
+        AppTask::SubmitPlayingNow(track) => submit_playing_now(ctx, track),
+        AppTask::SubmitListens => submit_listens(ctx),
+
+        AppTask::GetStats => get_stats(ctx),
+
+        AppTask::GetStations => get_stations(ctx),
+        AppTask::AddStation(name, url) => add_station(ctx, name, url),
+        AppTask::DeleteStation(id) => delete_station(ctx, id),
+
+        AppTask::GetEpisodes => get_episodes(ctx),
+        AppTask::SubscribePodcast(url) => subscribe_podcast(ctx, url),
+        AppTask::RefreshPodcasts => refresh_podcasts(ctx),
+        AppTask::UnsubscribePodcast(id) => unsubscribe_podcast(ctx, id),
+        AppTask::DownloadEpisode(id) => download_episode(ctx, id),
+        AppTask::PlayEpisode(durable_id) => play_episode(ctx, durable_id),
+        AppTask::SetEpisodePlayed(id, played) => set_episode_played(ctx, id, played),
+        AppTask::TrackEpisodePlayed(durable_id) => track_episode_played(ctx, durable_id),
+
+        AppTask::AutoDj(request) => auto_dj(ctx, request),
     }
 }
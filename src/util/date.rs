@@ -0,0 +1,236 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Calendar date conversion and parsing.
+//!
+//! Only the small subset of date handling needed for podcast feeds and the
+//! play history is provided here: converting between Unix timestamps and
+//! civil (proleptic Gregorian) dates, and parsing the RFC 2822 and RFC 3339
+//! formats used by RSS and Atom respectively.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECS_PER_DAY: i64 = 86_400;
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// Converts a civil date to a number of days since the Unix epoch.
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Converts a number of days since the Unix epoch to a civil date, returned
+/// as `(year, month, day)`.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Converts a Unix timestamp to a civil date, returned as `(year, month,
+/// day)`.
+pub(crate) fn date_from_timestamp(timestamp: i64) -> (i64, u32, u32) {
+    civil_from_days(timestamp.div_euclid(SECS_PER_DAY))
+}
+
+/// Parses an RFC 2822 date, as used by RSS, to a Unix timestamp.
+///
+/// The day of the week and the seconds are optional, and the obsolete named
+/// North American time zones are understood.
+///
+/// # Examples
+///
+/// ```ignore
+/// assert_eq!(parse_rfc2822("Thu, 01 Jan 1970 01:00:00 +0100"), Some(0));
+/// ```
+pub(crate) fn parse_rfc2822(value: &str) -> Option<i64> {
+    let value = match value.split_once(',') {
+        Some((_, rest)) => rest,
+        None => value,
+    };
+
+    let mut parts = value.split_whitespace();
+    let day: u32 = parts.next()?.parse().ok()?;
+    let month = parts.next()?.to_ascii_lowercase();
+    let month = MONTHS.iter().position(|m| month.starts_with(m))? as u32 + 1;
+    let year: i64 = match parts.next()?.parse().ok()? {
+        year @ 0..50 => year + 2000,
+        year @ 50..1000 => year + 1900,
+        year => year,
+    };
+    let secs = parse_time(parts.next()?)?;
+    let offset = parts.next().map_or(Some(0), parse_zone)?;
+
+    Some(days_from_civil(year, month, day) * SECS_PER_DAY + secs - offset)
+}
+
+/// Parses an RFC 3339 date, as used by Atom, to a Unix timestamp.
+///
+/// A date without a time is taken to be midnight UTC.
+///
+/// # Examples
+///
+/// ```ignore
+/// assert_eq!(parse_rfc3339("1970-01-01T01:00:00+01:00"), Some(0));
+/// ```
+pub(crate) fn parse_rfc3339(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let (date, time) = match value.split_once(['T', 't', ' ']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (value, None),
+    };
+
+    let mut date_parts = date.splitn(3, '-');
+    let year: i64 = date_parts.next()?.parse().ok()?;
+    let month: u32 = date_parts.next()?.parse().ok()?;
+    let day: u32 = date_parts.next()?.parse().ok()?;
+
+    let (secs, offset) = match time {
+        Some(time) => {
+            let zone_start = time
+                .find(['Z', 'z', '+', '-'])
+                .unwrap_or(time.len());
+            let (time, zone) = time.split_at(zone_start);
+            // Fractional seconds are of no interest
+            let time = time.split('.').next()?;
+            (parse_time(time)?, parse_zone(zone)?)
+        }
+        None => (0, 0),
+    };
+
+    Some(days_from_civil(year, month, day) * SECS_PER_DAY + secs - offset)
+}
+
+/// Parses `HH:MM` or `HH:MM:SS` to a number of seconds into the day.
+fn parse_time(value: &str) -> Option<i64> {
+    let mut parts = value.split(':');
+    let hours: i64 = parts.next()?.parse().ok()?;
+    let mins: i64 = parts.next()?.parse().ok()?;
+    let secs: i64 = parts.next().map_or(Some(0), |s| s.parse().ok())?;
+    Some(hours * 3600 + mins * 60 + secs)
+}
+
+/// Parses a time zone to its offset from UTC in seconds.
+///
+/// Unrecognised zone names are taken to be UTC.
+fn parse_zone(value: &str) -> Option<i64> {
+    let offset_hours = match value.to_ascii_uppercase().as_str() {
+        "" | "Z" | "UT" | "UTC" | "GMT" => 0,
+        "EDT" => -4,
+        "EST" | "CDT" => -5,
+        "CST" | "MDT" => -6,
+        "MST" | "PDT" => -7,
+        "PST" => -8,
+        zone if zone.starts_with(['+', '-']) => {
+            let sign = if zone.starts_with('-') { -1 } else { 1 };
+            let digits: String = zone[1..].chars().filter(char::is_ascii_digit).collect();
+            if digits.len() != 4 {
+                return None;
+            }
+            let hours: i64 = digits[..2].parse().ok()?;
+            let mins: i64 = digits[2..].parse().ok()?;
+            return Some(sign * (hours * 3600 + mins * 60));
+        }
+        _ => 0,
+    };
+    Some(offset_hours * 3600)
+}
+
+/// Returns the current time as a Unix timestamp.
+pub(crate) fn now_timestamp() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn civil_dates_round_trip() {
+        for days in [-719_468, -1, 0, 1, 11_016, 19_782, 2_932_896] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(date_from_timestamp(1_709_164_800), (2024, 2, 29));
+        assert_eq!(date_from_timestamp(-1), (1969, 12, 31));
+    }
+
+    #[test]
+    fn parses_rfc2822() {
+        assert_eq!(parse_rfc2822("Fri, 01 Mar 2024 12:30:15 +0000"), Some(1_709_296_215));
+        assert_eq!(parse_rfc2822("1 Mar 2024 12:30:15 GMT"), Some(1_709_296_215));
+        assert_eq!(parse_rfc2822("Fri, 01 March 2024 12:30:15"), Some(1_709_296_215));
+        assert_eq!(parse_rfc2822("Fri, 01 Mar 2024 12:30 +0000"), Some(1_709_296_200));
+        assert_eq!(parse_rfc2822("Fri, 01 Mar 2024 13:30:15 +0100"), Some(1_709_296_215));
+        assert_eq!(parse_rfc2822("Fri, 01 Mar 2024 07:30:15 EST"), Some(1_709_296_215));
+        assert_eq!(parse_rfc2822("Fri, 01 Mar 2024 04:30:15 PST"), Some(1_709_296_215));
+        assert_eq!(parse_rfc2822("Fri, 01 Mar 2024 12:30:15 XYZ"), Some(1_709_296_215));
+    }
+
+    #[test]
+    fn parses_rfc2822_two_digit_years() {
+        assert_eq!(parse_rfc2822("Fri, 31 Dec 99 23:59:00 +0000"), Some(946_684_740));
+        assert_eq!(parse_rfc2822("Fri, 01 Mar 24 12:30:15 +0000"), Some(1_709_296_215));
+    }
+
+    #[test]
+    fn rejects_invalid_rfc2822() {
+        assert_eq!(parse_rfc2822(""), None);
+        assert_eq!(parse_rfc2822("yesterday"), None);
+        assert_eq!(parse_rfc2822("Fri, 01 Foo 2024 12:30:15 +0000"), None);
+        assert_eq!(parse_rfc2822("Fri, 01 Mar 2024"), None);
+        assert_eq!(parse_rfc2822("Fri, 01 Mar 2024 noon +0000"), None);
+        assert_eq!(parse_rfc2822("Fri, 01 Mar 2024 12:30:15 +01"), None);
+    }
+
+    #[test]
+    fn parses_rfc3339() {
+        assert_eq!(parse_rfc3339("2024-03-01T12:30:15Z"), Some(1_709_296_215));
+        assert_eq!(parse_rfc3339("2024-03-01t12:30:15z"), Some(1_709_296_215));
+        assert_eq!(parse_rfc3339("2024-03-01 12:30:15Z"), Some(1_709_296_215));
+        assert_eq!(parse_rfc3339("2024-03-01T12:30:15.250Z"), Some(1_709_296_215));
+        assert_eq!(parse_rfc3339("2024-03-01T14:30:15+02:00"), Some(1_709_296_215));
+        assert_eq!(parse_rfc3339("2024-03-01T07:30:15-05:00"), Some(1_709_296_215));
+        assert_eq!(parse_rfc3339(" 2024-02-29 "), Some(1_709_164_800));
+    }
+
+    #[test]
+    fn rejects_invalid_rfc3339() {
+        assert_eq!(parse_rfc3339(""), None);
+        assert_eq!(parse_rfc3339("2024-03"), None);
+        assert_eq!(parse_rfc3339("01/03/2024"), None);
+        assert_eq!(parse_rfc3339("2024-03-01T12"), None);
+        assert_eq!(parse_rfc3339("2024-03-01T12:30:15+2"), None);
+    }
+}
@@ -13,6 +13,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::time::Duration;
+
+use crate::util::date::date_from_timestamp;
+
 pub(crate) enum TimeFormat {
     Minutes,
     Hours,
@@ -52,3 +56,124 @@ pub(crate) fn format_time(total_seconds: u64, style: TimeFormat) -> String {
         }
     }
 }
+
+/// Formats a long duration in seconds, such as the total length of a library,
+/// as days, hours and minutes.
+///
+/// # Examples
+///
+/// ```ignore
+/// assert_eq!(format_long_duration(93_780), "1d 2h 3m");
+/// assert_eq!(format_long_duration(300), "5m");
+/// ```
+pub(crate) fn format_long_duration(total_seconds: u64) -> String {
+    let days = total_seconds / 86_400;
+    let hours = (total_seconds % 86_400) / 3600;
+    let mins = (total_seconds % 3600) / 60;
+
+    if days > 0 {
+        format!("{days}d {hours}h {mins}m")
+    } else if hours > 0 {
+        format!("{hours}h {mins}m")
+    } else {
+        format!("{mins}m")
+    }
+}
+
+/// Formats a Unix timestamp as an ISO 8601 `YYYY-MM-DD` date (in UTC).
+pub(crate) fn format_date(timestamp: i64) -> String {
+    let (year, month, day) = date_from_timestamp(timestamp);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Formats a Unix timestamp as an ISO 8601 `YYYY-MM-DD HH:MM` date and time
+/// (in UTC).
+pub(crate) fn format_datetime(timestamp: i64) -> String {
+    let secs_of_day = timestamp.rem_euclid(86_400);
+    format!(
+        "{} {:02}:{:02}",
+        format_date(timestamp),
+        secs_of_day / 3600,
+        (secs_of_day % 3600) / 60
+    )
+}
+
+/// Parses a human-friendly duration such as `30m`, `1h30m`, `90s`, `7d` or
+/// `45`.
+///
+/// A number without a unit is taken to be a number of minutes. Returns `None`
+/// if the text is not a duration, or the duration is too long to represent.
+///
+/// # Arguments
+///
+/// * `value` - The text to parse.
+///
+/// # Examples
+///
+/// ```ignore
+/// assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(5400)));
+/// assert_eq!(parse_duration("45"), Some(Duration::from_secs(2700)));
+/// ```
+pub(crate) fn parse_duration(value: &str) -> Option<Duration> {
+    if let Ok(mins) = value.parse::<u64>() {
+        return mins.checked_mul(60).map(Duration::from_secs);
+    }
+
+    let mut total: u64 = 0;
+    let mut number = String::new();
+
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let amount: u64 = number.parse().ok()?;
+        number.clear();
+
+        let unit_secs = match c.to_ascii_lowercase() {
+            'w' => 7 * 86_400,
+            'd' => 86_400,
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        total = total.checked_add(amount.checked_mul(unit_secs)?)?;
+    }
+
+    if !number.is_empty() || total == 0 {
+        return None;
+    }
+
+    Some(Duration::from_secs(total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("45"), Some(Duration::from_secs(2700)));
+        assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("90s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("1W2D"), Some(Duration::from_secs(9 * 86_400)));
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("0m"), None);
+        assert_eq!(parse_duration("1h30"), None);
+        assert_eq!(parse_duration("1x"), None);
+        assert_eq!(parse_duration("h"), None);
+    }
+
+    #[test]
+    fn rejects_overflowing_durations() {
+        assert_eq!(parse_duration(&u64::MAX.to_string()), None);
+        assert_eq!(parse_duration(&format!("{}w", u64::MAX / 86_400)), None);
+        assert_eq!(parse_duration(&format!("{}s1s", u64::MAX)), None);
+    }
+}
@@ -20,10 +20,16 @@
 //!
 //! # Sub-modules
 //!
+//! * [`date`]: Calendar date conversion and parsing.
 //! * [`format`]: Logic for converting raw data into human-readable strings for
 //!   the UI.
+//! * [`path_template`]: Naming files from their tags.
 //! * [`terminal`]: Low-level utilities for interacting with the terminal
 //!   emulator, such as color control and raw mode management.
 
+pub(crate) mod date;
 pub(crate) mod format;
+pub(crate) mod path_template;
 pub(crate) mod term;
+#[cfg(test)]
+pub(crate) mod test_server;
@@ -0,0 +1,159 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Path templates, for naming files from their tags.
+//!
+//! A template is a relative path with fields in braces, such as
+//! `{albumartist}/{year} - {album}/{track:02} {title}.{ext}`. A field may give
+//! a width to zero pad its value to, and values are sanitised so that they can
+//! not add directories or characters that are not allowed in file names.
+
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+
+/// Characters replaced in field values, as they are not allowed in file names
+/// on at least one common platform.
+const ILLEGAL_CHARS: &[char] = &['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+enum Part {
+    Text(String),
+    Field { name: String, width: usize },
+}
+
+/// A parsed path template.
+pub(crate) struct PathTemplate {
+    components: Vec<Vec<Part>>,
+}
+
+impl PathTemplate {
+    /// Parses a template.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The template text.
+    /// * `fields` - The names of the fields the template may use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template is not a relative path, a brace is not
+    /// closed, or a field is not one of the given fields.
+    pub(crate) fn parse(template: &str, fields: &[&str]) -> Result<Self> {
+        if template.starts_with('/') || template.split('/').any(|c| c.trim() == "..") {
+            return Err(anyhow!("Template must be a relative path: {}", template));
+        }
+
+        let components = template
+            .split('/')
+            .map(|component| parse_component(component, fields))
+            .collect::<Result<_>>()?;
+
+        Ok(Self { components })
+    }
+
+    /// Renders the template, looking up the value of each field.
+    ///
+    /// Components that render empty are left out, so the result is empty if
+    /// every component is.
+    pub(crate) fn render<F>(&self, value: F) -> PathBuf
+    where
+        F: Fn(&str) -> String,
+    {
+        self.components
+            .iter()
+            .map(|parts| {
+                let text: String = parts
+                    .iter()
+                    .map(|part| match part {
+                        Part::Text(text) => text.clone(),
+                        Part::Field { name, width } => {
+                            let value = sanitise(&value(name));
+                            if value.is_empty() {
+                                value
+                            } else {
+                                format!("{:0>width$}", value, width = width)
+                            }
+                        }
+                    })
+                    .collect();
+                // Trailing dots are not allowed on some platforms, and would
+                // allow a value of ".."
+                text.trim_start()
+                    .trim_end_matches(|c: char| c == '.' || c.is_whitespace())
+                    .to_string()
+            })
+            .filter(|component| !component.is_empty())
+            .collect()
+    }
+}
+
+fn parse_component(component: &str, fields: &[&str]) -> Result<Vec<Part>> {
+    let mut parts = vec![];
+    let mut rest = component;
+
+    while let Some(start) = rest.find('{') {
+        if start > 0 {
+            parts.push(Part::Text(rest[..start].to_string()));
+        }
+
+        let end = rest[start..]
+            .find('}')
+            .map(|end| start + end)
+            .ok_or_else(|| anyhow!("Unclosed brace in template: {}", component))?;
+
+        let field = &rest[start + 1..end];
+        let (name, width) = match field.split_once(':') {
+            Some((name, width)) => {
+                let width = width
+                    .parse()
+                    .map_err(|_| anyhow!("Invalid width in template: {}", field))?;
+                (name, width)
+            }
+            None => (field, 0),
+        };
+
+        if !fields.contains(&name) {
+            return Err(anyhow!("Unknown field in template: {}", name));
+        }
+
+        parts.push(Part::Field {
+            name: name.to_string(),
+            width,
+        });
+
+        rest = &rest[end + 1..];
+    }
+
+    if !rest.is_empty() {
+        parts.push(Part::Text(rest.to_string()));
+    }
+
+    Ok(parts)
+}
+
+/// Replaces characters that are not allowed in file names.
+fn sanitise(value: &str) -> String {
+    value
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_control() || ILLEGAL_CHARS.contains(&c) {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
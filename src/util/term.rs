@@ -17,7 +17,8 @@
 //!
 //! This module provides functions to manipulate the terminal emulator's
 //! properties, such as background colors, using OSC (Operating System Command)
-//! escape sequences.
+//! escape sequences, and detects which graphics protocol, if any, images can be
+//! drawn with.
 //!
 //! # Compatibility
 //!
@@ -25,7 +26,10 @@
 //! codes. Most modern terminals (XTerm, iTerm2, Alacritty, Kitty) support these
 //! sequences.
 
-use std::io::{self, Write};
+use std::{
+    env,
+    io::{self, Write},
+};
 
 /// Sets the terminal background color using an OSC 11 escape sequence.
 ///
@@ -58,3 +62,70 @@ pub(crate) fn reset_terminal_bg() {
     print!("\x1b]111\x07");
     io::stdout().flush().unwrap();
 }
+
+/// A way of drawing images in the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GraphicsProtocol {
+    /// The kitty graphics protocol, also spoken by Ghostty and WezTerm.
+    Kitty,
+
+    /// DEC sixel graphics.
+    Sixel,
+
+    /// iTerm2 inline images.
+    Iterm2,
+
+    /// Unicode upper half blocks, two pixels to a cell, which works anywhere
+    /// with true colour.
+    HalfBlocks,
+}
+
+/// Picks the graphics protocol to draw album art with.
+///
+/// The `setting` is the configured protocol: `"kitty"`, `"sixel"`,
+/// `"iterm2"`, `"halfblocks"`, `"none"` to not draw album art at all, or
+/// `"auto"` to detect it.
+///
+/// There is no reliable way to query graphics support without reading the
+/// terminal's replies from stdin, so detection goes by the environment
+/// variables the terminals themselves set. Anything unrecognised, and
+/// anything inside tmux or screen, which would need the escape sequences
+/// wrapping in passthrough, falls back to half blocks.
+pub(crate) fn detect_graphics_protocol(setting: &str) -> Option<GraphicsProtocol> {
+    match setting.to_ascii_lowercase().as_str() {
+        "kitty" => return Some(GraphicsProtocol::Kitty),
+        "sixel" => return Some(GraphicsProtocol::Sixel),
+        "iterm2" => return Some(GraphicsProtocol::Iterm2),
+        "halfblocks" => return Some(GraphicsProtocol::HalfBlocks),
+        "none" => return None,
+        _ => {}
+    }
+
+    let var = |name: &str| env::var(name).unwrap_or_default();
+
+    let term = var("TERM");
+    let term_program = var("TERM_PROGRAM");
+
+    if !var("TMUX").is_empty() || term.starts_with("screen") || term.starts_with("tmux") {
+        return Some(GraphicsProtocol::HalfBlocks);
+    }
+
+    let protocol = if !var("KITTY_WINDOW_ID").is_empty()
+        || term.contains("kitty")
+        || term.contains("ghostty")
+        || matches!(term_program.as_str(), "ghostty" | "WezTerm")
+    {
+        GraphicsProtocol::Kitty
+    } else if term_program == "iTerm.app" || var("LC_TERMINAL") == "iTerm2" {
+        GraphicsProtocol::Iterm2
+    } else if ["foot", "mlterm", "contour", "yaft"]
+        .iter()
+        .any(|name| term.starts_with(name))
+    {
+        GraphicsProtocol::Sixel
+    } else {
+        GraphicsProtocol::HalfBlocks
+    };
+
+    Some(protocol)
+}
@@ -0,0 +1,96 @@
+// Copyright (C) 2026  Caprica Software Limited
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! A minimal local HTTP server, standing in for web services in tests.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::mpsc,
+    thread,
+};
+
+/// A request received by a [`serve`] server.
+pub(crate) struct Request {
+    pub(crate) method: String,
+    pub(crate) path: String,
+    /// The header lines, as sent.
+    pub(crate) headers: Vec<String>,
+    pub(crate) body: Vec<u8>,
+}
+
+/// Starts a server that answers every request with the status and body chosen
+/// by `respond`.
+///
+/// Returns the base URL of the server and a receiver for the requests it has
+/// answered.
+pub(crate) fn serve(
+    respond: impl Fn(&Request) -> (u16, Vec<u8>) + Send + 'static,
+) -> (String, mpsc::Receiver<Request>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (request_tx, request_rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for socket in listener.incoming() {
+            let mut socket = socket.unwrap();
+            let mut reader = BufReader::new(&socket);
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut request_line = request_line.split_whitespace();
+            let method = request_line.next().unwrap_or_default().to_string();
+            let path = request_line.next().unwrap_or_default().to_string();
+
+            let headers: Vec<String> = reader
+                .by_ref()
+                .lines()
+                .map_while(|line| line.ok())
+                .take_while(|line| !line.is_empty())
+                .collect();
+            let len = headers
+                .iter()
+                .find_map(|header| {
+                    let (name, value) = header.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse().ok())?
+                })
+                .unwrap_or(0);
+            let mut body = vec![0u8; len];
+            reader.read_exact(&mut body).unwrap();
+
+            let request = Request {
+                method,
+                path,
+                headers,
+                body,
+            };
+
+            let (status, body) = respond(&request);
+            let _ = socket.write_all(
+                format!(
+                    "HTTP/1.1 {} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    body.len()
+                )
+                .as_bytes(),
+            );
+            let _ = socket.write_all(&body);
+            let _ = request_tx.send(request);
+        }
+    });
+
+    (url, request_rx)
+}